@@ -0,0 +1,362 @@
+//! Timings of whole solutions.
+//!
+//! Every run parses the input and solves one part, so a timing covers
+//! everything a day does. Timings can be saved as a JSON baseline and later
+//! timings compared against it:
+//!
+//! ```json
+//! {
+//!   "runs": 10,
+//!   "timings": [
+//!     { "day": 1, "part": 1, "min_ns": 81200, "median_ns": 83100, "max_ns": 95400 }
+//!   ]
+//! }
+//! ```
+
+use crate::solution::{
+    Entry,
+    Part,
+    SolutionError,
+};
+use serde_json::{
+    json,
+    Value,
+};
+use std::{
+    path::Path,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+/// Runs of every part if not given otherwise.
+pub const RUNS: usize = 10;
+
+/// Percent a median may grow over the baseline before it is a regression.
+pub const THRESHOLD: u32 = 10;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Timing {
+    pub day: usize,
+    pub part: Part,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Baseline {
+    pub runs: usize,
+    pub timings: Vec<Timing>,
+}
+
+/// A timing next to the timing of the same day and part in a baseline.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Comparison {
+    pub timing: Timing,
+    pub baseline: Option<Timing>,
+}
+
+#[derive(Debug)]
+pub enum BenchError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    InvalidBaseline(String),
+}
+
+impl From<std::io::Error> for BenchError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for BenchError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+impl std::fmt::Display for BenchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{}", err),
+            Self::Json(err) => write!(f, "invalid baseline: {}", err),
+            Self::InvalidBaseline(message) => write!(f, "invalid baseline: {}", message),
+        }
+    }
+}
+
+/// Parses `input` and solves `part` `runs` times.
+pub fn measure(
+    entry: &Entry,
+    input: &str,
+    part: Part,
+    runs: usize,
+) -> Result<Timing, SolutionError> {
+    let mut durations = Vec::with_capacity(runs);
+
+    for _ in 0..runs.max(1) {
+        let start = Instant::now();
+        test::black_box(entry.parse(input)?.part(part)?);
+        durations.push(start.elapsed());
+    }
+
+    Ok(Timing::from_durations(entry.day, part, durations))
+}
+
+impl Timing {
+    /// Summarizes the durations of at least one run. The median of an even
+    /// number of runs is the upper one of both middle runs.
+    pub fn from_durations(day: usize, part: Part, mut durations: Vec<Duration>) -> Self {
+        durations.sort();
+
+        Self {
+            day,
+            part,
+            min: durations[0],
+            median: durations[durations.len() / 2],
+            max: durations[durations.len() - 1],
+        }
+    }
+
+    pub fn to_json(self) -> Value {
+        json!({
+            "day": self.day,
+            "part": self.part.number(),
+            "min_ns": nanos(self.min),
+            "median_ns": nanos(self.median),
+            "max_ns": nanos(self.max),
+        })
+    }
+
+    fn from_json(value: &Value) -> Result<Self, BenchError> {
+        let number = |key: &str| {
+            value[key].as_u64().ok_or_else(|| {
+                BenchError::InvalidBaseline(format!("missing `{}` in {}", key, value))
+            })
+        };
+
+        let part = number("part")?;
+
+        Ok(Self {
+            day: number("day")? as usize,
+            part: Part::from_number(part as usize)
+                .ok_or_else(|| BenchError::InvalidBaseline(format!("invalid part {}", part)))?,
+            min: Duration::from_nanos(number("min_ns")?),
+            median: Duration::from_nanos(number("median_ns")?),
+            max: Duration::from_nanos(number("max_ns")?),
+        })
+    }
+}
+
+fn nanos(duration: Duration) -> u64 {
+    duration.as_nanos() as u64
+}
+
+impl Baseline {
+    pub fn get(&self, day: usize, part: Part) -> Option<Timing> {
+        self.timings
+            .iter()
+            .find(|timing| timing.day == day && timing.part == part)
+            .copied()
+    }
+
+    /// Compares every timing against this baseline.
+    pub fn compare(&self, timings: &[Timing]) -> Vec<Comparison> {
+        timings
+            .iter()
+            .map(|timing| Comparison {
+                timing: *timing,
+                baseline: self.get(timing.day, timing.part),
+            })
+            .collect()
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "runs": self.runs,
+            "timings": self.timings.iter().map(|timing| timing.to_json()).collect::<Vec<_>>(),
+        })
+    }
+
+    pub fn from_json(value: &Value) -> Result<Self, BenchError> {
+        let timings = value["timings"]
+            .as_array()
+            .ok_or_else(|| BenchError::InvalidBaseline("missing `timings`".to_string()))?;
+
+        Ok(Self {
+            runs: value["runs"].as_u64().unwrap_or(0) as usize,
+            timings: timings
+                .iter()
+                .map(Timing::from_json)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), BenchError> {
+        std::fs::write(path, serde_json::to_string_pretty(&self.to_json())? + "\n")?;
+
+        Ok(())
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, BenchError> {
+        Self::from_json(&serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+}
+
+impl Comparison {
+    /// Change of the median against the baseline in percent.
+    pub fn change(&self) -> Option<f64> {
+        self.baseline.map(|baseline| {
+            let before = baseline.median.as_secs_f64();
+            let after = self.timing.median.as_secs_f64();
+
+            if before == 0.0 {
+                0.0
+            } else {
+                (after - before) / before * 100.0
+            }
+        })
+    }
+
+    /// The timing with the median of the baseline, the change and whether it
+    /// is a regression.
+    pub fn to_json(&self, threshold: u32) -> Value {
+        let mut value = self.timing.to_json();
+
+        value["baseline_median_ns"] = json!(self.baseline.map(|baseline| nanos(baseline.median)));
+        value["change_percent"] = json!(self.change());
+        value["regression"] = json!(self.is_regression(threshold));
+
+        value
+    }
+
+    /// Whether the median grew more than `threshold` percent.
+    pub fn is_regression(&self, threshold: u32) -> bool {
+        self.change()
+            .is_some_and(|change| change > f64::from(threshold))
+    }
+}
+
+impl std::fmt::Display for Timing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "day {:>2} part {}: min {:>12?} median {:>12?} max {:>12?}",
+            self.day, self.part, self.min, self.median, self.max
+        )
+    }
+}
+
+impl std::fmt::Display for Comparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.timing)?;
+
+        match (self.baseline, self.change()) {
+            (Some(baseline), Some(change)) => {
+                write!(f, " baseline {:>12?} {:>+7.1}%", baseline.median, change)
+            }
+            _ => write!(f, " baseline {:>12}", "-"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Baseline,
+        Comparison,
+        Timing,
+    };
+    use crate::solution::{
+        self,
+        Part,
+    };
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn timing(day: usize, part: Part, median: u64) -> Timing {
+        Timing {
+            day,
+            part,
+            min: Duration::from_millis(median - 1),
+            median: Duration::from_millis(median),
+            max: Duration::from_millis(median + 1),
+        }
+    }
+
+    #[test]
+    fn from_durations() {
+        let durations = [5, 1, 4, 2, 3, 6]
+            .iter()
+            .map(|millis| Duration::from_millis(*millis))
+            .collect();
+
+        assert_eq!(
+            Timing {
+                day: 4,
+                part: Part::Two,
+                min: Duration::from_millis(1),
+                median: Duration::from_millis(4),
+                max: Duration::from_millis(6),
+            },
+            Timing::from_durations(4, Part::Two, durations)
+        );
+    }
+
+    #[test]
+    fn measure_runs() {
+        let entry = solution::get(4).unwrap();
+        let timing = super::measure(entry, "111110-111112", Part::One, 3).unwrap();
+
+        assert_eq!((4, Part::One), (timing.day, timing.part));
+        assert!(timing.min <= timing.median && timing.median <= timing.max);
+        assert!(super::measure(entry, "111110", Part::One, 3).is_err());
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let baseline = Baseline {
+            runs: 5,
+            timings: vec![timing(1, Part::One, 10), timing(13, Part::Two, 2000)],
+        };
+
+        assert_eq!(baseline, Baseline::from_json(&baseline.to_json()).unwrap());
+        assert!(Baseline::from_json(&serde_json::json!({ "runs": 5 })).is_err());
+        assert!(Baseline::from_json(&serde_json::json!({
+            "timings": [{ "day": 1, "part": 3, "min_ns": 1, "median_ns": 1, "max_ns": 1 }]
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn compare_flags_regressions() {
+        let baseline = Baseline {
+            runs: 5,
+            timings: vec![timing(1, Part::One, 100), timing(1, Part::Two, 100)],
+        };
+
+        let comparisons = baseline.compare(&[
+            timing(1, Part::One, 105),
+            timing(1, Part::Two, 120),
+            timing(2, Part::One, 100),
+        ]);
+
+        assert_eq!(
+            vec![false, true, false],
+            comparisons
+                .iter()
+                .map(|comparison| comparison.is_regression(10))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Comparison {
+                timing: timing(2, Part::One, 100),
+                baseline: None,
+            },
+            comparisons[2]
+        );
+        assert_eq!(Some(20.0), comparisons[1].change().map(f64::round));
+    }
+}
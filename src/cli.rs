@@ -0,0 +1,593 @@
+//! Command-line interface of the solutions binary.
+//!
+//! ```text
+//! advent_of_code_2019 run [day|all] [--part 1|2] [--input <file>] [--journal <file>] [--visualize] [--quiet]
+//! advent_of_code_2019 list
+//! advent_of_code_2019 bench [day] [--runs <n>] [--save <file>] [--baseline <file>]
+//! advent_of_code_2019 verify [day] [--answers <file>] [--save <file>]
+//! ```
+//!
+//! Days are given as `13` or `day_13`. Without `--input` a day solves
+//! `inputs/day_NN.txt`, or the input compiled into the binary if there is no
+//! such file. `--format json` prints one JSON record per day and part
+//! instead of text.
+
+use crate::{
+    bench::{
+        self,
+        BenchError,
+    },
+    input::InputError,
+    output::Format,
+    solution::{
+        self,
+        Part,
+        SolutionError,
+    },
+    verify::VerifyError,
+};
+use std::path::PathBuf;
+
+pub const USAGE: &str = "usage: advent_of_code_2019 <command> [options]
+
+commands:
+    run [day|all]             solve both parts of every day or one day
+    list                      show the days with a solution
+    bench [day]               time every day or one day
+    verify [day]              compare the answers with the expected ones
+    dap [address]             serve the debug adapter protocol
+    gdb <program> [address] [inputs...]
+                              serve the GDB remote protocol
+    visualize [program]       show the memory of a running computer
+    decompile [program]       print software as pseudo-code
+    diagnostics               run the self-tests of the computer
+    replay <journal>          replay a recorded run
+
+options:
+    --part <1|2>              only solve one part
+    --input <file>            solve this input instead of inputs/day_NN.txt
+    --inputs <directory>      read inputs from this directory
+    --visualize               open canvases of days that draw
+    --journal <file>          record the run of the computer to replay it,
+                              as <file>.day_NN.part_N.computer_N when
+                              several run
+    --runs <n>                runs of every part to time, default 10
+    --save <file>             save the timings as a JSON baseline, or
+                              record the answers found as expected ones,
+                              still failing if any differed
+    --baseline <file>         compare the timings against a baseline
+    --threshold <percent>     slowdown of a regression, default 10
+    --answers <file>          expected answers instead of inputs/answers.json
+    --format <text|json>      print text or one JSON record per part
+    -q, --quiet               only print the answers
+    -h, --help                show this message";
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Command {
+    Run {
+        /// Every day if not given.
+        day: Option<usize>,
+        part: Option<Part>,
+        /// Journal to record the computer of the day in.
+        journal: Option<PathBuf>,
+    },
+    List,
+    Bench {
+        day: Option<usize>,
+        part: Option<Part>,
+        runs: usize,
+        save: Option<PathBuf>,
+        baseline: Option<PathBuf>,
+        /// Percent a median may grow over the baseline.
+        threshold: u32,
+    },
+    Verify {
+        day: Option<usize>,
+        part: Option<Part>,
+        answers: Option<PathBuf>,
+        save: Option<PathBuf>,
+    },
+    Dap(Option<String>),
+    Gdb {
+        program: PathBuf,
+        address: Option<String>,
+        inputs: Vec<isize>,
+    },
+    Visualize(Option<PathBuf>),
+    Decompile(Option<PathBuf>),
+    Diagnostics,
+    Replay(PathBuf),
+    Help,
+}
+
+/// Options that apply to every command.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Options {
+    pub input: Option<PathBuf>,
+    /// Directory with the inputs of every day.
+    pub inputs: Option<PathBuf>,
+    pub visualize: bool,
+    pub quiet: bool,
+    pub format: Format,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Arguments {
+    pub command: Command,
+    pub options: Options,
+}
+
+#[derive(Debug)]
+pub enum CliError {
+    MissingCommand,
+    UnknownCommand(String),
+    UnknownDay(String),
+    InvalidPart(String),
+    UnknownOption(String),
+    MissingValue(&'static str),
+    MissingArgument(&'static str),
+    InvalidArgument(String),
+    Io(std::io::Error),
+    Input(InputError),
+    Solution(SolutionError),
+    Bench(BenchError),
+    Verify(VerifyError),
+    /// A tool command failed.
+    Failed(String),
+}
+
+impl From<std::io::Error> for CliError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<SolutionError> for CliError {
+    fn from(err: SolutionError) -> Self {
+        Self::Solution(err)
+    }
+}
+
+impl From<BenchError> for CliError {
+    fn from(err: BenchError) -> Self {
+        Self::Bench(err)
+    }
+}
+
+impl From<VerifyError> for CliError {
+    fn from(err: VerifyError) -> Self {
+        Self::Verify(err)
+    }
+}
+
+impl From<InputError> for CliError {
+    fn from(err: InputError) -> Self {
+        Self::Input(err)
+    }
+}
+
+impl CliError {
+    /// Whether the command line was wrong, as opposed to solving failing.
+    pub fn is_usage(&self) -> bool {
+        !matches!(
+            self,
+            Self::Io(_)
+                | Self::Input(_)
+                | Self::Solution(_)
+                | Self::Bench(_)
+                | Self::Verify(_)
+                | Self::Failed(_)
+        )
+    }
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingCommand => write!(f, "missing command"),
+            Self::UnknownCommand(command) => write!(f, "unknown command `{}`", command),
+            Self::UnknownDay(day) => write!(
+                f,
+                "unknown day `{}`, available days: {}",
+                day,
+                solution::days()
+                    .map(|day| day.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::InvalidPart(part) => write!(f, "invalid part `{}`, expected 1 or 2", part),
+            Self::UnknownOption(option) => write!(f, "unknown option `{}`", option),
+            Self::MissingValue(option) => write!(f, "missing value for `{}`", option),
+            Self::MissingArgument(argument) => write!(f, "missing argument <{}>", argument),
+            Self::InvalidArgument(argument) => write!(f, "invalid argument `{}`", argument),
+            Self::Io(err) => write!(f, "{}", err),
+            Self::Input(err) => write!(f, "{}", err),
+            Self::Solution(err) => write!(f, "{}", err),
+            Self::Bench(err) => write!(f, "{}", err),
+            Self::Verify(err) => write!(f, "{}", err),
+            Self::Failed(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+/// Parses the arguments without the name of the binary. Options may appear
+/// anywhere.
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Arguments, CliError> {
+    let mut options = Options::default();
+    let mut part = None;
+    let mut runs = bench::RUNS;
+    let mut save = None;
+    let mut baseline = None;
+    let mut threshold = bench::THRESHOLD;
+    let mut answers = None;
+    let mut journal = None;
+    let mut positional = Vec::new();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let (name, value) = match arg.find('=') {
+            Some(index) if arg.starts_with("--") => {
+                (&arg[..index], Some(arg[index + 1..].to_string()))
+            }
+            _ => (arg.as_str(), None),
+        };
+
+        match name {
+            "--part" => {
+                let value = value
+                    .or_else(|| args.next())
+                    .ok_or(CliError::MissingValue("--part"))?;
+                part = Some(parse_part(&value)?);
+            }
+            "--input" => {
+                let value = value
+                    .or_else(|| args.next())
+                    .ok_or(CliError::MissingValue("--input"))?;
+                options.input = Some(PathBuf::from(value));
+            }
+            "--inputs" => {
+                let value = value
+                    .or_else(|| args.next())
+                    .ok_or(CliError::MissingValue("--inputs"))?;
+                options.inputs = Some(PathBuf::from(value));
+            }
+            "--runs" => {
+                let value = value
+                    .or_else(|| args.next())
+                    .ok_or(CliError::MissingValue("--runs"))?;
+                runs = value
+                    .parse()
+                    .ok()
+                    .filter(|runs| *runs > 0)
+                    .ok_or(CliError::InvalidArgument(value))?;
+            }
+            "--save" => {
+                let value = value
+                    .or_else(|| args.next())
+                    .ok_or(CliError::MissingValue("--save"))?;
+                save = Some(PathBuf::from(value));
+            }
+            "--baseline" => {
+                let value = value
+                    .or_else(|| args.next())
+                    .ok_or(CliError::MissingValue("--baseline"))?;
+                baseline = Some(PathBuf::from(value));
+            }
+            "--threshold" => {
+                let value = value
+                    .or_else(|| args.next())
+                    .ok_or(CliError::MissingValue("--threshold"))?;
+                threshold = value
+                    .parse()
+                    .map_err(|_| CliError::InvalidArgument(value))?;
+            }
+            "--answers" => {
+                let value = value
+                    .or_else(|| args.next())
+                    .ok_or(CliError::MissingValue("--answers"))?;
+                answers = Some(PathBuf::from(value));
+            }
+            "--journal" => {
+                let value = value
+                    .or_else(|| args.next())
+                    .ok_or(CliError::MissingValue("--journal"))?;
+                journal = Some(PathBuf::from(value));
+            }
+            "--format" => {
+                let value = value
+                    .or_else(|| args.next())
+                    .ok_or(CliError::MissingValue("--format"))?;
+                options.format = value.parse().map_err(CliError::InvalidArgument)?;
+            }
+            "--visualize" => options.visualize = true,
+            "-q" | "--quiet" => options.quiet = true,
+            "-h" | "--help" => {
+                return Ok(Arguments {
+                    command: Command::Help,
+                    options,
+                })
+            }
+            name if name.starts_with("--") => return Err(CliError::UnknownOption(arg)),
+            _ => positional.push(arg),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let command = positional.next().ok_or(CliError::MissingCommand)?;
+
+    let command = match command.as_str() {
+        "run" => Command::Run {
+            day: match positional.next().as_deref() {
+                None | Some("all") => None,
+                Some(day) => Some(parse_day(day)?),
+            },
+            part,
+            journal,
+        },
+        "list" => Command::List,
+        "bench" => Command::Bench {
+            day: positional.next().as_deref().map(parse_day).transpose()?,
+            part,
+            runs,
+            save,
+            baseline,
+            threshold,
+        },
+        "verify" => Command::Verify {
+            day: positional.next().as_deref().map(parse_day).transpose()?,
+            part,
+            answers,
+            save,
+        },
+        "dap" => Command::Dap(positional.next()),
+        "gdb" => Command::Gdb {
+            program: positional
+                .next()
+                .map(PathBuf::from)
+                .ok_or(CliError::MissingArgument("program"))?,
+            address: positional.next(),
+            inputs: positional
+                .by_ref()
+                .map(|input| input.parse().map_err(|_| CliError::InvalidArgument(input)))
+                .collect::<Result<_, _>>()?,
+        },
+        "visualize" => Command::Visualize(positional.next().map(PathBuf::from)),
+        "decompile" => Command::Decompile(positional.next().map(PathBuf::from)),
+        "diagnostics" => Command::Diagnostics,
+        "replay" => Command::Replay(
+            positional
+                .next()
+                .map(PathBuf::from)
+                .ok_or(CliError::MissingArgument("journal"))?,
+        ),
+        "help" => Command::Help,
+        _ => return Err(CliError::UnknownCommand(command)),
+    };
+
+    if let Some(arg) = positional.next() {
+        return Err(CliError::InvalidArgument(arg));
+    }
+
+    Ok(Arguments { command, options })
+}
+
+/// Parses a day like `13` or `day_13`.
+pub fn parse_day(day: &str) -> Result<usize, CliError> {
+    day.trim_start_matches("day_")
+        .parse()
+        .ok()
+        .filter(|number| solution::get(*number).is_some())
+        .ok_or_else(|| CliError::UnknownDay(day.to_string()))
+}
+
+pub fn parse_part(part: &str) -> Result<Part, CliError> {
+    part.parse()
+        .ok()
+        .and_then(Part::from_number)
+        .ok_or_else(|| CliError::InvalidPart(part.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse,
+        Arguments,
+        CliError,
+        Command,
+        Options,
+    };
+    use crate::{
+        output::Format,
+        solution::Part,
+    };
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parse_run() {
+        assert_eq!(
+            Arguments {
+                command: Command::Run {
+                    day: Some(13),
+                    part: Some(Part::Two),
+                    journal: None,
+                },
+                options: Options {
+                    input: Some(PathBuf::from("inputs/day_13.txt")),
+                    inputs: None,
+                    visualize: true,
+                    quiet: true,
+                    format: Format::Text,
+                },
+            },
+            parse(args(
+                "--quiet run day_13 --part 2 --input inputs/day_13.txt --visualize"
+            ))
+            .unwrap()
+        );
+
+        assert_eq!(
+            Command::Run {
+                day: Some(4),
+                part: None,
+                journal: None,
+            },
+            parse(args("run 4")).unwrap().command
+        );
+        assert_eq!(
+            Command::Run {
+                day: Some(9),
+                part: Some(Part::One),
+                journal: Some(PathBuf::from("boost.journal")),
+            },
+            parse(args("run 9 --part 1 --journal boost.journal"))
+                .unwrap()
+                .command
+        );
+        assert_eq!(
+            Format::Json,
+            parse(args("run 8 --format json")).unwrap().options.format
+        );
+
+        for line in &["run", "run all --format json"] {
+            assert_eq!(
+                Command::Run {
+                    day: None,
+                    part: None,
+                    journal: None,
+                },
+                parse(args(line)).unwrap().command
+            );
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(matches!(parse(args("run 10")), Err(CliError::UnknownDay(day)) if day == "10"));
+        assert!(matches!(
+            parse(args("run day_99")),
+            Err(CliError::UnknownDay(_))
+        ));
+        assert!(matches!(
+            parse(args("run 1 all")),
+            Err(CliError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            parse(args("run 1 --part 3")),
+            Err(CliError::InvalidPart(_))
+        ));
+        assert!(matches!(
+            parse(args("run 1 --input")),
+            Err(CliError::MissingValue("--input"))
+        ));
+        assert!(matches!(
+            parse(args("run 9 --journal")),
+            Err(CliError::MissingValue("--journal"))
+        ));
+        assert!(matches!(
+            parse(args("day_13")),
+            Err(CliError::UnknownCommand(_))
+        ));
+        assert!(matches!(
+            parse(args("list --fast")),
+            Err(CliError::UnknownOption(_))
+        ));
+        assert!(matches!(
+            parse(args("run 8 --format=yaml")),
+            Err(CliError::InvalidArgument(format)) if format == "yaml"
+        ));
+        assert!(matches!(parse(args("")), Err(CliError::MissingCommand)));
+    }
+
+    #[test]
+    fn parse_tools() {
+        assert_eq!(
+            Command::Gdb {
+                program: PathBuf::from("boost.txt"),
+                address: Some("127.0.0.1:1234".to_string()),
+                inputs: vec![1, -2],
+            },
+            parse(args("gdb boost.txt 127.0.0.1:1234 1 -2"))
+                .unwrap()
+                .command
+        );
+        assert_eq!(Command::List, parse(args("list")).unwrap().command);
+    }
+
+    #[test]
+    fn parse_bench() {
+        assert_eq!(
+            Command::Bench {
+                day: None,
+                part: None,
+                runs: 10,
+                save: None,
+                baseline: None,
+                threshold: 10,
+            },
+            parse(args("bench")).unwrap().command
+        );
+        assert_eq!(
+            Command::Bench {
+                day: Some(12),
+                part: Some(Part::One),
+                runs: 3,
+                save: Some(PathBuf::from("after.json")),
+                baseline: Some(PathBuf::from("before.json")),
+                threshold: 25,
+            },
+            parse(args(
+                "bench day_12 --part 1 --runs 3 --save after.json --baseline=before.json --threshold 25"
+            ))
+            .unwrap()
+            .command
+        );
+
+        assert!(matches!(
+            parse(args("bench --runs 0")),
+            Err(CliError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            parse(args("bench --threshold fast")),
+            Err(CliError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            parse(args("bench 10")),
+            Err(CliError::UnknownDay(_))
+        ));
+    }
+
+    #[test]
+    fn parse_verify() {
+        assert_eq!(
+            Command::Verify {
+                day: None,
+                part: None,
+                answers: None,
+                save: None,
+            },
+            parse(args("verify")).unwrap().command
+        );
+        assert_eq!(
+            Command::Verify {
+                day: Some(8),
+                part: Some(Part::Two),
+                answers: Some(PathBuf::from("answers.json")),
+                save: Some(PathBuf::from("inputs/answers.json")),
+            },
+            parse(args(
+                "verify 8 --part 2 --answers answers.json --save inputs/answers.json"
+            ))
+            .unwrap()
+            .command
+        );
+        assert!(matches!(
+            parse(args("verify --answers")),
+            Err(CliError::MissingValue("--answers"))
+        ));
+    }
+}
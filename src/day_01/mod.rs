@@ -4,12 +4,34 @@ pub mod part_1;
 pub mod part_2;
 
 pub use crate::day_01::{
-    part_1::{
-        calculate_fuel_requirement,
-        calculate_ship_fuel_requirement,
-    },
-    part_2::{
-        calculate_compensated_fuel_requirement,
-        calculate_compensated_ship_fuel_requirement,
-    },
+    part_1::calculate_fuel_requirement,
+    part_2::calculate_compensated_fuel_requirement,
 };
+use crate::solution::{
+    Answer,
+    Solution,
+    SolutionError,
+};
+use part_1::Mass;
+
+/// The masses of the modules of the ship.
+#[derive(Debug)]
+pub struct Day01 {
+    masses: Vec<Mass>,
+}
+
+impl Solution for Day01 {
+    fn parse(input: &str) -> Result<Self, SolutionError> {
+        Ok(Self {
+            masses: part_1::parse_masses(input)?,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer, SolutionError> {
+        Ok(part_1::ship_fuel_requirement(&self.masses).into())
+    }
+
+    fn part_two(&self) -> Result<Answer, SolutionError> {
+        Ok(part_2::compensated_ship_fuel_requirement(&self.masses).into())
+    }
+}
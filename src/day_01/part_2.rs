@@ -4,13 +4,13 @@ use crate::day_01::part_1::{
     calculate_fuel_requirement,
     Fuel,
     Mass,
-    SHIP_MODULES_MASSES,
 };
 
-/// Calculate required fuel for the mass of the ship and the additional fuel
-/// required to also lift the initial ship mass fuel.
-pub fn calculate_compensated_ship_fuel_requirement() -> Fuel {
-    SHIP_MODULES_MASSES
+/// Calculate required fuel for the mass of the ship with the given module
+/// masses and the additional fuel required to also lift the initial ship mass
+/// fuel.
+pub fn compensated_ship_fuel_requirement(masses: &[Mass]) -> Fuel {
+    masses
         .iter()
         .map(|module_mass| calculate_compensated_fuel_requirement(*module_mass))
         .sum()
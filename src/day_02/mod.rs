@@ -1,5 +1,48 @@
 //! Solutions for Advent of Code 2019 Day 02
 
-pub mod computer;
 pub mod part_1;
 pub mod part_2;
+
+use crate::{
+    intcode::{
+        journal::Journal,
+        program::Program,
+    },
+    solution::{
+        Answer,
+        Solution,
+        SolutionError,
+    },
+};
+
+/// The gravity assist program.
+#[derive(Debug)]
+pub struct Day02 {
+    software: Program,
+    journal: Option<Journal>,
+}
+
+impl Solution for Day02 {
+    fn parse(input: &str) -> Result<Self, SolutionError> {
+        Ok(Self {
+            software: input.parse()?,
+            journal: None,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer, SolutionError> {
+        part_1::run_with(&self.software, 12, 2, self.journal.as_ref())
+            .map(Answer::from)
+            .ok_or_else(|| SolutionError::NoAnswer("the software crashed".to_string()))
+    }
+
+    fn part_two(&self) -> Result<Answer, SolutionError> {
+        part_2::find_noun_and_verb(&self.software, self.journal.as_ref())
+            .map(Answer::from)
+            .ok_or_else(|| SolutionError::NoAnswer("no noun and verb output 19690720".to_string()))
+    }
+
+    fn set_journals(&mut self, journals: &[Journal]) {
+        self.journal = journals.first().cloned();
+    }
+}
@@ -1,22 +1,38 @@
 //! Solutions for Advent of Code 2019 Day 02 Part 1
-use crate::day_02::computer::Computer;
+use crate::intcode::{
+    computer::Computer,
+    journal::Journal,
+    program::Program,
+};
 
-pub const INPUT: &[usize] = &[
-    1, 0, 0, 3, 1, 1, 2, 3, 1, 3, 4, 3, 1, 5, 0, 3, 2, 13, 1, 19, 1, 5, 19, 23, 2, 10, 23, 27, 1,
-    27, 5, 31, 2, 9, 31, 35, 1, 35, 5, 39, 2, 6, 39, 43, 1, 43, 5, 47, 2, 47, 10, 51, 2, 51, 6, 55,
-    1, 5, 55, 59, 2, 10, 59, 63, 1, 63, 6, 67, 2, 67, 6, 71, 1, 71, 5, 75, 1, 13, 75, 79, 1, 6, 79,
-    83, 2, 83, 13, 87, 1, 87, 6, 91, 1, 10, 91, 95, 1, 95, 9, 99, 2, 99, 13, 103, 1, 103, 6, 107,
-    2, 107, 6, 111, 1, 111, 2, 115, 1, 115, 13, 0, 99, 2, 0, 14, 0,
-];
+pub const INPUT: &str = include_str!("../../inputs/day_02.txt");
 
-pub fn restore_gravity_assist_program() -> usize {
-    let mut input = INPUT.to_vec();
+pub fn software() -> Program {
+    INPUT.parse().unwrap()
+}
+
+/// Runs the software with the given noun and verb and returns the value
+/// left at address 0. The run is recorded in `journal` if given.
+pub fn run_with(
+    software: &Program,
+    noun: isize,
+    verb: isize,
+    journal: Option<&Journal>,
+) -> Option<isize> {
+    let mut software = software.to_vec();
+
+    if software.len() < 3 {
+        return None;
+    }
+
+    software[1] = noun;
+    software[2] = verb;
 
-    input[1] = 12;
-    input[2] = 2;
+    let mut computer = Computer::from(software);
 
-    let computer = Computer::from(input);
-    let values = computer.run().unwrap();
+    if let Some(journal) = journal {
+        computer = computer.with_journal(journal);
+    }
 
-    values[0]
+    Some(computer.run().ok()?.memory.get(0))
 }
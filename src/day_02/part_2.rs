@@ -1,23 +1,22 @@
 //! Solutions for Advent of Code 2019 Day 02 Part 2
 
-use crate::day_02::{
-    computer::Computer,
-    part_1::INPUT,
+use crate::{
+    day_02::part_1::run_with,
+    intcode::{
+        journal::Journal,
+        program::Program,
+    },
 };
 
-pub fn what() -> Option<usize> {
-    const LOOKING_FOR: usize = 19_690_720;
+/// Finds the noun and verb that make the software output 19690720 and
+/// returns `100 * noun + verb`. `journal` holds the run of the noun and verb
+/// found if given.
+pub fn find_noun_and_verb(software: &Program, journal: Option<&Journal>) -> Option<isize> {
+    const LOOKING_FOR: isize = 19_690_720;
 
-    for noun in { 0..=99 } {
-        for verb in { 0..=99 } {
-            let mut input = INPUT.to_vec();
-            input[1] = noun;
-            input[2] = verb;
-
-            let computer = Computer::from(input);
-            let values = computer.run().unwrap();
-
-            if values[0] == LOOKING_FOR {
+    for noun in 0..=99 {
+        for verb in 0..=99 {
+            if run_with(software, noun, verb, journal) == Some(LOOKING_FOR) {
                 return Some(100 * noun + verb);
             }
         }
@@ -3,4 +3,44 @@
 pub mod part_1;
 pub mod part_2;
 
-pub const INPUT: &str = "153517-630395";
+use crate::solution::{
+    Answer,
+    Solution,
+    SolutionError,
+};
+
+pub const INPUT: &str = include_str!("../../inputs/day_04.txt");
+
+/// Parses a range of passwords like `153517-630395`.
+pub fn parse_range(input: &str) -> Option<(usize, usize)> {
+    let mut split = input.trim().split('-');
+    let from = split.next()?.parse().ok()?;
+    let to = split.next()?.parse().ok()?;
+
+    Some((from, to))
+}
+
+/// The range of the password.
+#[derive(Debug)]
+pub struct Day04 {
+    from: usize,
+    to: usize,
+}
+
+impl Solution for Day04 {
+    fn parse(input: &str) -> Result<Self, SolutionError> {
+        let (from, to) = parse_range(input).ok_or_else(|| {
+            SolutionError::InvalidInput("expected a range like 100000-999999".to_string())
+        })?;
+
+        Ok(Self { from, to })
+    }
+
+    fn part_one(&self) -> Result<Answer, SolutionError> {
+        Ok(part_1::count_passwords_between(self.from, self.to).into())
+    }
+
+    fn part_two(&self) -> Result<Answer, SolutionError> {
+        Ok(part_2::count_passwords_between(self.from, self.to).into())
+    }
+}
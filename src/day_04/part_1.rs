@@ -1,15 +1,7 @@
 use rayon::prelude::*;
 use std::collections::BTreeMap;
 
-pub fn count_passwords() -> usize {
-    let mut split = super::INPUT.split('-');
-    let from: usize = split.next().unwrap().parse().unwrap();
-    let to: usize = split.next().unwrap().parse().unwrap();
-
-    _count_passwords(from, to)
-}
-
-fn _count_passwords(from: usize, to: usize) -> usize {
+pub fn count_passwords_between(from: usize, to: usize) -> usize {
     (from..=to)
         .into_par_iter()
         .filter(|password| is_password(*password))
@@ -136,7 +128,7 @@ mod benchs {
         let from = test::black_box(0);
         let to = test::black_box(1);
 
-        b.iter(|| super::_count_passwords(from, to))
+        b.iter(|| super::count_passwords_between(from, to))
     }
 
     #[bench]
@@ -144,17 +136,16 @@ mod benchs {
         let from = test::black_box(0);
         let to = test::black_box(10000);
 
-        b.iter(|| super::_count_passwords(from, to))
+        b.iter(|| super::count_passwords_between(from, to))
     }
 
     #[bench]
     fn count_passwords_input(b: &mut Bencher) {
-        let input = test::black_box(crate::day_04::INPUT);
-
-        let mut split = input.split('-');
-        let from: usize = split.next().unwrap().parse().unwrap();
-        let to: usize = split.next().unwrap().parse().unwrap();
+        let solution = crate::solution::get(4)
+            .unwrap()
+            .parse(crate::day_04::INPUT)
+            .unwrap();
 
-        b.iter(|| super::_count_passwords(from, to))
+        b.iter(|| solution.part_one())
     }
 }
@@ -7,15 +7,7 @@ use crate::day_04::part_1::{
     password_is_monotonic,
 };
 
-pub fn count_passwords() -> usize {
-    _count_passwords(super::INPUT)
-}
-
-pub fn _count_passwords(input: &str) -> usize {
-    let mut split = input.split('-');
-    let first: usize = split.next().unwrap().parse().unwrap();
-    let second: usize = split.next().unwrap().parse().unwrap();
-
+pub fn count_passwords_between(first: usize, second: usize) -> usize {
     (first..=second)
         .into_par_iter()
         .filter(|password| is_password(*password))
@@ -82,22 +74,27 @@ mod benchs {
 
     #[bench]
     fn count_passwords_single(b: &mut Bencher) {
-        let input = test::black_box("0-1");
+        let from = test::black_box(0);
+        let to = test::black_box(1);
 
-        b.iter(|| super::_count_passwords(input))
+        b.iter(|| super::count_passwords_between(from, to))
     }
 
     #[bench]
     fn count_passwords_lots(b: &mut Bencher) {
-        let input = test::black_box("0-10000");
+        let from = test::black_box(0);
+        let to = test::black_box(10000);
 
-        b.iter(|| super::_count_passwords(input))
+        b.iter(|| super::count_passwords_between(from, to))
     }
 
     #[bench]
     fn count_passwords_input(b: &mut Bencher) {
-        let input = test::black_box(crate::day_04::INPUT);
+        let solution = crate::solution::get(4)
+            .unwrap()
+            .parse(crate::day_04::INPUT)
+            .unwrap();
 
-        b.iter(|| super::_count_passwords(input))
+        b.iter(|| solution.part_two())
     }
 }
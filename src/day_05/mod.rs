@@ -1,3 +1,49 @@
-pub mod computer;
 pub mod part_1;
 pub mod part_2;
+
+use crate::{
+    intcode::{
+        journal::Journal,
+        program::Program,
+    },
+    solution::{
+        Answer,
+        Solution,
+        SolutionError,
+    },
+};
+
+/// The diagnostic program of the ship computer.
+#[derive(Debug)]
+pub struct Day05 {
+    software: Program,
+    journal: Option<Journal>,
+}
+
+impl Solution for Day05 {
+    fn parse(input: &str) -> Result<Self, SolutionError> {
+        Ok(Self {
+            software: input.parse()?,
+            journal: None,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer, SolutionError> {
+        let report = part_1::diagnostic_code(&self.software, self.journal.as_ref());
+
+        report
+            .code()
+            .map(Answer::from)
+            .ok_or_else(|| SolutionError::NoAnswer(report.to_string()))
+    }
+
+    fn part_two(&self) -> Result<Answer, SolutionError> {
+        part_2::diagnostic_code(&self.software, self.journal.as_ref())
+            .map(Answer::from)
+            .ok_or_else(|| SolutionError::NoAnswer("the software crashed".to_string()))
+    }
+
+    fn set_journals(&mut self, journals: &[Journal]) {
+        self.journal = journals.first().cloned();
+    }
+}
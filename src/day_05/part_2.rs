@@ -1,20 +1,24 @@
-use crate::day_05::computer::{
-    Computer,
-    Reader,
-    Writer,
+use crate::intcode::{
+    computer::{
+        Computer,
+        Reader,
+    },
+    journal::Journal,
+    program::Program,
 };
 
 pub const INPUT_VALUES: &[isize] = &[5];
 
-pub fn run() {
-    let mut computer = Computer {
-        memory: crate::day_05::part_1::INPUT_MEMORY.to_vec(),
-        input: Reader::Tester(Box::new(INPUT_VALUES.to_vec().into_iter())),
-        output: Writer::Tester { values: Vec::new() },
-    };
+/// Diagnostic code of the thermal radiator controller, the last output of
+/// the diagnostic program. The run is recorded in `journal` if given.
+pub fn diagnostic_code(software: &Program, journal: Option<&Journal>) -> Option<isize> {
+    let mut computer = Computer::default()
+        .with_software(software.to_vec())
+        .with_input(Reader::Tester(Box::new(INPUT_VALUES.to_vec().into_iter())));
 
-    computer.run().unwrap();
-    let output = computer.output;
+    if let Some(journal) = journal {
+        computer = computer.with_journal(journal);
+    }
 
-    dbg!(&output);
+    computer.run().ok()?.outputs.last().copied()
 }
@@ -1,15 +1,22 @@
-use crate::day_07::computer::{
-    Computer,
-    DebugFlags,
-    Reader,
-    Writer,
+use crate::intcode::{
+    computer::{
+        Computer,
+        Reader,
+        Recording,
+        Writer,
+    },
+    journal::Journal,
 };
 use crossbeam_channel::unbounded;
 use std::thread;
 
+/// Number of amplifiers in series.
+pub const AMPLIFIERS: usize = 5;
+
 pub struct AmplifiersLoop {
     phase_settings: Vec<isize>,
     software: Vec<isize>,
+    journals: Vec<Journal>,
 }
 
 impl AmplifiersLoop {
@@ -17,76 +24,76 @@ impl AmplifiersLoop {
         Self {
             phase_settings,
             software,
+            journals: Vec::new(),
         }
     }
 
-    pub fn run(self) -> isize {
-        let (sender_a, receiver_a) = unbounded();
-        let (sender_b, receiver_b) = unbounded();
-        let (sender_c, receiver_c) = unbounded();
-        let (sender_d, receiver_d) = unbounded();
-        let (sender_e, receiver_e) = unbounded();
-
-        sender_e.send(self.phase_settings[0]).unwrap();
-        sender_e.send(0).unwrap();
-
-        sender_a.send(self.phase_settings[1]).unwrap();
-        sender_b.send(self.phase_settings[2]).unwrap();
-        sender_c.send(self.phase_settings[3]).unwrap();
-        sender_d.send(self.phase_settings[4]).unwrap();
-
-        // Amp A
-        {
-            let software = self.software.clone();
-            let receiver_e = receiver_e.clone();
-            thread::spawn(move || {
-                let input = Reader::Channel(receiver_e);
-                let output = Writer::Channel(sender_a);
-                Amplifier::new(software, input, output).run();
-            });
-        }
-
-        // Amp B
-        {
-            let software = self.software.clone();
-            thread::spawn(move || {
-                let input = Reader::Channel(receiver_a);
-                let output = Writer::Channel(sender_b);
-                Amplifier::new(software, input, output).run();
-            });
-        }
-
-        // Amp C
-        {
-            let software = self.software.clone();
-            thread::spawn(move || {
-                let input = Reader::Channel(receiver_b);
-                let output = Writer::Channel(sender_c);
-                Amplifier::new(software, input, output).run();
-            });
+    /// Records the run of every amplifier to its own journal in the order of
+    /// the phase settings.
+    pub fn with_journals(self, journals: &[Journal]) -> Self {
+        Self {
+            journals: journals.to_vec(),
+            ..self
         }
+    }
 
-        // Amp D
-        {
-            let software = self.software.clone();
-            thread::spawn(move || {
-                let input = Reader::Channel(receiver_c);
-                let output = Writer::Channel(sender_d);
-                Amplifier::new(software, input, output).run();
-            });
+    pub fn run(self) -> isize {
+        let count = self.phase_settings.len();
+        let channels = (0..count).map(|_| unbounded()).collect::<Vec<_>>();
+        let thrusters = Recording::default();
+        let software = self.software;
+        let journals = self.journals;
+
+        // Every amplifier reads from its own channel and writes to the channel
+        // of the next one. The last amplifier feeds back into the first.
+        let amplifiers = self
+            .phase_settings
+            .into_iter()
+            .enumerate()
+            .map(|(index, phase_setting)| {
+                let software = software.clone();
+                let receiver = channels[index].1.clone();
+                let sender = channels[(index + 1) % count].0.clone();
+                let thrusters = thrusters.clone();
+                let journal = journals.get(index).cloned();
+
+                let prefix = if index == 0 {
+                    vec![phase_setting, 0]
+                } else {
+                    vec![phase_setting]
+                };
+
+                thread::spawn(move || {
+                    let input = Reader::chain(prefix, Reader::Channel(receiver));
+                    let mut output = Writer::Channel(sender);
+
+                    if index == count - 1 {
+                        output = output.record(&thrusters);
+                    }
+
+                    let mut amplifier = Amplifier::new(software, input, output);
+
+                    if let Some(journal) = &journal {
+                        amplifier = amplifier.with_journal(journal);
+                    }
+
+                    amplifier.run();
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for amplifier in amplifiers {
+            amplifier.join().unwrap();
         }
 
-        let input = Reader::Channel(receiver_d);
-        let output = Writer::Channel(sender_e);
-        Amplifier::new(self.software, input, output).run();
-
-        receiver_e.recv().unwrap()
+        *thrusters.values().last().unwrap()
     }
 }
 
 pub struct Amplifiers {
     phase_settings: Vec<isize>,
     software: Vec<isize>,
+    journals: Vec<Journal>,
 }
 
 impl Amplifiers {
@@ -94,17 +101,33 @@ impl Amplifiers {
         Self {
             phase_settings,
             software,
+            journals: Vec::new(),
+        }
+    }
+
+    /// Records the run of every amplifier to its own journal in the order of
+    /// the phase settings.
+    pub fn with_journals(self, journals: &[Journal]) -> Self {
+        Self {
+            journals: journals.to_vec(),
+            ..self
         }
     }
 
     pub fn run(self) -> isize {
         let mut parameter = 0;
 
-        for phase_setting in self.phase_settings {
+        for (index, phase_setting) in self.phase_settings.into_iter().enumerate() {
             let input = Reader::Tester(Box::new(vec![phase_setting, parameter].into_iter()));
             let output = Writer::Tester { values: Vec::new() };
 
-            let result = Amplifier::new(self.software.clone(), input, output).run();
+            let mut amplifier = Amplifier::new(self.software.clone(), input, output);
+
+            if let Some(journal) = self.journals.get(index) {
+                amplifier = amplifier.with_journal(journal);
+            }
+
+            let result = amplifier.run();
 
             parameter = result[0];
         }
@@ -126,15 +149,19 @@ impl Amplifier {
     }
 
     pub fn new(software: Vec<isize>, input: Reader, output: Writer) -> Self {
-        let computer = Computer {
-            memory: software,
-            input,
-            output,
-            debug_flags: DebugFlags::default(),
-        };
+        let computer = Computer::default()
+            .with_software(software)
+            .with_input(input)
+            .with_output(output);
 
         Self { computer }
     }
+
+    pub fn with_journal(self, journal: &Journal) -> Self {
+        Self {
+            computer: self.computer.with_journal(journal),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -143,6 +170,7 @@ mod tests {
         Amplifiers,
         AmplifiersLoop,
     };
+    use crate::intcode::journal::Journal;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -219,4 +247,43 @@ mod tests {
 
         assert_eq!(expected, got);
     }
+
+    #[test]
+    fn amplifiers_replay() {
+        let phase_settings = vec![4, 3, 2, 1, 0];
+
+        let software = vec![
+            3, 15, 3, 16, 1002, 16, 10, 16, 1, 16, 15, 15, 4, 15, 99, 0, 0,
+        ];
+
+        let journals = (0..5).map(|_| Journal::default()).collect::<Vec<_>>();
+        Amplifiers::new(phase_settings, software)
+            .with_journals(&journals)
+            .run();
+
+        assert_eq!(vec![43210], journals[4].replay().unwrap().outputs);
+        assert_eq!(vec![4, 0], journals[0].inputs());
+    }
+
+    #[test]
+    fn amplifiers_loop_replay() {
+        let phase_settings = vec![9, 8, 7, 6, 5];
+
+        let software = vec![
+            3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1,
+            28, 1005, 28, 6, 99, 0, 0, 5,
+        ];
+
+        let journals = (0..5).map(|_| Journal::default()).collect::<Vec<_>>();
+        AmplifiersLoop::new(phase_settings, software)
+            .with_journals(&journals)
+            .run();
+
+        let thrusters = journals[4].replay().unwrap().outputs;
+        assert_eq!(Some(&139_629_729), thrusters.last());
+
+        for journal in &journals {
+            assert_eq!(journal.outputs(), journal.replay().unwrap().outputs);
+        }
+    }
 }
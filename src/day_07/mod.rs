@@ -1,4 +1,52 @@
 pub mod amplifier;
-pub mod computer;
 pub mod part_1;
 pub mod part_2;
+
+use crate::{
+    intcode::{
+        journal::Journal,
+        program::Program,
+    },
+    solution::{
+        Answer,
+        Solution,
+        SolutionError,
+    },
+};
+
+/// The amplifier controller software.
+#[derive(Debug)]
+pub struct Day07 {
+    software: Program,
+    journals: Vec<Journal>,
+}
+
+impl Solution for Day07 {
+    fn parse(input: &str) -> Result<Self, SolutionError> {
+        Ok(Self {
+            software: input.parse()?,
+            journals: Vec::new(),
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer, SolutionError> {
+        part_1::max_thruster_signal(&self.software, &self.journals)
+            .map(Answer::from)
+            .ok_or_else(|| SolutionError::NoAnswer("no phase setting".to_string()))
+    }
+
+    fn part_two(&self) -> Result<Answer, SolutionError> {
+        part_2::max_thruster_signal(&self.software, &self.journals)
+            .map(Answer::from)
+            .ok_or_else(|| SolutionError::NoAnswer("no phase setting".to_string()))
+    }
+
+    /// One computer per amplifier.
+    fn computers(&self) -> usize {
+        amplifier::AMPLIFIERS
+    }
+
+    fn set_journals(&mut self, journals: &[Journal]) {
+        self.journals = journals.to_vec();
+    }
+}
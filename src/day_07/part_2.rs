@@ -1,16 +1,27 @@
-use crate::day_07::{
-    amplifier::AmplifiersLoop,
-    part_1::AMPLIFIER_SOFTWARE,
+use crate::{
+    day_07::amplifier::{
+        AmplifiersLoop,
+        AMPLIFIERS,
+    },
+    intcode::{
+        journal::Journal,
+        program::Program,
+    },
 };
 use itertools::Itertools;
 
-pub fn run() {
-    let phase_settings = (0..5).permutations(5);
+/// Highest signal the amplifiers in a feedback loop send to the thrusters
+/// over every phase setting. The run of the best setting is recorded in
+/// `journals`, one per amplifier.
+pub fn max_thruster_signal(software: &Program, journals: &[Journal]) -> Option<isize> {
+    let setting = (0..AMPLIFIERS as isize)
+        .permutations(AMPLIFIERS)
+        .map(|setting| setting.into_iter().map(|x| x + 5).collect::<Vec<_>>())
+        .max_by_key(|setting| AmplifiersLoop::new(setting.clone(), software.to_vec()).run())?;
 
-    let max = phase_settings
-        .map(|setting| setting.into_iter().map(|x| x + 5).collect())
-        .map(|setting| AmplifiersLoop::new(setting, AMPLIFIER_SOFTWARE.to_vec()).run())
-        .max();
-
-    dbg!(max);
+    Some(
+        AmplifiersLoop::new(setting, software.to_vec())
+            .with_journals(journals)
+            .run(),
+    )
 }
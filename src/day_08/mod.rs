@@ -1,3 +1,46 @@
 pub mod part_1;
 pub mod part_2;
 pub mod picture;
+
+use crate::solution::{
+    Answer,
+    Solution,
+    SolutionError,
+};
+
+/// The pixels of the image, layer after layer.
+#[derive(Debug)]
+pub struct Day08 {
+    pixels: String,
+}
+
+impl Solution for Day08 {
+    fn parse(input: &str) -> Result<Self, SolutionError> {
+        let pixels = input.trim();
+
+        if pixels.is_empty()
+            || !pixels.len().is_multiple_of(part_1::WIDTH * part_1::HEIGHT)
+            || !pixels.chars().all(|pixel| ('0'..='2').contains(&pixel))
+        {
+            return Err(SolutionError::InvalidInput(format!(
+                "expected layers of {}x{} pixels",
+                part_1::WIDTH,
+                part_1::HEIGHT
+            )));
+        }
+
+        Ok(Self {
+            pixels: pixels.to_string(),
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer, SolutionError> {
+        Ok(part_1::checksum(&self.pixels).into())
+    }
+
+    fn part_two(&self) -> Result<Answer, SolutionError> {
+        let render = part_2::render(&self.pixels).to_string();
+
+        Ok(Answer::Render(render.trim_start_matches('\n').to_string()))
+    }
+}
@@ -1,20 +1,21 @@
 use crate::day_08::{
     part_1::{
         HEIGHT,
-        PIXELS,
         WIDTH,
     },
-    picture::Picture,
+    picture::{
+        Picture,
+        Render,
+    },
 };
 
-pub fn run() {
+/// Decodes the image by stacking its layers.
+pub fn render(pixels: &str) -> Render {
     let picture = Picture {
         width: WIDTH,
         height: HEIGHT,
-        pixels: PIXELS,
+        pixels: pixels.trim(),
     };
 
-    let out = picture.render();
-
-    println!("day_08 - part_2:\n{}", out);
+    picture.render()
 }
@@ -1,20 +1,24 @@
-use crate::day_09::{
+use crate::intcode::{
     computer::{
         Computer,
         Reader,
     },
-    part_1::BOOST_SOFTWARE,
+    journal::Journal,
+    program::Program,
 };
 
 pub const INPUT_VALUES: &[isize] = &[2];
 
-pub fn run() {
+/// Coordinates of the distress signal, which BOOST outputs in sensor boost
+/// mode. The run is recorded in `journal` if given.
+pub fn coordinates(software: &Program, journal: Option<&Journal>) -> Option<isize> {
     let mut computer = Computer::default()
-        .with_memory(BOOST_SOFTWARE.to_vec())
+        .with_software(software.to_vec())
         .with_input(Reader::Tester(Box::new(INPUT_VALUES.to_vec().into_iter())));
 
-    computer.run().unwrap();
-    let output = computer.output.values();
+    if let Some(journal) = journal {
+        computer = computer.with_journal(journal);
+    }
 
-    dbg!(output);
+    computer.run().ok()?.outputs.last().copied()
 }
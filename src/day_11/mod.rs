@@ -1,5 +1,59 @@
 pub mod canvas;
-pub mod computer;
 pub mod part_1;
 pub mod part_2;
 pub mod roboter;
+
+use crate::{
+    intcode::{
+        journal::Journal,
+        program::Program,
+    },
+    solution::{
+        Answer,
+        Solution,
+        SolutionError,
+    },
+};
+
+/// The software of the hull painting robot.
+#[derive(Debug)]
+pub struct Day11 {
+    software: Program,
+    visualize: bool,
+    journal: Option<Journal>,
+}
+
+impl Solution for Day11 {
+    fn parse(input: &str) -> Result<Self, SolutionError> {
+        Ok(Self {
+            software: input.parse()?,
+            visualize: false,
+            journal: None,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer, SolutionError> {
+        part_1::count_painted_panels(self.software.clone(), self.journal.as_ref())
+            .map(Answer::from)
+            .map_err(|err| SolutionError::NoAnswer(format!("{:?}", err)))
+    }
+
+    /// Opens a canvas with the painted hull.
+    fn part_two(&self) -> Result<Answer, SolutionError> {
+        part_2::paint_registration_identifier(
+            self.software.clone(),
+            self.visualize,
+            self.journal.as_ref(),
+        )
+        .map(Answer::Render)
+        .map_err(|err| SolutionError::NoAnswer(format!("{:?}", err)))
+    }
+
+    fn set_visualize(&mut self, visualize: bool) {
+        self.visualize = visualize;
+    }
+
+    fn set_journals(&mut self, journals: &[Journal]) {
+        self.journal = journals.first().cloned();
+    }
+}
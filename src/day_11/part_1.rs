@@ -1,8 +1,25 @@
-use super::roboter::Roboter;
+use super::roboter::{
+    Roboter,
+    RunError,
+};
+use crate::intcode::{
+    journal::Journal,
+    program::Program,
+};
 
-pub fn run() {
-    let mut robot = Roboter::default();
-    robot.run();
+/// Number of panels the robot paints at least once. The run is recorded in
+/// `journal` if given.
+pub fn count_painted_panels(
+    software: Program,
+    journal: Option<&Journal>,
+) -> Result<usize, RunError> {
+    let mut robot = Roboter::default().with_software(software);
 
-    dbg!(&robot.canvas.len());
+    if let Some(journal) = journal {
+        robot = robot.with_journal(journal);
+    }
+
+    robot.run()?;
+
+    Ok(robot.canvas.len())
 }
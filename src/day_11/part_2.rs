@@ -1,10 +1,34 @@
 use super::roboter::{
     Color,
     Roboter,
+    RunError,
 };
+use crate::intcode::{
+    journal::Journal,
+    program::Program,
+};
+
+/// The registration identifier the robot paints when it starts on a white
+/// panel. `draw` also opens a canvas with the hull. The run is recorded in
+/// `journal` if given.
+pub fn paint_registration_identifier(
+    software: Program,
+    draw: bool,
+    journal: Option<&Journal>,
+) -> Result<String, RunError> {
+    let mut robot = Roboter::default()
+        .with_default_color(Color::White)
+        .with_software(software);
+
+    if let Some(journal) = journal {
+        robot = robot.with_journal(journal);
+    }
+
+    robot.run()?;
+
+    if draw {
+        robot.draw();
+    }
 
-pub fn run() {
-    let mut robot = Roboter::default().with_default_color(Color::White);
-    robot.run();
-    robot.draw();
+    Ok(robot.render())
 }
@@ -1,668 +1,30 @@
-use super::computer::{
-    Computer,
-    Reader,
-    Writer,
+use crate::intcode::{
+    codec::{
+        self,
+        Codec,
+        CodecError,
+    },
+    computer::{
+        Computer,
+        ComputerError,
+        Reader,
+        Writer,
+    },
+    journal::Journal,
+    program::Program,
+};
+use crossbeam_channel::{
+    unbounded,
+    Receiver,
+    Sender,
 };
-use crossbeam_channel::unbounded;
 use std::{
     collections::BTreeMap,
+    convert::TryFrom,
     thread,
 };
 
-const ROBOT_SOFTWARE: &[isize] = &[
-    3,
-    8,
-    1005,
-    8,
-    330,
-    1106,
-    0,
-    11,
-    0,
-    0,
-    0,
-    104,
-    1,
-    104,
-    0,
-    3,
-    8,
-    102,
-    -1,
-    8,
-    10,
-    101,
-    1,
-    10,
-    10,
-    4,
-    10,
-    1008,
-    8,
-    0,
-    10,
-    4,
-    10,
-    102,
-    1,
-    8,
-    29,
-    2,
-    9,
-    4,
-    10,
-    1006,
-    0,
-    10,
-    1,
-    1103,
-    17,
-    10,
-    3,
-    8,
-    102,
-    -1,
-    8,
-    10,
-    101,
-    1,
-    10,
-    10,
-    4,
-    10,
-    108,
-    0,
-    8,
-    10,
-    4,
-    10,
-    101,
-    0,
-    8,
-    61,
-    1006,
-    0,
-    21,
-    1006,
-    0,
-    51,
-    3,
-    8,
-    1002,
-    8,
-    -1,
-    10,
-    101,
-    1,
-    10,
-    10,
-    4,
-    10,
-    108,
-    1,
-    8,
-    10,
-    4,
-    10,
-    1001,
-    8,
-    0,
-    89,
-    1,
-    102,
-    19,
-    10,
-    1,
-    1107,
-    17,
-    10,
-    1006,
-    0,
-    18,
-    3,
-    8,
-    1002,
-    8,
-    -1,
-    10,
-    1001,
-    10,
-    1,
-    10,
-    4,
-    10,
-    1008,
-    8,
-    1,
-    10,
-    4,
-    10,
-    1001,
-    8,
-    0,
-    123,
-    1,
-    9,
-    2,
-    10,
-    2,
-    1105,
-    10,
-    10,
-    2,
-    103,
-    9,
-    10,
-    2,
-    1105,
-    15,
-    10,
-    3,
-    8,
-    102,
-    -1,
-    8,
-    10,
-    1001,
-    10,
-    1,
-    10,
-    4,
-    10,
-    1008,
-    8,
-    0,
-    10,
-    4,
-    10,
-    102,
-    1,
-    8,
-    161,
-    3,
-    8,
-    102,
-    -1,
-    8,
-    10,
-    101,
-    1,
-    10,
-    10,
-    4,
-    10,
-    108,
-    1,
-    8,
-    10,
-    4,
-    10,
-    101,
-    0,
-    8,
-    182,
-    3,
-    8,
-    1002,
-    8,
-    -1,
-    10,
-    101,
-    1,
-    10,
-    10,
-    4,
-    10,
-    1008,
-    8,
-    0,
-    10,
-    4,
-    10,
-    101,
-    0,
-    8,
-    205,
-    2,
-    1102,
-    6,
-    10,
-    1006,
-    0,
-    38,
-    2,
-    1007,
-    20,
-    10,
-    2,
-    1105,
-    17,
-    10,
-    3,
-    8,
-    102,
-    -1,
-    8,
-    10,
-    1001,
-    10,
-    1,
-    10,
-    4,
-    10,
-    108,
-    1,
-    8,
-    10,
-    4,
-    10,
-    1001,
-    8,
-    0,
-    241,
-    3,
-    8,
-    102,
-    -1,
-    8,
-    10,
-    101,
-    1,
-    10,
-    10,
-    4,
-    10,
-    108,
-    1,
-    8,
-    10,
-    4,
-    10,
-    101,
-    0,
-    8,
-    263,
-    1006,
-    0,
-    93,
-    2,
-    5,
-    2,
-    10,
-    2,
-    6,
-    7,
-    10,
-    3,
-    8,
-    102,
-    -1,
-    8,
-    10,
-    101,
-    1,
-    10,
-    10,
-    4,
-    10,
-    108,
-    0,
-    8,
-    10,
-    4,
-    10,
-    1001,
-    8,
-    0,
-    296,
-    1006,
-    0,
-    81,
-    1006,
-    0,
-    68,
-    1006,
-    0,
-    76,
-    2,
-    4,
-    4,
-    10,
-    101,
-    1,
-    9,
-    9,
-    1007,
-    9,
-    1010,
-    10,
-    1005,
-    10,
-    15,
-    99,
-    109,
-    652,
-    104,
-    0,
-    104,
-    1,
-    21102,
-    825_594_262_284,
-    1,
-    1,
-    21102,
-    347,
-    1,
-    0,
-    1105,
-    1,
-    451,
-    21101,
-    0,
-    932_855_939_852,
-    1,
-    21101,
-    358,
-    0,
-    0,
-    1106,
-    0,
-    451,
-    3,
-    10,
-    104,
-    0,
-    104,
-    1,
-    3,
-    10,
-    104,
-    0,
-    104,
-    0,
-    3,
-    10,
-    104,
-    0,
-    104,
-    1,
-    3,
-    10,
-    104,
-    0,
-    104,
-    1,
-    3,
-    10,
-    104,
-    0,
-    104,
-    0,
-    3,
-    10,
-    104,
-    0,
-    104,
-    1,
-    21102,
-    1,
-    235_152_649_255,
-    1,
-    21101,
-    405,
-    0,
-    0,
-    1105,
-    1,
-    451,
-    21102,
-    235_350_879_235,
-    1,
-    1,
-    21102,
-    416,
-    1,
-    0,
-    1106,
-    0,
-    451,
-    3,
-    10,
-    104,
-    0,
-    104,
-    0,
-    3,
-    10,
-    104,
-    0,
-    104,
-    0,
-    21102,
-    988_757_512_972,
-    1,
-    1,
-    21101,
-    439,
-    0,
-    0,
-    1106,
-    0,
-    451,
-    21102,
-    1,
-    988_669_698_828,
-    1,
-    21101,
-    0,
-    450,
-    0,
-    1106,
-    0,
-    451,
-    99,
-    109,
-    2,
-    22101,
-    0,
-    -1,
-    1,
-    21102,
-    40,
-    1,
-    2,
-    21102,
-    1,
-    482,
-    3,
-    21102,
-    472,
-    1,
-    0,
-    1106,
-    0,
-    515,
-    109,
-    -2,
-    2105,
-    1,
-    0,
-    0,
-    1,
-    0,
-    0,
-    1,
-    109,
-    2,
-    3,
-    10,
-    204,
-    -1,
-    1001,
-    477,
-    478,
-    493,
-    4,
-    0,
-    1001,
-    477,
-    1,
-    477,
-    108,
-    4,
-    477,
-    10,
-    1006,
-    10,
-    509,
-    1101,
-    0,
-    0,
-    477,
-    109,
-    -2,
-    2106,
-    0,
-    0,
-    0,
-    109,
-    4,
-    1202,
-    -1,
-    1,
-    514,
-    1207,
-    -3,
-    0,
-    10,
-    1006,
-    10,
-    532,
-    21102,
-    1,
-    0,
-    -3,
-    21202,
-    -3,
-    1,
-    1,
-    21202,
-    -2,
-    1,
-    2,
-    21102,
-    1,
-    1,
-    3,
-    21102,
-    1,
-    551,
-    0,
-    1106,
-    0,
-    556,
-    109,
-    -4,
-    2105,
-    1,
-    0,
-    109,
-    5,
-    1207,
-    -3,
-    1,
-    10,
-    1006,
-    10,
-    579,
-    2207,
-    -4,
-    -2,
-    10,
-    1006,
-    10,
-    579,
-    22101,
-    0,
-    -4,
-    -4,
-    1105,
-    1,
-    647,
-    21201,
-    -4,
-    0,
-    1,
-    21201,
-    -3,
-    -1,
-    2,
-    21202,
-    -2,
-    2,
-    3,
-    21102,
-    598,
-    1,
-    0,
-    1105,
-    1,
-    556,
-    21202,
-    1,
-    1,
-    -4,
-    21101,
-    0,
-    1,
-    -1,
-    2207,
-    -4,
-    -2,
-    10,
-    1006,
-    10,
-    617,
-    21102,
-    1,
-    0,
-    -1,
-    22202,
-    -2,
-    -1,
-    -2,
-    2107,
-    0,
-    -3,
-    10,
-    1006,
-    10,
-    639,
-    21202,
-    -1,
-    1,
-    1,
-    21102,
-    1,
-    639,
-    0,
-    105,
-    1,
-    514,
-    21202,
-    -2,
-    -1,
-    -2,
-    22201,
-    -4,
-    -2,
-    -4,
-    109,
-    -5,
-    2105,
-    1,
-    0,
-];
+pub const ROBOT_SOFTWARE: &str = include_str!("../../inputs/day_11.txt");
 
 #[derive(Debug)]
 pub struct Roboter {
@@ -670,6 +32,29 @@ pub struct Roboter {
     facing: Direction,
     position: (isize, isize),
     default_color: Color,
+    journal: Option<Journal>,
+    /// Software to run instead of the puzzle input.
+    software: Option<Program>,
+}
+
+#[derive(Debug)]
+pub enum RunError {
+    Codec(CodecError),
+    Computer(ComputerError),
+    /// The thread running the software panicked.
+    Panicked,
+}
+
+impl From<CodecError> for RunError {
+    fn from(err: CodecError) -> Self {
+        Self::Codec(err)
+    }
+}
+
+impl From<ComputerError> for RunError {
+    fn from(err: ComputerError) -> Self {
+        Self::Computer(err)
+    }
 }
 
 impl Default for Roboter {
@@ -679,6 +64,8 @@ impl Default for Roboter {
             facing: Direction::Up,
             position: (0, 0),
             default_color: Color::Black,
+            journal: None,
+            software: None,
         }
     }
 }
@@ -691,42 +78,86 @@ impl Roboter {
         }
     }
 
-    pub fn run(&mut self) {
+    pub fn with_software(self, software: Program) -> Self {
+        Self {
+            software: Some(software),
+            ..self
+        }
+    }
+
+    /// Records the run of the robot software to replay it.
+    pub fn with_journal(self, journal: &Journal) -> Self {
+        Self {
+            journal: Some(journal.clone()),
+            ..self
+        }
+    }
+
+    /// Runs the software until it halts. Fails if the software outputs
+    /// something the robot doesn't understand or the computer fails.
+    pub fn run(&mut self) -> Result<(), RunError> {
         let (sender_output, receiver_output) = unbounded();
         let (sender_input, receiver_input) = unbounded();
+        let journal = self.journal.clone();
+        let software = self.software.clone().unwrap_or_else(software);
 
-        thread::spawn(move || {
+        let handle = thread::spawn(move || {
             let input = Reader::Channel(receiver_input);
             let output = Writer::Channel(sender_output);
 
             let mut computer = Computer::default()
-                .with_software(ROBOT_SOFTWARE.to_vec())
+                .with_software(software.into())
                 .with_input(input)
                 .with_output(output);
 
-            computer.run().unwrap();
+            if let Some(journal) = &journal {
+                computer = computer.with_journal(journal);
+            }
+
+            computer.run().map(|_| ())
         });
 
+        self.paint_all(&sender_input, &receiver_output)?;
+
+        // Without its channels the software halts or fails on its own.
+        drop(sender_input);
+
+        handle.join().map_err(|_| RunError::Panicked)??;
+
+        Ok(())
+    }
+
+    /// Shows the camera to the software and follows its instructions until
+    /// it stops.
+    fn paint_all(
+        &mut self,
+        sender_input: &Sender<isize>,
+        receiver_output: &Receiver<isize>,
+    ) -> Result<(), CodecError> {
         loop {
-            let send_value = self
+            let camera = self
                 .canvas
                 .get(&self.position)
                 .unwrap_or(&self.default_color);
-            sender_input.send(send_value.into()).unwrap();
 
-            if let Ok(color_value) = receiver_output.recv() {
-                self.paint(color_value.into());
-            } else {
-                break;
+            // The software stops reading once it halted.
+            for value in HullPainterCodec.encode(camera) {
+                if sender_input.send(value).is_err() {
+                    return Ok(());
+                }
             }
 
-            if let Ok(turn_value) = receiver_output.recv() {
-                self.turn(turn_value.into());
-            } else {
-                break;
-            }
+            match codec::receive(&HullPainterCodec, receiver_output) {
+                Some(instruction) => {
+                    let Instruction { color, turn } = instruction?;
+
+                    self.paint(color);
+                    self.turn(turn);
+                    self.step();
+                }
 
-            self.step();
+                None => return Ok(()),
+            }
         }
     }
 
@@ -767,6 +198,39 @@ impl Roboter {
         self.canvas.insert(self.position, color);
     }
 
+    /// The painted hull as text with `#` for white panels.
+    pub fn render(&self) -> String {
+        let white = self
+            .canvas
+            .iter()
+            .filter(|(_, color)| **color == Color::White)
+            .map(|(position, _)| *position)
+            .collect::<Vec<_>>();
+
+        let (min_x, max_x) = white
+            .iter()
+            .map(|(x, _)| *x)
+            .fold((0, 0), |(min, max), x| (min.min(x), max.max(x)));
+        let (min_y, max_y) = white
+            .iter()
+            .map(|(_, y)| *y)
+            .fold((0, 0), |(min, max), y| (min.min(y), max.max(y)));
+
+        (min_y..=max_y)
+            .map(|y| {
+                (min_x..=max_x)
+                    .map(|x| match self.canvas.get(&(x, y)) {
+                        Some(Color::White) => '#',
+                        _ => ' ',
+                    })
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     pub fn draw(&self) {
         let mut canvas = super::canvas::Canvas::default();
 
@@ -780,18 +244,28 @@ impl Roboter {
     }
 }
 
+/// The puzzle input.
+pub fn software() -> Program {
+    ROBOT_SOFTWARE.parse().unwrap()
+}
+
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
 pub enum Color {
     Black,
     White,
 }
 
-impl From<isize> for Color {
-    fn from(i: isize) -> Self {
+impl TryFrom<isize> for Color {
+    type Error = CodecError;
+
+    fn try_from(i: isize) -> Result<Self, Self::Error> {
         match i {
-            0 => Self::Black,
-            1 => Self::White,
-            _ => unreachable!(),
+            0 => Ok(Self::Black),
+            1 => Ok(Self::White),
+            value => Err(CodecError::OutOfRange {
+                field: "color",
+                value,
+            }),
         }
     }
 }
@@ -805,7 +279,7 @@ impl Into<isize> for &Color {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Eq, PartialEq)]
 pub enum Direction {
     Up,
     Down,
@@ -813,12 +287,146 @@ pub enum Direction {
     Right,
 }
 
-impl From<isize> for Direction {
-    fn from(i: isize) -> Self {
+impl TryFrom<isize> for Direction {
+    type Error = CodecError;
+
+    fn try_from(i: isize) -> Result<Self, Self::Error> {
         match i {
-            0 => Self::Left,
-            1 => Self::Right,
-            _ => unreachable!(),
+            0 => Ok(Self::Left),
+            1 => Ok(Self::Right),
+            value => Err(CodecError::OutOfRange {
+                field: "turn",
+                value,
+            }),
         }
     }
 }
+
+/// Protocol of the hull painting robot. The software reads the color below
+/// the robot from the camera and outputs the color to paint followed by the
+/// direction to turn.
+pub struct HullPainterCodec;
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Instruction {
+    pub color: Color,
+    pub turn: Direction,
+}
+
+impl Codec for HullPainterCodec {
+    type Message = Instruction;
+    type Command = Color;
+
+    fn message_size(&self) -> usize {
+        2
+    }
+
+    fn decode(&self, values: &[isize]) -> Result<Self::Message, CodecError> {
+        match *values {
+            [color, turn] => Ok(Instruction {
+                color: Color::try_from(color)?,
+                turn: Direction::try_from(turn)?,
+            }),
+            _ => Err(CodecError::Truncated {
+                expected: 2,
+                values: values.to_vec(),
+            }),
+        }
+    }
+
+    fn encode(&self, command: &Self::Command) -> Vec<isize> {
+        vec![command.into()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Color,
+        Direction,
+        HullPainterCodec,
+        Instruction,
+        Roboter,
+        RunError,
+    };
+    use crate::intcode::{
+        codec::{
+            decode_all,
+            Codec,
+            CodecError,
+        },
+        computer::ComputerError,
+        journal::Journal,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn hull_painter_codec() {
+        let expected = vec![Instruction {
+            color: Color::White,
+            turn: Direction::Left,
+        }];
+
+        assert_eq!(Ok(expected), decode_all(&HullPainterCodec, &[1, 0]));
+
+        assert_eq!(
+            Err(CodecError::OutOfRange {
+                field: "turn",
+                value: 2,
+            }),
+            decode_all(&HullPainterCodec, &[0, 2])
+        );
+
+        assert_eq!(
+            Err(CodecError::Truncated {
+                expected: 2,
+                values: vec![1],
+            }),
+            decode_all(&HullPainterCodec, &[1, 0, 1])
+        );
+
+        assert_eq!(vec![1], HullPainterCodec.encode(&Color::White));
+    }
+
+    #[test]
+    fn roboter_run_errors() {
+        let run = |software: &str| {
+            Roboter::default()
+                .with_software(software.parse().unwrap())
+                .run()
+        };
+
+        assert!(run("3,100,104,1,104,0,99").is_ok());
+
+        assert!(matches!(
+            run("3,100,104,1,99"),
+            Err(RunError::Codec(CodecError::Truncated { .. }))
+        ));
+        assert!(matches!(
+            run("3,100,104,1,104,2,99"),
+            Err(RunError::Codec(CodecError::OutOfRange { .. }))
+        ));
+        assert!(matches!(
+            run("3,100,301,99"),
+            Err(RunError::Computer(ComputerError::InvalidParameterMode(3)))
+        ));
+    }
+
+    #[test]
+    fn roboter_replay() {
+        let journal = Journal::default();
+
+        let mut robot = Roboter::default().with_journal(&journal);
+        robot.run().unwrap();
+
+        let replayed = journal
+            .to_string()
+            .parse::<Journal>()
+            .unwrap()
+            .replay()
+            .unwrap();
+
+        assert_eq!(journal.inputs(), replayed.inputs);
+        assert_eq!(journal.outputs(), replayed.outputs);
+    }
+}
@@ -5,6 +5,13 @@ pub mod part_2;
 pub mod position;
 pub mod velocity;
 
+use crate::solution::{
+    Answer,
+    Solution,
+    SolutionError,
+};
+use field::Field;
+
 pub fn parse_values(s: &str) -> Result<(isize, isize, isize), ParseValueError> {
     let mut x = 0;
     let mut y = 0;
@@ -36,3 +43,28 @@ impl From<std::num::ParseIntError> for ParseValueError {
         Self::IntError(err)
     }
 }
+
+/// The moons of Jupiter.
+#[derive(Debug)]
+pub struct Day12 {
+    field: Field,
+}
+
+impl Solution for Day12 {
+    fn parse(input: &str) -> Result<Self, SolutionError> {
+        Ok(Self {
+            field: input
+                .trim()
+                .parse()
+                .map_err(|err| SolutionError::InvalidInput(format!("{:?}", err)))?,
+        })
+    }
+
+    fn part_one(&self) -> Result<Answer, SolutionError> {
+        Ok(part_1::total_energy(self.field.clone()).into())
+    }
+
+    fn part_two(&self) -> Result<Answer, SolutionError> {
+        Ok(part_2::steps_until_repeat(&self.field).into())
+    }
+}
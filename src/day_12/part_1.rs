@@ -1,13 +1,8 @@
 use super::field::Field;
 
-pub const INPUT: &str = "<x=-1, y=7, z=3>
-<x=12, y=2, z=-13>
-<x=14, y=18, z=-8>
-<x=17, y=4, z=-4>";
+pub const INPUT: &str = include_str!("../../inputs/day_12.txt");
 
-pub fn run() {
-    let field: Field = INPUT.parse().unwrap();
-    let energy = field.step_n(1000).calculate_energy();
-
-    dbg!(energy);
+/// Total energy in the system after 1000 steps.
+pub fn total_energy(field: Field) -> isize {
+    field.step_n(1000).calculate_energy()
 }
@@ -1,9 +1,9 @@
 use super::field::Field;
 use num::Integer;
 
-pub fn run() {
-    let start_field: Field = super::part_1::INPUT.parse().unwrap();
-
+/// Number of steps until the moons are back at a previous state, which is
+/// when every axis repeats at once.
+pub fn steps_until_repeat(start_field: &Field) -> usize {
     let (steps_x, _) =
         std::iter::successors(Some((2usize, start_field.step())), |(steps, field)| {
             Some((steps + 1, field.step()))
@@ -28,5 +28,5 @@ pub fn run() {
         .last()
         .unwrap();
 
-    dbg!(steps_x.lcm(&steps_y).lcm(&steps_z));
+    steps_x.lcm(&steps_y).lcm(&steps_z)
 }
@@ -1,13 +1,31 @@
-use super::computer::{
-    Computer,
-    Reader,
-    Writer,
+use super::patches;
+use crate::intcode::{
+    codec::{
+        self,
+        Codec,
+        CodecError,
+    },
+    computer::{
+        Computer,
+        ComputerError,
+        Reader,
+        Writer,
+    },
+    journal::Journal,
+    patch::Patch,
+    program::Program,
 };
 use crossbeam_channel::bounded;
 use std::{
     collections::BTreeMap,
+    convert::TryFrom,
     sync::{
+        atomic::{
+            AtomicUsize,
+            Ordering,
+        },
         Arc,
+        Condvar,
         Mutex,
     },
     thread,
@@ -16,12 +34,26 @@ use std::{
 #[derive(Debug, Default)]
 pub struct Arcade {
     pub tiles: BTreeMap<Position, Tile>,
-    quarters: isize,
+    patches: Vec<Patch>,
     draw: bool,
+    /// Software to run instead of the puzzle input.
+    software: Option<Program>,
+    journal: Option<Journal>,
 }
 
+#[derive(Debug)]
 pub enum RunError {
     Receive(crossbeam_channel::RecvError),
+    Codec(CodecError),
+    Computer(ComputerError),
+    /// The thread running the software panicked.
+    Panicked,
+}
+
+impl From<ComputerError> for RunError {
+    fn from(err: ComputerError) -> Self {
+        Self::Computer(err)
+    }
 }
 
 impl From<crossbeam_channel::RecvError> for RunError {
@@ -30,91 +62,141 @@ impl From<crossbeam_channel::RecvError> for RunError {
     }
 }
 
+impl From<CodecError> for RunError {
+    fn from(err: CodecError) -> Self {
+        Self::Codec(err)
+    }
+}
+
 impl Arcade {
     pub fn with_quarters(self, quarters: isize) -> Self {
-        Self { quarters, ..self }
+        self.with_patch(patches::free_play(quarters))
+    }
+
+    pub fn with_patch(mut self, patch: Patch) -> Self {
+        self.patches.push(patch);
+        self
+    }
+
+    pub fn with_software(self, software: Program) -> Self {
+        Self {
+            software: Some(software),
+            ..self
+        }
+    }
+
+    /// Records the run of the arcade software to replay it.
+    pub fn with_journal(self, journal: &Journal) -> Self {
+        Self {
+            journal: Some(journal.clone()),
+            ..self
+        }
     }
 
     pub fn set_draw(self) -> Self {
         Self { draw: true, ..self }
     }
 
-    pub fn run(&mut self) -> isize {
+    pub fn run(&mut self) -> Result<isize, RunError> {
         let (sender_output, receiver_output) = bounded(0);
 
-        let quarters = self.quarters;
-        let current_ball = Arc::new(Mutex::new(Position::default()));
-        let current_paddle = Arc::new(Mutex::new(Position::default()));
+        let patches = self.patches.clone();
+        let journal = self.journal.clone();
+        let software = self.software.clone().unwrap_or_else(software);
+        let screen = Arc::new((Mutex::new(Screen::default()), Condvar::new()));
 
-        {
-            let current_ball = Arc::clone(&current_ball);
-            let current_paddle = Arc::clone(&current_paddle);
+        let handle = {
+            let screen = Arc::clone(&screen);
+            let sent = Arc::new(AtomicUsize::new(0));
 
             thread::spawn(move || {
                 let bot = Bot {
-                    current_ball,
-                    current_paddle,
+                    screen,
+                    sent: Arc::clone(&sent),
                 };
 
-                let input = Reader::Tester(Box::new(bot));
-                let output = Writer::Channel(sender_output);
-
-                let mut software = include_str!("arcade_software.txt")
-                    .split(',')
-                    .map(|split| split.trim().parse())
-                    .collect::<Result<Vec<isize>, std::num::ParseIntError>>()
-                    .unwrap();
-
-                software[0] = quarters;
-
-                let mut computer = Computer::default()
-                    .with_software(software)
-                    .with_input(input)
-                    .with_output(output);
+                let input = Reader::Tester(Box::new(
+                    bot.flat_map(|joystick| ArcadeCodec.encode(&joystick)),
+                ));
+                let output = Writer::Channel(sender_output).map(move |value| {
+                    sent.fetch_add(1, Ordering::SeqCst);
+                    value
+                });
+
+                let mut computer = patches.into_iter().fold(
+                    Computer::default()
+                        .with_software(software.into())
+                        .with_input(input)
+                        .with_output(output),
+                    Computer::with_patch,
+                );
+
+                if let Some(journal) = &journal {
+                    computer = computer.with_journal(journal);
+                }
 
-                computer.run().unwrap();
-            });
-        }
+                computer.run().map(|_| ())
+            })
+        };
 
         let mut draw = false;
         let mut score = 0;
+        let mut error = None;
+
+        while let Some(message) = codec::receive(&ArcadeCodec, &receiver_output) {
+            let message = match message {
+                Ok(message) => message,
+                Err(codec_error) => {
+                    error = Some(codec_error);
+                    break;
+                }
+            };
 
-        loop {
-            let x = receiver_output.recv();
-            let y = receiver_output.recv();
-            let score_tile = receiver_output.recv();
+            match message {
+                Display::Score(value) => {
+                    score = value;
+                    draw = true;
+                }
 
-            if x.is_err() || y.is_err() || score_tile.is_err() {
-                break;
-            }
+                Display::Tile(position, tile) => {
+                    let mut screen = screen.0.lock().unwrap();
 
-            let x = x.unwrap();
-            let y = y.unwrap();
-            let score_tile = score_tile.unwrap();
+                    if tile == Tile::Ball {
+                        screen.ball = position;
+                    }
 
-            if x == -1 && y == 0 {
-                score = score_tile;
-                draw = true;
-            } else {
-                let tile = score_tile.into();
+                    if tile == Tile::HorizontalPaddle {
+                        screen.paddle = position;
+                    }
 
-                if tile == Tile::Ball {
-                    *current_ball.lock().unwrap() = Position { x, y };
-                }
+                    drop(screen);
+
+                    self.tiles.insert(position, tile);
 
-                if tile == Tile::HorizontalPaddle {
-                    *current_paddle.lock().unwrap() = Position { x, y };
+                    if (tile == Tile::Ball || tile == Tile::HorizontalPaddle) && self.draw && draw {
+                        self.draw();
+                    };
                 }
+            }
+
+            screen.0.lock().unwrap().drawn += ArcadeCodec.message_size();
+            screen.1.notify_all();
+        }
 
-                self.tiles.insert(Position { x, y }, tile);
+        if let Some(error) = error {
+            // Stops the bot and the software so that the thread ends.
+            screen.0.lock().unwrap().stopped = true;
+            screen.1.notify_all();
+            drop(receiver_output);
 
-                if (tile == Tile::Ball || tile == Tile::HorizontalPaddle) && self.draw && draw {
-                    self.draw();
-                };
-            }
+            handle.join().map_err(|_| RunError::Panicked)?.ok();
+
+            return Err(error.into());
         }
 
-        score
+        handle.join().map_err(|_| RunError::Panicked)??;
+
+        Ok(score)
     }
 
     pub fn draw(&self) {
@@ -128,29 +210,109 @@ impl Arcade {
     }
 }
 
+pub const ARCADE_SOFTWARE: &str = include_str!("../../inputs/day_13.txt");
+
+/// The puzzle input.
+pub fn software() -> Program {
+    ARCADE_SOFTWARE.parse().unwrap()
+}
+
+/// What the bot sees of the screen. `drawn` counts the output values the
+/// arcade has displayed so far, `stopped` is set when the arcade can't
+/// display any more.
+#[derive(Debug, Default)]
+struct Screen {
+    ball: Position,
+    paddle: Position,
+    drawn: usize,
+    stopped: bool,
+}
+
+/// Moves the joystick towards the ball. Before every move it waits until
+/// the arcade displayed everything the software output, so that it never
+/// sees the ball of an earlier frame. It stops moving once the arcade
+/// stopped.
 struct Bot {
-    pub current_ball: Arc<Mutex<Position>>,
-    pub current_paddle: Arc<Mutex<Position>>,
+    screen: Arc<(Mutex<Screen>, Condvar)>,
+    /// Output values of the software.
+    sent: Arc<AtomicUsize>,
 }
 
 impl Iterator for Bot {
-    type Item = isize;
+    type Item = Joystick;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let ball = self.current_ball.lock().unwrap();
-        let paddle = self.current_paddle.lock().unwrap();
+        let sent = self.sent.load(Ordering::SeqCst);
+        let (screen, displayed) = &*self.screen;
+        let screen = displayed
+            .wait_while(screen.lock().unwrap(), |screen| {
+                !screen.stopped && screen.drawn < sent
+            })
+            .unwrap();
+
+        if screen.stopped {
+            return None;
+        }
+
+        let joystick = match screen.ball.x.cmp(&screen.paddle.x) {
+            std::cmp::Ordering::Equal => Joystick::Neutral,
+            std::cmp::Ordering::Greater => Joystick::Right,
+            std::cmp::Ordering::Less => Joystick::Left,
+        };
+
+        Some(joystick)
+    }
+}
+
+/// Display protocol of the arcade cabinet. The software outputs `(x, y, tile)`
+/// triples and `(-1, 0, score)` for the score display and reads the position
+/// of the joystick.
+pub struct ArcadeCodec;
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum Display {
+    Tile(Position, Tile),
+    Score(isize),
+}
 
-        let direction = match ball.x.cmp(&paddle.x) {
-            std::cmp::Ordering::Equal => 0,
-            std::cmp::Ordering::Greater => 1,
-            std::cmp::Ordering::Less => -1,
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Joystick {
+    Left,
+    Neutral,
+    Right,
+}
+
+impl Codec for ArcadeCodec {
+    type Message = Display;
+    type Command = Joystick;
+
+    fn message_size(&self) -> usize {
+        3
+    }
+
+    fn decode(&self, values: &[isize]) -> Result<Self::Message, CodecError> {
+        match *values {
+            [-1, 0, score] => Ok(Display::Score(score)),
+            [x, y, tile] => Ok(Display::Tile(Position { x, y }, Tile::try_from(tile)?)),
+            _ => Err(CodecError::Truncated {
+                expected: 3,
+                values: values.to_vec(),
+            }),
+        }
+    }
+
+    fn encode(&self, command: &Self::Command) -> Vec<isize> {
+        let value = match command {
+            Joystick::Left => -1,
+            Joystick::Neutral => 0,
+            Joystick::Right => 1,
         };
 
-        Some(direction)
+        vec![value]
     }
 }
 
-#[derive(Debug, Default, Ord, PartialOrd, Eq, PartialEq)]
+#[derive(Debug, Default, Ord, PartialOrd, Eq, PartialEq, Copy, Clone)]
 pub struct Position {
     pub x: isize,
     pub y: isize,
@@ -165,15 +327,100 @@ pub enum Tile {
     Ball,
 }
 
-impl From<isize> for Tile {
-    fn from(i: isize) -> Self {
+impl TryFrom<isize> for Tile {
+    type Error = CodecError;
+
+    fn try_from(i: isize) -> Result<Self, Self::Error> {
         match i {
-            0 => Self::Empty,
-            1 => Self::Wall,
-            2 => Self::Block,
-            3 => Self::HorizontalPaddle,
-            4 => Self::Ball,
-            _ => unreachable!(),
+            0 => Ok(Self::Empty),
+            1 => Ok(Self::Wall),
+            2 => Ok(Self::Block),
+            3 => Ok(Self::HorizontalPaddle),
+            4 => Ok(Self::Ball),
+            value => Err(CodecError::OutOfRange {
+                field: "tile",
+                value,
+            }),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Arcade,
+        ArcadeCodec,
+        Display,
+        Joystick,
+        Position,
+        RunError,
+        Tile,
+    };
+    use crate::intcode::{
+        codec::{
+            decode_all,
+            Codec,
+            CodecError,
+        },
+        computer::ComputerError,
+        patch::PatchError,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn arcade_codec() {
+        let expected = vec![
+            Display::Tile(Position { x: 1, y: 2 }, Tile::Ball),
+            Display::Score(12345),
+        ];
+
+        assert_eq!(
+            Ok(expected),
+            decode_all(&ArcadeCodec, &[1, 2, 4, -1, 0, 12345])
+        );
+
+        assert_eq!(
+            Err(CodecError::OutOfRange {
+                field: "tile",
+                value: 5,
+            }),
+            decode_all(&ArcadeCodec, &[1, 2, 5])
+        );
+
+        assert_eq!(vec![-1], ArcadeCodec.encode(&Joystick::Left));
+    }
+
+    #[test]
+    fn arcade_run_errors() {
+        let run = |software: &str| {
+            Arcade::default()
+                .with_software(software.parse().unwrap())
+                .run()
+        };
+
+        assert_eq!(5, run("104,-1,104,0,104,5,99").unwrap());
+
+        assert!(matches!(
+            run("104,1,104,2,99"),
+            Err(RunError::Codec(CodecError::Truncated { .. }))
+        ));
+        // The software keeps playing after a tile the arcade can't display.
+        assert!(matches!(
+            run("104,1,104,2,104,9,3,0,104,0,1105,1,6"),
+            Err(RunError::Codec(CodecError::OutOfRange { .. }))
+        ));
+        assert!(matches!(
+            run("104,1,104,2,104,0,301"),
+            Err(RunError::Computer(ComputerError::InvalidParameterMode(3)))
+        ));
+        assert!(matches!(
+            Arcade::default()
+                .with_software("104,-1,104,0,104,5,99".parse().unwrap())
+                .with_quarters(2)
+                .run(),
+            Err(RunError::Computer(ComputerError::Patch(
+                PatchError::OriginalMismatch { address: 0, .. }
+            )))
+        ));
+    }
+}
@@ -1,5 +1,57 @@
 pub mod arcade;
 pub mod canvas;
-pub mod computer;
 pub mod part_1;
 pub mod part_2;
+pub mod patches;
+
+use crate::{
+    intcode::{
+        journal::Journal,
+        program::Program,
+    },
+    solution::{
+        Answer,
+        Solution,
+        SolutionError,
+    },
+};
+
+/// The software of the arcade cabinet.
+#[derive(Debug)]
+pub struct Day13 {
+    software: Program,
+    visualize: bool,
+    journal: Option<Journal>,
+}
+
+impl Solution for Day13 {
+    fn parse(input: &str) -> Result<Self, SolutionError> {
+        Ok(Self {
+            software: input.parse()?,
+            visualize: false,
+            journal: None,
+        })
+    }
+
+    /// Opens a canvas with the screen after the game exits.
+    fn part_one(&self) -> Result<Answer, SolutionError> {
+        part_1::count_blocks(self.software.clone(), self.visualize, self.journal.as_ref())
+            .map(Answer::from)
+            .map_err(|err| SolutionError::NoAnswer(format!("{:?}", err)))
+    }
+
+    /// Shows the game while the bot plays it.
+    fn part_two(&self) -> Result<Answer, SolutionError> {
+        part_2::play(self.software.clone(), self.visualize, self.journal.as_ref())
+            .map(Answer::from)
+            .map_err(|err| SolutionError::NoAnswer(format!("{:?}", err)))
+    }
+
+    fn set_visualize(&mut self, visualize: bool) {
+        self.visualize = visualize;
+    }
+
+    fn set_journals(&mut self, journals: &[Journal]) {
+        self.journal = journals.first().cloned();
+    }
+}
@@ -1,17 +1,35 @@
 use super::arcade::{
     Arcade,
+    RunError,
     Tile,
 };
+use crate::intcode::{
+    journal::Journal,
+    program::Program,
+};
+
+/// Number of block tiles on the screen when the game exits. The run is
+/// recorded in `journal` if given.
+pub fn count_blocks(
+    software: Program,
+    draw: bool,
+    journal: Option<&Journal>,
+) -> Result<usize, RunError> {
+    let mut arcade = Arcade::default().with_software(software);
+
+    if let Some(journal) = journal {
+        arcade = arcade.with_journal(journal);
+    }
 
-pub fn run() {
-    let mut arcade = Arcade::default();
-    let score = arcade.run();
+    arcade.run()?;
 
-    let blocks = arcade
+    if draw {
+        arcade.draw();
+    }
+
+    Ok(arcade
         .tiles
         .values()
         .filter(|tile| **tile == Tile::Block)
-        .count();
-
-    dbg!((blocks, score));
+        .count())
 }
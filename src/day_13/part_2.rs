@@ -1,8 +1,24 @@
-use super::arcade::Arcade;
+use super::arcade::{
+    Arcade,
+    RunError,
+};
+use crate::intcode::{
+    journal::Journal,
+    program::Program,
+};
 
-pub fn run() {
-    let mut arcade = Arcade::default().with_quarters(2);
-    let score = arcade.run();
+/// Score after the last block is broken. `draw` shows the game while the
+/// bot plays it. The run is recorded in `journal` if given.
+pub fn play(software: Program, draw: bool, journal: Option<&Journal>) -> Result<isize, RunError> {
+    let mut arcade = Arcade::default().with_software(software).with_quarters(2);
 
-    dbg!(score);
+    if draw {
+        arcade = arcade.set_draw();
+    }
+
+    if let Some(journal) = journal {
+        arcade = arcade.with_journal(journal);
+    }
+
+    arcade.run()
 }
@@ -0,0 +1,73 @@
+//! Memory patches for the arcade software.
+use crate::intcode::patch::Patch;
+
+/// Address that holds the number of quarters inserted into the cabinet.
+const QUARTERS: usize = 0;
+
+/// Addresses of the row of the screen that holds the paddle, without the
+/// walls on both ends.
+const PADDLE_ROW: std::ops::RangeInclusive<usize> = 1305..=1337;
+const PADDLE: usize = 1321;
+
+pub fn free_play(quarters: isize) -> Patch {
+    Patch::new("free play", "insert quarters before the game boots")
+        .with_edit(QUARTERS, 1, quarters)
+}
+
+/// Fills the whole paddle row with paddle tiles so the ball can never be
+/// missed, no matter what the joystick does.
+pub fn wide_paddle() -> Patch {
+    PADDLE_ROW.fold(
+        Patch::new("wide paddle", "widen the paddle to the whole screen"),
+        |patch, address| {
+            let original = if address == PADDLE { 3 } else { 0 };
+            patch.with_edit(address, original, 3)
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        free_play,
+        wide_paddle,
+    };
+    use crate::intcode::{
+        computer::{
+            Computer,
+            Reader,
+        },
+        program::Program,
+    };
+    use pretty_assertions::assert_eq;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn wide_paddle_clears_all_blocks() {
+        let software: Program = crate::day_13::arcade::ARCADE_SOFTWARE.parse().unwrap();
+
+        let mut computer = Computer::default()
+            .with_software(software.into())
+            .with_input(Reader::Tester(Box::new(std::iter::repeat(0))))
+            .with_patch(free_play(2))
+            .with_patch(wide_paddle());
+
+        computer.run().unwrap();
+
+        let mut screen = BTreeMap::new();
+        let mut score = 0;
+
+        for chunk in computer.output.values().chunks(3) {
+            match chunk {
+                [-1, 0, value] => score = *value,
+                [x, y, tile] => {
+                    screen.insert((*x, *y), *tile);
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        assert_eq!(0, screen.values().filter(|tile| **tile == 2).count());
+        assert_eq!(10025, score);
+    }
+}
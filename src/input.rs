@@ -0,0 +1,176 @@
+//! Puzzle inputs.
+//!
+//! Every account gets its own puzzle input. Inputs are read at runtime from
+//! `inputs/day_NN.txt`, so another input is used by replacing the file
+//! instead of recompiling. Without the file a day falls back to the input
+//! embedded into the binary. Each day parses the text itself.
+
+use crate::{
+    day_01,
+    day_02,
+    day_03,
+    day_04,
+    day_05,
+    day_07,
+    day_08,
+    day_09,
+    day_11,
+    day_12,
+    day_13,
+};
+use std::path::{
+    Path,
+    PathBuf,
+};
+
+/// Directory the inputs are read from by default.
+pub const DIRECTORY: &str = "inputs";
+
+/// Where an input was read from.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Source {
+    File(PathBuf),
+    Embedded,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Input {
+    pub day: usize,
+    pub source: Source,
+    pub text: String,
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    UnknownDay(usize),
+    Io(PathBuf, std::io::Error),
+}
+
+impl std::fmt::Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownDay(day) => write!(f, "no input for day {}", day),
+            Self::Io(path, err) => write!(f, "{}: {}", path.display(), err),
+        }
+    }
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Embedded => write!(f, "embedded input"),
+        }
+    }
+}
+
+/// Path of the input of a day in `directory`, like `inputs/day_08.txt`.
+pub fn path<P: AsRef<Path>>(directory: P, day: usize) -> PathBuf {
+    directory.as_ref().join(format!("day_{:02}.txt", day))
+}
+
+/// The input compiled into the binary.
+pub fn embedded(day: usize) -> Option<&'static str> {
+    Some(match day {
+        1 => day_01::part_1::INPUT,
+        2 => day_02::part_1::INPUT,
+        3 => day_03::INPUT,
+        4 => day_04::INPUT,
+        5 => day_05::part_1::INPUT_MEMORY,
+        7 => day_07::part_1::AMPLIFIER_SOFTWARE,
+        8 => day_08::part_1::PIXELS,
+        9 => day_09::part_1::BOOST_SOFTWARE,
+        11 => day_11::roboter::ROBOT_SOFTWARE,
+        12 => day_12::part_1::INPUT,
+        13 => day_13::arcade::ARCADE_SOFTWARE,
+        _ => return None,
+    })
+}
+
+/// Loads the input of a day from `directory` or falls back to the embedded
+/// input if there is no file for it.
+pub fn load<P: AsRef<Path>>(directory: P, day: usize) -> Result<Input, InputError> {
+    let path = path(directory, day);
+
+    if path.is_file() {
+        return read(path, day);
+    }
+
+    embedded(day)
+        .map(|text| Input {
+            day,
+            source: Source::Embedded,
+            text: text.to_string(),
+        })
+        .ok_or(InputError::UnknownDay(day))
+}
+
+/// Reads the input of a day from a file.
+pub fn read<P: AsRef<Path>>(path: P, day: usize) -> Result<Input, InputError> {
+    let path = path.as_ref().to_path_buf();
+
+    match std::fs::read_to_string(&path) {
+        Ok(text) => Ok(Input {
+            day,
+            source: Source::File(path),
+            text,
+        }),
+        Err(err) => Err(InputError::Io(path, err)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        embedded,
+        load,
+        path,
+        read,
+        InputError,
+        Source,
+    };
+    use crate::solution;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    #[test]
+    fn input_files_are_embedded() {
+        for day in solution::days() {
+            let embedded = embedded(day).unwrap();
+            let file =
+                std::fs::read_to_string(path(concat!(env!("CARGO_MANIFEST_DIR"), "/inputs"), day))
+                    .unwrap();
+
+            assert_eq!(file, embedded, "day {}", day);
+        }
+
+        assert_eq!(None, embedded(10));
+    }
+
+    #[test]
+    fn load_falls_back_to_embedded() {
+        let directory = std::env::temp_dir().join(format!("inputs_{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let input = load(&directory, 4).unwrap();
+        assert_eq!(Source::Embedded, input.source);
+        assert_eq!("153517-630395\n", input.text);
+
+        std::fs::write(path(&directory, 4), "100000-200000\n").unwrap();
+
+        let input = load(&directory, 4).unwrap();
+        assert_eq!(Source::File(path(&directory, 4)), input.source);
+        assert_eq!("100000-200000\n", input.text);
+
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert!(matches!(
+            load(&directory, 10),
+            Err(InputError::UnknownDay(10))
+        ));
+        assert!(matches!(
+            read(PathBuf::from("missing/day_04.txt"), 4),
+            Err(InputError::Io(_, _))
+        ));
+    }
+}
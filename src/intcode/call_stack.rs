@@ -0,0 +1,219 @@
+use std::collections::BTreeMap;
+
+/// Call frames inferred from the calling convention of compiled Intcode
+/// software. A caller stores the return address at `rb+k` and jumps to the
+/// function which then grows its frame with `AdjustRelativeBase`. The function
+/// returns with an indirect jump through the stored return address.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct CallStack {
+    /// Active frames, the innermost frame last.
+    pub frames: Vec<Frame>,
+    /// Writes since the last jump that could hold a return address.
+    stores: BTreeMap<usize, isize>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Frame {
+    /// Guessed entry address of the function.
+    pub entry: usize,
+    /// Address of the jump that called the function.
+    pub call_site: usize,
+    pub return_address: usize,
+    /// Address the return address was stored at.
+    pub return_slot: usize,
+    /// Relative base at the time of the call.
+    pub base: isize,
+    /// How far the function moved the relative base.
+    pub size: isize,
+}
+
+/// Frames of a call stack from the innermost to the outermost function.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Backtrace {
+    pub frames: Vec<StackFrame>,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct StackFrame {
+    /// Address that is executed in this frame, the call site for every frame
+    /// but the innermost.
+    pub pc: usize,
+    pub entry: usize,
+    pub size: isize,
+    /// Offset of the return address from the relative base of the caller.
+    /// `None` for the outermost frame.
+    pub return_offset: Option<isize>,
+}
+
+impl CallStack {
+    pub fn store(&mut self, address: usize, value: isize) {
+        self.stores.insert(address, value);
+    }
+
+    /// Called for every jump instruction at `pc` with the jump target if the
+    /// jump was taken and the address the target was read from.
+    pub fn jump(
+        &mut self,
+        pc: usize,
+        size: usize,
+        target: Option<usize>,
+        source: isize,
+        relative_base: isize,
+    ) {
+        let stores = std::mem::take(&mut self.stores);
+
+        let target = match target {
+            Some(target) => target,
+            None => return,
+        };
+
+        let returned = self.frames.iter().rposition(|frame| {
+            frame.return_slot as isize == source && frame.return_address == target
+        });
+
+        if let Some(depth) = returned {
+            self.frames.truncate(depth);
+            return;
+        }
+
+        let return_address = pc + size;
+
+        let return_slot = stores
+            .iter()
+            .find(|(_, value)| **value == return_address as isize)
+            .map(|(address, _)| *address);
+
+        if let Some(return_slot) = return_slot {
+            self.frames.push(Frame {
+                entry: target,
+                call_site: pc,
+                return_address,
+                return_slot,
+                base: relative_base,
+                size: 0,
+            });
+        }
+    }
+
+    pub fn adjust_relative_base(&mut self, relative_base: isize) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.size = relative_base - frame.base;
+        }
+    }
+
+    pub fn backtrace(&self, pc: usize) -> Backtrace {
+        let mut frames = Vec::with_capacity(self.frames.len() + 1);
+        let mut pc = pc;
+
+        for frame in self.frames.iter().rev() {
+            frames.push(StackFrame {
+                pc,
+                entry: frame.entry,
+                size: frame.size,
+                return_offset: Some(frame.return_slot as isize - frame.base),
+            });
+
+            pc = frame.call_site;
+        }
+
+        frames.push(StackFrame {
+            pc,
+            entry: 0,
+            size: 0,
+            return_offset: None,
+        });
+
+        Backtrace { frames }
+    }
+}
+
+impl std::fmt::Display for Backtrace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (depth, frame) in self.frames.iter().enumerate() {
+            write!(
+                f,
+                "#{:<3} {:>6}  in function at {}",
+                depth, frame.pc, frame.entry
+            )?;
+
+            if let Some(return_offset) = frame.return_offset {
+                write!(
+                    f,
+                    " (frame size {}, return address at rb{:+})",
+                    frame.size, return_offset
+                )?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Frame,
+        StackFrame,
+    };
+    use crate::intcode::computer::{
+        Computer,
+        ComputerError,
+    };
+    use pretty_assertions::assert_eq;
+
+    // Sets up a stack at 20, stores the return address 9 at rb+0 and calls
+    // the function at 10 which reserves a frame of 3 values.
+    const CALLER: &[isize] = &[109, 20, 21101, 9, 0, 0, 1105, 1, 10, 99];
+
+    #[test]
+    fn call_stack_return() {
+        let mut input_software = CALLER.to_vec();
+        input_software.extend(&[109, 3, 109, -3, 2106, 0, 0]);
+
+        let mut computer = Computer::from(input_software).with_call_stack();
+        computer.run().unwrap();
+
+        assert_eq!(Vec::<Frame>::new(), computer.call_stack.unwrap().frames);
+    }
+
+    #[test]
+    fn call_stack_crash_backtrace() {
+        let mut input_software = CALLER.to_vec();
+        input_software.extend(&[109, 3, 204, -100]);
+
+        let mut computer = Computer::from(input_software).with_call_stack();
+        let report = computer.run_with_crash_report().unwrap_err();
+
+        match report.error {
+            ComputerError::NegativeAddress(address) => assert_eq!(-77, address),
+            ref got => panic!("unexpected error: {:?}", got),
+        }
+
+        let backtrace = report.backtrace.unwrap();
+
+        let expected = vec![
+            StackFrame {
+                pc: 12,
+                entry: 10,
+                size: 3,
+                return_offset: Some(0),
+            },
+            StackFrame {
+                pc: 6,
+                entry: 0,
+                size: 0,
+                return_offset: None,
+            },
+        ];
+
+        assert_eq!(expected, backtrace.frames);
+
+        let expected = "#0       12  in function at 10 (frame size 3, return address at rb+0)
+#1        6  in function at 0
+";
+
+        assert_eq!(expected, backtrace.to_string());
+    }
+}
@@ -0,0 +1,151 @@
+use crossbeam_channel::Receiver;
+
+/// Protocol of a device that talks to Intcode software. Output values are
+/// grouped into typed messages and typed commands are encoded into input
+/// values.
+pub trait Codec {
+    type Message;
+    type Command;
+
+    /// Number of output values that make up one message.
+    fn message_size(&self) -> usize;
+
+    /// Decodes one message from exactly `message_size` values.
+    fn decode(&self, values: &[isize]) -> Result<Self::Message, CodecError>;
+
+    fn encode(&self, command: &Self::Command) -> Vec<isize>;
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum CodecError {
+    /// The output ended in the middle of a message.
+    Truncated {
+        expected: usize,
+        values: Vec<isize>,
+    },
+    OutOfRange {
+        field: &'static str,
+        value: isize,
+    },
+}
+
+/// Receives the next message. Returns `None` if the output ended cleanly
+/// between two messages.
+pub fn receive<C: Codec>(
+    codec: &C,
+    receiver: &Receiver<isize>,
+) -> Option<Result<C::Message, CodecError>> {
+    let expected = codec.message_size();
+    let mut values = Vec::with_capacity(expected);
+
+    while values.len() < expected {
+        match receiver.recv() {
+            Ok(value) => values.push(value),
+            Err(_) if values.is_empty() => return None,
+            Err(_) => return Some(Err(CodecError::Truncated { expected, values })),
+        }
+    }
+
+    Some(codec.decode(&values))
+}
+
+/// Decodes every message of a finished run.
+pub fn decode_all<C: Codec>(codec: &C, values: &[isize]) -> Result<Vec<C::Message>, CodecError> {
+    let expected = codec.message_size();
+
+    values
+        .chunks(expected)
+        .map(|chunk| {
+            if chunk.len() < expected {
+                Err(CodecError::Truncated {
+                    expected,
+                    values: chunk.to_vec(),
+                })
+            } else {
+                codec.decode(chunk)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        decode_all,
+        receive,
+        Codec,
+        CodecError,
+    };
+    use crossbeam_channel::unbounded;
+    use pretty_assertions::assert_eq;
+
+    /// Pairs of a key and a digit.
+    struct Pairs;
+
+    impl Codec for Pairs {
+        type Message = (isize, u8);
+        type Command = isize;
+
+        fn message_size(&self) -> usize {
+            2
+        }
+
+        fn decode(&self, values: &[isize]) -> Result<Self::Message, CodecError> {
+            match values[1] {
+                0..=9 => Ok((values[0], values[1] as u8)),
+                value => Err(CodecError::OutOfRange {
+                    field: "digit",
+                    value,
+                }),
+            }
+        }
+
+        fn encode(&self, command: &Self::Command) -> Vec<isize> {
+            vec![*command]
+        }
+    }
+
+    #[test]
+    fn codec_decode_all() {
+        assert_eq!(Ok(vec![(1, 2), (3, 4)]), decode_all(&Pairs, &[1, 2, 3, 4]));
+
+        assert_eq!(
+            Err(CodecError::Truncated {
+                expected: 2,
+                values: vec![3],
+            }),
+            decode_all(&Pairs, &[1, 2, 3])
+        );
+
+        assert_eq!(
+            Err(CodecError::OutOfRange {
+                field: "digit",
+                value: 10,
+            }),
+            decode_all(&Pairs, &[1, 10])
+        );
+    }
+
+    #[test]
+    fn codec_receive() {
+        let (sender, receiver) = unbounded();
+
+        for value in &[1, 2, 3] {
+            sender.send(*value).unwrap();
+        }
+
+        drop(sender);
+
+        assert_eq!(Some(Ok((1, 2))), receive(&Pairs, &receiver));
+
+        assert_eq!(
+            Some(Err(CodecError::Truncated {
+                expected: 2,
+                values: vec![3],
+            })),
+            receive(&Pairs, &receiver)
+        );
+
+        assert_eq!(None, receive(&Pairs, &receiver));
+    }
+}
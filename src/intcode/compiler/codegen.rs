@@ -0,0 +1,543 @@
+use super::{
+    parser::{
+        Expression,
+        Function,
+        Operator,
+        Statement,
+    },
+    CompileError,
+};
+use std::collections::BTreeMap;
+
+const ADD: isize = 1;
+const MUL: isize = 2;
+const READ: isize = 3;
+const WRITE: isize = 4;
+const JUMP_IF_TRUE: isize = 5;
+const JUMP_IF_FALSE: isize = 6;
+const LESS_THAN: isize = 7;
+const EQUALS: isize = 8;
+const ADJUST_RELATIVE_BASE: isize = 9;
+const HALT: isize = 99;
+
+/// Slot of the return address in a frame.
+const RETURN_ADDRESS: usize = 0;
+/// Slot of the first parameter which is also used for the return value.
+const RETURN_VALUE: usize = 1;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Operand {
+    Immediate(isize),
+    /// Slot in the frame of the current function.
+    Slot(usize),
+    /// Slot in the frame of a function that is called next.
+    Outgoing(usize),
+    Label(usize),
+    /// Size of the frame of the current function times the factor.
+    FrameSize(isize),
+}
+
+#[derive(Debug)]
+enum Item {
+    Label(usize),
+    Instruction(isize, Vec<Operand>),
+}
+
+/// Instructions of one function with the size of its frame.
+struct Code {
+    items: Vec<Item>,
+    frame_size: usize,
+}
+
+/// Generates code for functions that follow this calling convention:
+///
+/// The caller stores the arguments at `rb+1` and up, the return address at
+/// `rb+0` and jumps to the function. The function moves the relative base
+/// past its frame so slot `s` of the frame is at `rb+s-size`. It returns by
+/// storing the return value in slot 1, moving the relative base back and
+/// jumping through the return address at `rb+0`.
+#[derive(Default)]
+pub struct Codegen {
+    functions: BTreeMap<String, (usize, usize)>,
+    code: Vec<Code>,
+    next_label: usize,
+
+    items: Vec<Item>,
+    scopes: Vec<Vec<(String, usize)>>,
+    next_slot: usize,
+    frame_size: usize,
+    return_label: usize,
+}
+
+impl Codegen {
+    pub fn compile(mut self, functions: &[Function]) -> Result<Vec<isize>, CompileError> {
+        for function in functions {
+            let label = self.label();
+            let previous = self
+                .functions
+                .insert(function.name.clone(), (label, function.parameters.len()));
+
+            if previous.is_some() {
+                return Err(CompileError::DuplicateFunction(function.name.clone()));
+            }
+        }
+
+        let main = match self.functions.get("main") {
+            Some((label, 0)) => *label,
+            _ => return Err(CompileError::MissingMain),
+        };
+
+        let end = self.label();
+        let ret = self.label();
+
+        // The stack starts right behind the software.
+        self.emit(ADJUST_RELATIVE_BASE, &[Operand::Label(end)]);
+        self.emit(
+            ADD,
+            &[
+                Operand::Label(ret),
+                Operand::Immediate(0),
+                Operand::Outgoing(RETURN_ADDRESS),
+            ],
+        );
+        self.emit(JUMP_IF_TRUE, &[Operand::Immediate(1), Operand::Label(main)]);
+        self.items.push(Item::Label(ret));
+        self.emit(HALT, &[]);
+
+        self.finish_code();
+
+        for function in functions {
+            self.function(function)?;
+        }
+
+        self.code.push(Code {
+            items: vec![Item::Label(end)],
+            frame_size: 0,
+        });
+
+        Ok(self.assemble())
+    }
+
+    fn function(&mut self, function: &Function) -> Result<(), CompileError> {
+        let (label, _) = self.functions[&function.name];
+
+        self.return_label = self.label();
+        self.scopes = vec![function
+            .parameters
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (name.clone(), 1 + index))
+            .collect()];
+        self.next_slot = (1 + function.parameters.len()).max(RETURN_VALUE + 1);
+        self.frame_size = self.next_slot;
+
+        self.items.push(Item::Label(label));
+        self.emit(ADJUST_RELATIVE_BASE, &[Operand::FrameSize(1)]);
+
+        self.statements(&function.body)?;
+
+        self.copy(Operand::Immediate(0), RETURN_VALUE);
+        self.items.push(Item::Label(self.return_label));
+        self.emit(ADJUST_RELATIVE_BASE, &[Operand::FrameSize(-1)]);
+        self.emit(
+            JUMP_IF_FALSE,
+            &[Operand::Immediate(0), Operand::Outgoing(RETURN_ADDRESS)],
+        );
+
+        self.finish_code();
+
+        Ok(())
+    }
+
+    fn finish_code(&mut self) {
+        self.code.push(Code {
+            items: std::mem::take(&mut self.items),
+            frame_size: self.frame_size,
+        });
+    }
+
+    fn block(&mut self, statements: &[Statement]) -> Result<(), CompileError> {
+        let mark = self.next_slot;
+        self.scopes.push(Vec::new());
+
+        self.statements(statements)?;
+
+        self.scopes.pop();
+        self.next_slot = mark;
+
+        Ok(())
+    }
+
+    fn statements(&mut self, statements: &[Statement]) -> Result<(), CompileError> {
+        for statement in statements {
+            let mark = self.next_slot;
+            self.statement(statement)?;
+
+            // Temporaries are only alive during a statement, variables
+            // declared by it stay.
+            match statement {
+                Statement::Let(..) => {}
+                _ => self.next_slot = mark,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn statement(&mut self, statement: &Statement) -> Result<(), CompileError> {
+        match statement {
+            Statement::Let(name, expression) => {
+                let mark = self.next_slot;
+                let value = self.expression(expression)?;
+                self.next_slot = mark;
+
+                let slot = self.temporary();
+                self.copy(value, slot);
+                self.scopes.last_mut().unwrap().push((name.clone(), slot));
+            }
+
+            Statement::Assign(name, expression) => {
+                let slot = self.variable(name)?;
+                let value = self.expression(expression)?;
+                self.copy(value, slot);
+            }
+
+            Statement::If(condition, then, otherwise) => {
+                let otherwise_label = self.label();
+                let end_label = self.label();
+
+                let condition = self.expression(condition)?;
+                self.emit(JUMP_IF_FALSE, &[condition, Operand::Label(otherwise_label)]);
+
+                self.block(then)?;
+                self.emit(
+                    JUMP_IF_TRUE,
+                    &[Operand::Immediate(1), Operand::Label(end_label)],
+                );
+
+                self.items.push(Item::Label(otherwise_label));
+                self.block(otherwise)?;
+                self.items.push(Item::Label(end_label));
+            }
+
+            Statement::While(condition, body) => {
+                let top_label = self.label();
+                let end_label = self.label();
+
+                self.items.push(Item::Label(top_label));
+
+                let mark = self.next_slot;
+                let condition = self.expression(condition)?;
+                self.next_slot = mark;
+
+                self.emit(JUMP_IF_FALSE, &[condition, Operand::Label(end_label)]);
+                self.block(body)?;
+                self.emit(
+                    JUMP_IF_TRUE,
+                    &[Operand::Immediate(1), Operand::Label(top_label)],
+                );
+
+                self.items.push(Item::Label(end_label));
+            }
+
+            Statement::Return(expression) => {
+                let value = match expression {
+                    Some(expression) => self.expression(expression)?,
+                    None => Operand::Immediate(0),
+                };
+
+                self.copy(value, RETURN_VALUE);
+                self.emit(
+                    JUMP_IF_TRUE,
+                    &[Operand::Immediate(1), Operand::Label(self.return_label)],
+                );
+            }
+
+            Statement::Expression(expression) => {
+                self.expression(expression)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn expression(&mut self, expression: &Expression) -> Result<Operand, CompileError> {
+        let operand = match expression {
+            Expression::Number(value) => Operand::Immediate(*value),
+
+            Expression::Variable(name) => Operand::Slot(self.variable(name)?),
+
+            Expression::Call(name, arguments) => self.call(name, arguments)?,
+
+            Expression::Negate(expression) => match self.expression(expression)? {
+                Operand::Immediate(value) => Operand::Immediate(-value),
+                operand => self.result(MUL, operand, Operand::Immediate(-1)),
+            },
+
+            Expression::Not(expression) => match self.expression(expression)? {
+                Operand::Immediate(value) => Operand::Immediate((value == 0) as isize),
+                operand => self.result(EQUALS, operand, Operand::Immediate(0)),
+            },
+
+            Expression::Binary(operator, left, right) => {
+                let left = self.expression(left)?;
+                let right = self.expression(right)?;
+
+                self.binary(*operator, left, right)
+            }
+        };
+
+        Ok(operand)
+    }
+
+    fn binary(&mut self, operator: Operator, left: Operand, right: Operand) -> Operand {
+        use Operator::*;
+
+        if let (Operand::Immediate(left), Operand::Immediate(right)) = (left, right) {
+            return Operand::Immediate(fold(operator, left, right));
+        }
+
+        match operator {
+            Add => self.result(ADD, left, right),
+            Multiply => self.result(MUL, left, right),
+            Less => self.result(LESS_THAN, left, right),
+            Greater => self.result(LESS_THAN, right, left),
+            Equal => self.result(EQUALS, left, right),
+
+            Subtract => {
+                let right = match right {
+                    Operand::Immediate(value) => Operand::Immediate(-value),
+                    right => self.result(MUL, right, Operand::Immediate(-1)),
+                };
+
+                self.result(ADD, left, right)
+            }
+
+            LessEqual => {
+                let greater = self.result(LESS_THAN, right, left);
+                self.result(EQUALS, greater, Operand::Immediate(0))
+            }
+
+            GreaterEqual => {
+                let less = self.result(LESS_THAN, left, right);
+                self.result(EQUALS, less, Operand::Immediate(0))
+            }
+
+            NotEqual => {
+                let equal = self.result(EQUALS, left, right);
+                self.result(EQUALS, equal, Operand::Immediate(0))
+            }
+
+            // Both sides are always evaluated.
+            And => {
+                let left_zero = self.result(EQUALS, left, Operand::Immediate(0));
+                let right_zero = self.result(EQUALS, right, Operand::Immediate(0));
+                let zeros = self.result(ADD, left_zero, right_zero);
+                self.result(EQUALS, zeros, Operand::Immediate(0))
+            }
+
+            Or => {
+                let left_zero = self.result(EQUALS, left, Operand::Immediate(0));
+                let right_zero = self.result(EQUALS, right, Operand::Immediate(0));
+                let both_zero = self.result(MUL, left_zero, right_zero);
+                self.result(EQUALS, both_zero, Operand::Immediate(0))
+            }
+        }
+    }
+
+    fn call(&mut self, name: &str, arguments: &[Expression]) -> Result<Operand, CompileError> {
+        let expected = match name {
+            "input" => 0,
+            "output" => 1,
+            _ => match self.functions.get(name) {
+                Some((_, arity)) => *arity,
+                None => return Err(CompileError::UnknownFunction(name.to_string())),
+            },
+        };
+
+        if arguments.len() != expected {
+            return Err(CompileError::ArgumentCount {
+                function: name.to_string(),
+                expected,
+                found: arguments.len(),
+            });
+        }
+
+        // Arguments are evaluated before any of them is stored as another call
+        // in an argument would overwrite the outgoing slots.
+        let arguments = arguments
+            .iter()
+            .map(|argument| self.expression(argument))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let operand = match name {
+            "input" => {
+                let slot = self.temporary();
+                self.emit(READ, &[Operand::Slot(slot)]);
+                Operand::Slot(slot)
+            }
+
+            "output" => {
+                self.emit(WRITE, &[arguments[0]]);
+                Operand::Immediate(0)
+            }
+
+            _ => {
+                let (label, _) = self.functions[name];
+                let ret = self.label();
+
+                for (index, argument) in arguments.into_iter().enumerate() {
+                    self.emit(
+                        ADD,
+                        &[
+                            argument,
+                            Operand::Immediate(0),
+                            Operand::Outgoing(1 + index),
+                        ],
+                    );
+                }
+
+                self.emit(
+                    ADD,
+                    &[
+                        Operand::Label(ret),
+                        Operand::Immediate(0),
+                        Operand::Outgoing(RETURN_ADDRESS),
+                    ],
+                );
+                self.emit(
+                    JUMP_IF_TRUE,
+                    &[Operand::Immediate(1), Operand::Label(label)],
+                );
+                self.items.push(Item::Label(ret));
+
+                let slot = self.temporary();
+                self.emit(
+                    ADD,
+                    &[
+                        Operand::Outgoing(RETURN_VALUE),
+                        Operand::Immediate(0),
+                        Operand::Slot(slot),
+                    ],
+                );
+
+                Operand::Slot(slot)
+            }
+        };
+
+        Ok(operand)
+    }
+
+    /// Emits an instruction that stores its result in a new temporary.
+    fn result(&mut self, opcode: isize, left: Operand, right: Operand) -> Operand {
+        let slot = self.temporary();
+        self.emit(opcode, &[left, right, Operand::Slot(slot)]);
+        Operand::Slot(slot)
+    }
+
+    fn copy(&mut self, value: Operand, slot: usize) {
+        if value != Operand::Slot(slot) {
+            self.emit(ADD, &[value, Operand::Immediate(0), Operand::Slot(slot)]);
+        }
+    }
+
+    fn variable(&self, name: &str) -> Result<usize, CompileError> {
+        self.scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter().rev())
+            .find(|(variable, _)| variable == name)
+            .map(|(_, slot)| *slot)
+            .ok_or_else(|| CompileError::UnknownVariable(name.to_string()))
+    }
+
+    fn temporary(&mut self) -> usize {
+        let slot = self.next_slot;
+        self.next_slot += 1;
+        self.frame_size = self.frame_size.max(self.next_slot);
+        slot
+    }
+
+    fn label(&mut self) -> usize {
+        let label = self.next_label;
+        self.next_label += 1;
+        label
+    }
+
+    fn emit(&mut self, opcode: isize, operands: &[Operand]) {
+        self.items
+            .push(Item::Instruction(opcode, operands.to_vec()));
+    }
+
+    fn assemble(&self) -> Vec<isize> {
+        let mut labels = BTreeMap::new();
+        let mut address = 0;
+
+        for item in self.code.iter().flat_map(|code| &code.items) {
+            match item {
+                Item::Label(label) => {
+                    labels.insert(*label, address);
+                }
+
+                Item::Instruction(_, operands) => address += 1 + operands.len(),
+            }
+        }
+
+        let mut software = Vec::with_capacity(address);
+
+        for code in &self.code {
+            for item in &code.items {
+                if let Item::Instruction(opcode, operands) = item {
+                    let mut value = *opcode;
+                    let mut factor = 100;
+
+                    for operand in operands {
+                        let mode = match operand {
+                            Operand::Slot(_) | Operand::Outgoing(_) => 2,
+                            _ => 1,
+                        };
+
+                        value += mode * factor;
+                        factor *= 10;
+                    }
+
+                    software.push(value);
+
+                    for operand in operands {
+                        let frame_size = code.frame_size as isize;
+
+                        software.push(match *operand {
+                            Operand::Immediate(value) => value,
+                            Operand::Slot(slot) => slot as isize - frame_size,
+                            Operand::Outgoing(slot) => slot as isize,
+                            Operand::Label(label) => labels[&label] as isize,
+                            Operand::FrameSize(factor) => factor * frame_size,
+                        });
+                    }
+                }
+            }
+        }
+
+        software
+    }
+}
+
+fn fold(operator: Operator, left: isize, right: isize) -> isize {
+    use Operator::*;
+
+    let value = match operator {
+        Add => return left + right,
+        Subtract => return left - right,
+        Multiply => return left * right,
+        Less => left < right,
+        LessEqual => left <= right,
+        Greater => left > right,
+        GreaterEqual => left >= right,
+        Equal => left == right,
+        NotEqual => left != right,
+        And => left != 0 && right != 0,
+        Or => left != 0 || right != 0,
+    };
+
+    value as isize
+}
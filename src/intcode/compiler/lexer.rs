@@ -0,0 +1,189 @@
+use super::CompileError;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Token {
+    Number(isize),
+    Identifier(String),
+    Fn,
+    Let,
+    If,
+    Else,
+    While,
+    Return,
+    LeftParen,
+    RightParen,
+    LeftBrace,
+    RightBrace,
+    Comma,
+    Semicolon,
+    Assign,
+    Plus,
+    Minus,
+    Star,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+    Not,
+    And,
+    Or,
+}
+
+/// A token and the byte offset it starts at in the source.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Spanned {
+    pub token: Token,
+    pub offset: usize,
+}
+
+pub fn tokenize(source: &str) -> Result<Vec<Spanned>, CompileError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((offset, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+
+            '/' if chars.peek().map(|(_, next)| *next) == Some('/') => {
+                for (_, c) in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+
+                continue;
+            }
+
+            '0'..='9' => {
+                let mut end = offset + 1;
+
+                while let Some((next_offset, next)) = chars.peek() {
+                    if !next.is_ascii_digit() {
+                        break;
+                    }
+
+                    end = next_offset + 1;
+                    chars.next();
+                }
+
+                let value = source[offset..end]
+                    .parse()
+                    .map_err(|err| CompileError::InvalidNumber { offset, err })?;
+
+                Token::Number(value)
+            }
+
+            c if c.is_alphabetic() || c == '_' => {
+                let mut end = offset + c.len_utf8();
+
+                while let Some((next_offset, next)) = chars.peek() {
+                    if !next.is_alphanumeric() && *next != '_' {
+                        break;
+                    }
+
+                    end = next_offset + next.len_utf8();
+                    chars.next();
+                }
+
+                match &source[offset..end] {
+                    "fn" => Token::Fn,
+                    "let" => Token::Let,
+                    "if" => Token::If,
+                    "else" => Token::Else,
+                    "while" => Token::While,
+                    "return" => Token::Return,
+                    name => Token::Identifier(name.to_string()),
+                }
+            }
+
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            '{' => Token::LeftBrace,
+            '}' => Token::RightBrace,
+            ',' => Token::Comma,
+            ';' => Token::Semicolon,
+            '+' => Token::Plus,
+            '-' => Token::Minus,
+            '*' => Token::Star,
+
+            '<' | '>' | '=' | '!' => {
+                let followed_by_equal = chars.peek().map(|(_, next)| *next) == Some('=');
+
+                if followed_by_equal {
+                    chars.next();
+                }
+
+                match (c, followed_by_equal) {
+                    ('<', false) => Token::Less,
+                    ('<', true) => Token::LessEqual,
+                    ('>', false) => Token::Greater,
+                    ('>', true) => Token::GreaterEqual,
+                    ('=', false) => Token::Assign,
+                    ('=', true) => Token::Equal,
+                    ('!', false) => Token::Not,
+                    ('!', true) => Token::NotEqual,
+                    _ => unreachable!(),
+                }
+            }
+
+            '&' | '|' => {
+                if chars.peek().map(|(_, next)| *next) != Some(c) {
+                    return Err(CompileError::UnexpectedCharacter {
+                        offset,
+                        character: c,
+                    });
+                }
+
+                chars.next();
+
+                if c == '&' {
+                    Token::And
+                } else {
+                    Token::Or
+                }
+            }
+
+            character => return Err(CompileError::UnexpectedCharacter { offset, character }),
+        };
+
+        tokens.push(Spanned { token, offset });
+    }
+
+    Ok(tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        tokenize,
+        Token,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn tokenize_statement() {
+        let expected = vec![
+            Token::Let,
+            Token::Identifier("x_1".to_string()),
+            Token::Assign,
+            Token::Number(12),
+            Token::LessEqual,
+            Token::Minus,
+            Token::Identifier("y".to_string()),
+            Token::And,
+            Token::Not,
+            Token::Identifier("z".to_string()),
+            Token::Semicolon,
+        ];
+
+        let got = tokenize("let x_1 = 12 <= -y && !z; // comment")
+            .unwrap()
+            .into_iter()
+            .map(|spanned| spanned.token)
+            .collect::<Vec<_>>();
+
+        assert_eq!(expected, got);
+    }
+}
@@ -0,0 +1,215 @@
+//! Compiler for a small language to Intcode software.
+//!
+//! ```text
+//! fn factorial(n) {
+//!     if n < 2 {
+//!         return 1;
+//!     }
+//!
+//!     return n * factorial(n - 1);
+//! }
+//!
+//! fn main() {
+//!     let n = input();
+//!
+//!     while n > 0 {
+//!         output(factorial(n));
+//!         n = n - 1;
+//!     }
+//! }
+//! ```
+//!
+//! Values are integers. There are `+`, `-`, `*`, comparisons, `!`, `&&` and
+//! `||` which always evaluate both sides. `input()` reads and `output(x)`
+//! writes a value. Execution starts at `main` and functions can recurse as
+//! their frames live on a stack behind the software that is addressed through
+//! the relative base.
+
+pub mod codegen;
+pub mod lexer;
+pub mod parser;
+
+use codegen::Codegen;
+use lexer::Token;
+use parser::Parser;
+
+#[derive(Debug)]
+pub enum CompileError {
+    UnexpectedCharacter {
+        offset: usize,
+        character: char,
+    },
+    InvalidNumber {
+        offset: usize,
+        err: std::num::ParseIntError,
+    },
+    UnexpectedToken {
+        offset: usize,
+        found: Option<Token>,
+        expected: &'static str,
+    },
+    UnknownVariable(String),
+    UnknownFunction(String),
+    ArgumentCount {
+        function: String,
+        expected: usize,
+        found: usize,
+    },
+    DuplicateFunction(String),
+    MissingMain,
+}
+
+pub fn compile(source: &str) -> Result<Vec<isize>, CompileError> {
+    let tokens = lexer::tokenize(source)?;
+    let functions = Parser::new(tokens, source.len()).program()?;
+
+    Codegen::default().compile(&functions)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate test;
+
+    use super::{
+        compile,
+        CompileError,
+    };
+    use crate::intcode::computer::{
+        Computer,
+        Reader,
+    };
+    use pretty_assertions::assert_eq;
+    use test::Bencher;
+
+    fn run(source: &str, input: Vec<isize>) -> Vec<isize> {
+        let software = compile(source).unwrap();
+
+        let mut computer = Computer::default()
+            .with_software(software)
+            .with_input(Reader::Tester(Box::new(input.into_iter())));
+
+        computer.run().unwrap().outputs
+    }
+
+    const FIBONACCI: &str = "
+        fn fibonacci(n) {
+            if n < 2 {
+                return n;
+            }
+
+            return fibonacci(n - 1) + fibonacci(n - 2);
+        }
+
+        fn main() {
+            output(fibonacci(input()));
+        }
+    ";
+
+    #[test]
+    fn compile_arithmetic() {
+        let source = "
+            fn main() {
+                let a = input();
+                let b = input();
+
+                output(a + b * 2);
+                output(a - b);
+                output(-(a * b) + 3 * 4);
+                output(a < b);
+                output(a >= b);
+                output(a != b && !(a == 0) || 0);
+            }
+        ";
+
+        assert_eq!(vec![13, -2, -3, 1, 0, 1], run(source, vec![3, 5]));
+    }
+
+    #[test]
+    fn compile_control_flow() {
+        let source = "
+            fn main() {
+                let i = 0;
+                let sum = 0;
+
+                while i < input() {
+                    if i == 2 {
+                        sum = sum + 100;
+                    } else if i > 3 {
+                        sum = sum + 1000;
+                    } else {
+                        sum = sum + 1;
+                    }
+
+                    i = i + 1;
+                }
+
+                output(sum);
+            }
+        ";
+
+        assert_eq!(vec![1103], run(source, vec![5, 5, 5, 5, 5, 5]));
+    }
+
+    #[test]
+    fn compile_recursion() {
+        assert_eq!(vec![55], run(FIBONACCI, vec![10]));
+    }
+
+    #[test]
+    fn compile_nested_calls() {
+        let source = "
+            fn add(a, b) {
+                return a + b;
+            }
+
+            fn main() {
+                let x = 1;
+                output(add(add(x, 2), add(3, add(4, x))));
+            }
+        ";
+
+        assert_eq!(vec![11], run(source, Vec::new()));
+    }
+
+    #[test]
+    fn compile_errors() {
+        match compile("fn main() { output(x); }") {
+            Err(CompileError::UnknownVariable(name)) => assert_eq!("x", name),
+            got => panic!("unexpected result: {:?}", got),
+        }
+
+        match compile("fn f(a) {} fn main() { f(); }") {
+            Err(CompileError::ArgumentCount {
+                function,
+                expected,
+                found,
+            }) => assert_eq!(("f".to_string(), 1, 0), (function, expected, found)),
+            got => panic!("unexpected result: {:?}", got),
+        }
+
+        match compile("fn main() { let x = 1 }") {
+            Err(CompileError::UnexpectedToken {
+                offset, expected, ..
+            }) => assert_eq!((22, ";"), (offset, expected)),
+            got => panic!("unexpected result: {:?}", got),
+        }
+
+        match compile("fn f() {}") {
+            Err(CompileError::MissingMain) => {}
+            got => panic!("unexpected result: {:?}", got),
+        }
+    }
+
+    #[bench]
+    fn bench_compiled_fibonacci(b: &mut Bencher) {
+        let software = compile(FIBONACCI).unwrap();
+
+        b.iter(|| {
+            let mut computer = Computer::default()
+                .with_software(software.clone())
+                .with_input(Reader::Tester(Box::new(vec![15].into_iter())));
+
+            computer.run().unwrap()
+        });
+    }
+}
@@ -0,0 +1,335 @@
+use super::{
+    lexer::{
+        Spanned,
+        Token,
+    },
+    CompileError,
+};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Function {
+    pub name: String,
+    pub parameters: Vec<String>,
+    pub body: Vec<Statement>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Statement {
+    Let(String, Expression),
+    Assign(String, Expression),
+    If(Expression, Vec<Statement>, Vec<Statement>),
+    While(Expression, Vec<Statement>),
+    Return(Option<Expression>),
+    Expression(Expression),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Expression {
+    Number(isize),
+    Variable(String),
+    /// Calls of functions and of the builtins `input()` and `output(x)`.
+    Call(String, Vec<Expression>),
+    Negate(Box<Expression>),
+    Not(Box<Expression>),
+    Binary(Operator, Box<Expression>, Box<Expression>),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+    And,
+    Or,
+}
+
+pub struct Parser {
+    tokens: Vec<Spanned>,
+    position: usize,
+    end: usize,
+}
+
+impl Parser {
+    /// `end` is the length of the source and used as the offset of errors at
+    /// the end of the input.
+    pub fn new(tokens: Vec<Spanned>, end: usize) -> Self {
+        Self {
+            tokens,
+            position: 0,
+            end,
+        }
+    }
+
+    pub fn program(&mut self) -> Result<Vec<Function>, CompileError> {
+        let mut functions = Vec::new();
+
+        while self.peek().is_some() {
+            functions.push(self.function()?);
+        }
+
+        Ok(functions)
+    }
+
+    fn function(&mut self) -> Result<Function, CompileError> {
+        self.expect(Token::Fn, "fn")?;
+        let name = self.identifier()?;
+        self.expect(Token::LeftParen, "(")?;
+
+        let mut parameters = Vec::new();
+
+        if !self.accept(&Token::RightParen) {
+            loop {
+                parameters.push(self.identifier()?);
+
+                if self.accept(&Token::RightParen) {
+                    break;
+                }
+
+                self.expect(Token::Comma, ", or )")?;
+            }
+        }
+
+        let body = self.block()?;
+
+        Ok(Function {
+            name,
+            parameters,
+            body,
+        })
+    }
+
+    fn block(&mut self) -> Result<Vec<Statement>, CompileError> {
+        self.expect(Token::LeftBrace, "{")?;
+
+        let mut statements = Vec::new();
+
+        while !self.accept(&Token::RightBrace) {
+            statements.push(self.statement()?);
+        }
+
+        Ok(statements)
+    }
+
+    fn statement(&mut self) -> Result<Statement, CompileError> {
+        let statement = match self.peek() {
+            Some(Token::Let) => {
+                self.next();
+                let name = self.identifier()?;
+                self.expect(Token::Assign, "=")?;
+                Statement::Let(name, self.expression()?)
+            }
+
+            Some(Token::If) => {
+                self.next();
+                return self.if_statement();
+            }
+
+            Some(Token::While) => {
+                self.next();
+                let condition = self.expression()?;
+                return Ok(Statement::While(condition, self.block()?));
+            }
+
+            Some(Token::Return) => {
+                self.next();
+
+                if self.peek() == Some(&Token::Semicolon) {
+                    Statement::Return(None)
+                } else {
+                    Statement::Return(Some(self.expression()?))
+                }
+            }
+
+            Some(Token::Identifier(name)) if self.peek_at(1) == Some(&Token::Assign) => {
+                let name = name.clone();
+                self.next();
+                self.next();
+                Statement::Assign(name, self.expression()?)
+            }
+
+            _ => Statement::Expression(self.expression()?),
+        };
+
+        self.expect(Token::Semicolon, ";")?;
+
+        Ok(statement)
+    }
+
+    fn if_statement(&mut self) -> Result<Statement, CompileError> {
+        let condition = self.expression()?;
+        let then = self.block()?;
+
+        let otherwise = if !self.accept(&Token::Else) {
+            Vec::new()
+        } else if self.accept(&Token::If) {
+            vec![self.if_statement()?]
+        } else {
+            self.block()?
+        };
+
+        Ok(Statement::If(condition, then, otherwise))
+    }
+
+    fn expression(&mut self) -> Result<Expression, CompileError> {
+        self.binary(0)
+    }
+
+    /// Parses binary operators by precedence, `level` 0 binds the weakest.
+    fn binary(&mut self, level: usize) -> Result<Expression, CompileError> {
+        const LEVELS: &[&[(Token, Operator)]] = &[
+            &[(Token::Or, Operator::Or)],
+            &[(Token::And, Operator::And)],
+            &[
+                (Token::Equal, Operator::Equal),
+                (Token::NotEqual, Operator::NotEqual),
+            ],
+            &[
+                (Token::Less, Operator::Less),
+                (Token::LessEqual, Operator::LessEqual),
+                (Token::Greater, Operator::Greater),
+                (Token::GreaterEqual, Operator::GreaterEqual),
+            ],
+            &[
+                (Token::Plus, Operator::Add),
+                (Token::Minus, Operator::Subtract),
+            ],
+            &[(Token::Star, Operator::Multiply)],
+        ];
+
+        if level == LEVELS.len() {
+            return self.unary();
+        }
+
+        let mut left = self.binary(level + 1)?;
+
+        while let Some(operator) = LEVELS[level]
+            .iter()
+            .find(|(token, _)| self.peek() == Some(token))
+            .map(|(_, operator)| *operator)
+        {
+            self.next();
+            let right = self.binary(level + 1)?;
+            left = Expression::Binary(operator, Box::new(left), Box::new(right));
+        }
+
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expression, CompileError> {
+        if self.accept(&Token::Minus) {
+            return Ok(Expression::Negate(Box::new(self.unary()?)));
+        }
+
+        if self.accept(&Token::Not) {
+            return Ok(Expression::Not(Box::new(self.unary()?)));
+        }
+
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expression, CompileError> {
+        let offset = self.offset();
+
+        match self.next() {
+            Some(Token::Number(value)) => Ok(Expression::Number(value)),
+
+            Some(Token::Identifier(name)) => {
+                if !self.accept(&Token::LeftParen) {
+                    return Ok(Expression::Variable(name));
+                }
+
+                let mut arguments = Vec::new();
+
+                if !self.accept(&Token::RightParen) {
+                    loop {
+                        arguments.push(self.expression()?);
+
+                        if self.accept(&Token::RightParen) {
+                            break;
+                        }
+
+                        self.expect(Token::Comma, ", or )")?;
+                    }
+                }
+
+                Ok(Expression::Call(name, arguments))
+            }
+
+            Some(Token::LeftParen) => {
+                let expression = self.expression()?;
+                self.expect(Token::RightParen, ")")?;
+                Ok(expression)
+            }
+
+            found => Err(CompileError::UnexpectedToken {
+                offset,
+                found,
+                expected: "expression",
+            }),
+        }
+    }
+
+    fn identifier(&mut self) -> Result<String, CompileError> {
+        let offset = self.offset();
+
+        match self.next() {
+            Some(Token::Identifier(name)) => Ok(name),
+            found => Err(CompileError::UnexpectedToken {
+                offset,
+                found,
+                expected: "identifier",
+            }),
+        }
+    }
+
+    fn expect(&mut self, token: Token, expected: &'static str) -> Result<(), CompileError> {
+        let offset = self.offset();
+
+        match self.next() {
+            Some(ref found) if *found == token => Ok(()),
+            found => Err(CompileError::UnexpectedToken {
+                offset,
+                found,
+                expected,
+            }),
+        }
+    }
+
+    fn accept(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.peek_at(0)
+    }
+
+    fn peek_at(&self, distance: usize) -> Option<&Token> {
+        self.tokens
+            .get(self.position + distance)
+            .map(|spanned| &spanned.token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek().cloned();
+        self.position += 1;
+        token
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map(|spanned| spanned.offset)
+            .unwrap_or(self.end)
+    }
+}
@@ -0,0 +1,1992 @@
+use super::{
+    call_stack::{
+        Backtrace,
+        CallStack,
+    },
+    coverage::Coverage,
+    crash::{
+        CrashReport,
+        History,
+        Trace,
+    },
+    instruction_set::{
+        Flow,
+        InstructionSet,
+        Standard,
+    },
+    journal::{
+        Event,
+        Journal,
+    },
+    patch::{
+        Patch,
+        PatchError,
+    },
+    protection::CodeProtection,
+    report::{
+        HaltReason,
+        Memory,
+        RunReport,
+        Statistics,
+    },
+    sanitizer::MemorySanitizer,
+};
+use crossbeam_channel::{
+    Receiver,
+    Sender,
+};
+use log::trace;
+use std::{
+    collections::{
+        BTreeMap,
+        VecDeque,
+    },
+    convert::{
+        TryFrom,
+        TryInto,
+    },
+    sync::{
+        Arc,
+        Mutex,
+    },
+};
+
+pub struct Computer {
+    pub call_stack: Option<CallStack>,
+    pub code_protection: Option<CodeProtection>,
+    pub coverage: Option<Coverage>,
+    pub debug_flags: DebugFlags,
+    pub exit_code: Option<isize>,
+    pub history: Option<History>,
+    pub input: Reader,
+    pub instruction_set: Box<dyn InstructionSet>,
+    pub journal: Option<Journal>,
+    pub memory: BTreeMap<usize, isize>,
+    pub memory_sanitizer: Option<MemorySanitizer>,
+    pub output: Writer,
+    /// Patches that were not applied yet.
+    pub patches: Vec<Patch>,
+    /// Address of the instruction that is currently executed.
+    pub pc: usize,
+    pub relative_base: isize,
+    statistics: Statistics,
+}
+
+impl Default for Computer {
+    fn default() -> Self {
+        Self {
+            call_stack: None,
+            code_protection: None,
+            coverage: None,
+            debug_flags: DebugFlags::default(),
+            exit_code: None,
+            history: None,
+            memory: BTreeMap::default(),
+            memory_sanitizer: None,
+            input: Reader::Tester(Box::new(Vec::new().into_iter())),
+            instruction_set: Box::new(Standard),
+            journal: None,
+            output: Writer::Tester { values: Vec::new() },
+            patches: Vec::new(),
+            pc: 0,
+            relative_base: 0,
+            statistics: Statistics::default(),
+        }
+    }
+}
+
+impl Computer {
+    pub fn with_software(self, software: Vec<isize>) -> Self {
+        let memory: BTreeMap<usize, isize> = software.into_iter().enumerate().collect();
+
+        let memory_sanitizer = self
+            .memory_sanitizer
+            .map(|memory_sanitizer| memory_sanitizer.with_initialized(memory.keys().copied()));
+
+        Self {
+            memory,
+            memory_sanitizer,
+            ..self
+        }
+    }
+
+    pub fn with_input(self, input: Reader) -> Self {
+        Self { input, ..self }
+    }
+
+    pub fn with_output(self, output: Writer) -> Self {
+        Self { output, ..self }
+    }
+
+    /// Keeps the last `capacity` instructions and I/O values for crash
+    /// reports.
+    pub fn with_history(self, capacity: usize) -> Self {
+        Self {
+            history: Some(History::new(capacity)),
+            ..self
+        }
+    }
+
+    pub fn with_patch(mut self, patch: Patch) -> Self {
+        self.patches.push(patch);
+        self
+    }
+
+    pub fn with_instruction_set<I: InstructionSet + 'static>(self, instruction_set: I) -> Self {
+        Self {
+            instruction_set: Box::new(instruction_set),
+            ..self
+        }
+    }
+
+    pub fn with_code_protection(self, code_protection: CodeProtection) -> Self {
+        Self {
+            code_protection: Some(code_protection),
+            ..self
+        }
+    }
+
+    /// Infers call frames from the calling convention of compiled software
+    /// for backtraces.
+    pub fn with_call_stack(self) -> Self {
+        Self {
+            call_stack: Some(CallStack::default()),
+            ..self
+        }
+    }
+
+    pub fn with_coverage(self) -> Self {
+        Self {
+            coverage: Some(Coverage::default()),
+            ..self
+        }
+    }
+
+    pub fn with_memory_sanitizer(self, memory_sanitizer: MemorySanitizer) -> Self {
+        let memory_sanitizer = memory_sanitizer.with_initialized(self.memory.keys().copied());
+
+        Self {
+            memory_sanitizer: Some(memory_sanitizer),
+            ..self
+        }
+    }
+
+    /// Records the software and every value that is read or written to
+    /// replay the run.
+    pub fn with_journal(self, journal: &Journal) -> Self {
+        Self {
+            journal: Some(journal.clone()),
+            ..self
+        }
+    }
+
+    pub fn with_relative_base(self, relative_base: isize) -> Self {
+        Self {
+            relative_base,
+            ..self
+        }
+    }
+
+    pub fn debug_low(self) -> Self {
+        Self {
+            debug_flags: self
+                .debug_flags
+                .print_instructions()
+                .print_jumps()
+                .print_relative_base()
+                .print_output(),
+            ..self
+        }
+    }
+
+    pub fn print_instructions(self) -> Self {
+        Self {
+            debug_flags: self.debug_flags.print_instructions(),
+            ..self
+        }
+    }
+
+    pub fn print_memory(self) -> Self {
+        Self {
+            debug_flags: self.debug_flags.print_memory(),
+            ..self
+        }
+    }
+
+    pub fn print_relative_base(self) -> Self {
+        Self {
+            debug_flags: self.debug_flags.print_relative_base(),
+            ..self
+        }
+    }
+
+    pub fn print_output(self) -> Self {
+        Self {
+            debug_flags: self.debug_flags.print_output(),
+            ..self
+        }
+    }
+
+    pub fn print_jumps(self) -> Self {
+        Self {
+            debug_flags: self.debug_flags.print_jumps(),
+            ..self
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct DebugFlags {
+    pub print_memory: bool,
+    pub print_instructions: bool,
+    pub print_relative_base: bool,
+    pub print_output: bool,
+    pub print_jumps: bool,
+}
+
+impl DebugFlags {
+    pub fn print_instructions(self) -> Self {
+        Self {
+            print_instructions: true,
+            ..self
+        }
+    }
+
+    pub fn print_memory(self) -> Self {
+        Self {
+            print_memory: true,
+            ..self
+        }
+    }
+
+    pub fn print_relative_base(self) -> Self {
+        Self {
+            print_relative_base: true,
+            ..self
+        }
+    }
+
+    pub fn print_output(self) -> Self {
+        Self {
+            print_output: true,
+            ..self
+        }
+    }
+
+    pub fn print_jumps(self) -> Self {
+        Self {
+            print_jumps: true,
+            ..self
+        }
+    }
+}
+
+impl From<Vec<isize>> for Computer {
+    fn from(software: Vec<isize>) -> Self {
+        Self {
+            input: Reader::Disabled,
+            output: Writer::Disabled,
+            ..Self::default()
+        }
+        .with_software(software)
+    }
+}
+
+#[derive(Debug)]
+pub enum ComputerError {
+    ReadFromInput(std::io::Error),
+    NegativeAddress(isize),
+    UnknownOpcode(usize),
+    InvalidParameterMode(u8),
+    CodeWrite { pc: usize, address: usize },
+    UninitializedRead { pc: usize, address: usize },
+    InputDisabled,
+    InputExhausted,
+    InputDisconnected,
+    OutputDisabled,
+    OutputDisconnected,
+    Patch(PatchError),
+}
+
+impl From<PatchError> for ComputerError {
+    fn from(err: PatchError) -> Self {
+        Self::Patch(err)
+    }
+}
+
+pub enum Reader {
+    Disabled,
+    Tester(Box<dyn Iterator<Item = isize>>),
+    Channel(Receiver<isize>),
+    /// Reads the prefix before reading from the rest.
+    Chain {
+        prefix: VecDeque<isize>,
+        rest: Box<Reader>,
+    },
+    Map(Box<Reader>, Box<dyn FnMut(isize) -> isize>),
+    /// Skips values the predicate rejects.
+    Filter(Box<Reader>, Box<dyn FnMut(isize) -> bool>),
+    Record(Box<Reader>, Recording),
+}
+
+impl Reader {
+    /// Reads the values of `prefix` first, e.g. a phase setting before the
+    /// values of another machine.
+    pub fn chain<I: IntoIterator<Item = isize>>(prefix: I, rest: Reader) -> Self {
+        Reader::Chain {
+            prefix: prefix.into_iter().collect(),
+            rest: Box::new(rest),
+        }
+    }
+
+    pub fn map<F: FnMut(isize) -> isize + 'static>(self, f: F) -> Self {
+        Reader::Map(Box::new(self), Box::new(f))
+    }
+
+    pub fn filter<F: FnMut(isize) -> bool + 'static>(self, predicate: F) -> Self {
+        Reader::Filter(Box::new(self), Box::new(predicate))
+    }
+
+    pub fn record(self, recording: &Recording) -> Self {
+        Reader::Record(Box::new(self), recording.clone())
+    }
+
+    fn read(&mut self) -> Result<isize, ComputerError> {
+        use Reader::*;
+
+        match self {
+            Disabled => Err(ComputerError::InputDisabled),
+            Tester(iter) => iter.next().ok_or(ComputerError::InputExhausted),
+            Channel(receiver) => receiver
+                .recv()
+                .map_err(|_| ComputerError::InputDisconnected),
+
+            Chain { prefix, rest } => match prefix.pop_front() {
+                Some(value) => Ok(value),
+                None => rest.read(),
+            },
+
+            Map(reader, f) => reader.read().map(f),
+
+            Filter(reader, predicate) => loop {
+                let value = reader.read()?;
+
+                if predicate(value) {
+                    break Ok(value);
+                }
+            },
+
+            Record(reader, recording) => {
+                let value = reader.read()?;
+                recording.push(value);
+
+                Ok(value)
+            }
+        }
+    }
+
+    /// Number of values that can be read without blocking if it is known.
+    pub fn pending(&self) -> Option<usize> {
+        use Reader::*;
+
+        match self {
+            Disabled => Some(0),
+
+            Tester(iter) => match iter.size_hint() {
+                (lower, Some(upper)) if lower == upper => Some(lower),
+                _ => None,
+            },
+
+            Channel(receiver) => Some(receiver.len()),
+
+            Chain { prefix, rest } => rest.pending().map(|pending| prefix.len() + pending),
+
+            Map(reader, _) | Record(reader, _) => reader.pending(),
+
+            Filter(reader, _) => match reader.pending() {
+                Some(0) => Some(0),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Adapters wrap a writer so the adapter that was added last sees a value
+/// first.
+pub enum Writer {
+    Disabled,
+    Tester {
+        values: Vec<isize>,
+    },
+    Channel(Sender<isize>),
+    /// Writes every value to all writers.
+    Tee(Vec<Writer>),
+    Map(Box<Writer>, Box<dyn FnMut(isize) -> isize>),
+    /// Drops values the predicate rejects.
+    Filter(Box<Writer>, Box<dyn FnMut(isize) -> bool>),
+    Record(Box<Writer>, Recording),
+}
+
+impl Writer {
+    pub fn tee(writers: Vec<Writer>) -> Self {
+        Writer::Tee(writers)
+    }
+
+    pub fn map<F: FnMut(isize) -> isize + 'static>(self, f: F) -> Self {
+        Writer::Map(Box::new(self), Box::new(f))
+    }
+
+    pub fn filter<F: FnMut(isize) -> bool + 'static>(self, predicate: F) -> Self {
+        Writer::Filter(Box::new(self), Box::new(predicate))
+    }
+
+    pub fn record(self, recording: &Recording) -> Self {
+        Writer::Record(Box::new(self), recording.clone())
+    }
+
+    fn write(&mut self, value: isize) -> Result<(), ComputerError> {
+        use Writer::*;
+
+        match self {
+            Disabled => Err(ComputerError::OutputDisabled),
+
+            Tester { values } => {
+                values.push(value);
+                Ok(())
+            }
+
+            Channel(sender) => sender
+                .send(value)
+                .map_err(|_| ComputerError::OutputDisconnected),
+
+            Tee(writers) => writers
+                .iter_mut()
+                .try_for_each(|writer| writer.write(value)),
+
+            Map(writer, f) => writer.write(f(value)),
+
+            Filter(writer, predicate) => {
+                if predicate(value) {
+                    writer.write(value)
+                } else {
+                    Ok(())
+                }
+            }
+
+            Record(writer, recording) => {
+                recording.push(value);
+                writer.write(value)
+            }
+        }
+    }
+
+    /// Values written to a tester, looking through the adapters.
+    #[allow(dead_code)]
+    pub fn values(self) -> Vec<isize> {
+        match self {
+            Writer::Tester { values } => values,
+            Writer::Map(writer, _) | Writer::Filter(writer, _) | Writer::Record(writer, _) => {
+                writer.values()
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Shared log of the values that went through a recording reader or writer.
+/// Clones share the same log so it can be read after the computer moved to
+/// another thread.
+#[derive(Debug, Default, Clone)]
+pub struct Recording {
+    values: Arc<Mutex<Vec<isize>>>,
+}
+
+impl Recording {
+    fn push(&self, value: isize) {
+        trace!("recorded value {}", value);
+        self.values.lock().unwrap().push(value);
+    }
+
+    pub fn values(&self) -> Vec<isize> {
+        self.values.lock().unwrap().clone()
+    }
+}
+
+impl Computer {
+    pub fn run(&mut self) -> Result<RunReport, ComputerError> {
+        self.pc = 0;
+        self.statistics = Statistics::default();
+
+        if let Some(journal) = &self.journal {
+            journal.start(
+                Memory::from(self.memory.clone()).to_vec(),
+                self.relative_base,
+                self.patches.clone(),
+            );
+        }
+
+        let halt = loop {
+            if let Some(halt) = self.step()? {
+                break halt;
+            }
+        };
+
+        let statistics = std::mem::take(&mut self.statistics);
+
+        Ok(RunReport {
+            halt,
+            pc: self.pc,
+            instructions: statistics.instructions,
+            inputs: statistics.inputs,
+            outputs: statistics.outputs,
+            highest_address: statistics.highest_address,
+            memory: self.memory.clone().into(),
+        })
+    }
+
+    /// Executes the instruction at `pc` and returns why the computer halted
+    /// if it did.
+    pub fn step(&mut self) -> Result<Option<HaltReason>, ComputerError> {
+        let mut index = self.pc;
+
+        if self.debug_flags.print_memory {
+            println!("memory:");
+            println!("{:#?}", self.memory);
+        }
+
+        if !self.patches.is_empty() {
+            self.apply_patches(index)?;
+        }
+
+        let memory_value = self.read_value(index, &ParameterMode::Immediate)?;
+        let intcode = self.instruction_set.decode(memory_value)?;
+
+        self.statistics.instructions += 1;
+        self.statistics.touch(index + intcode.size() - 1);
+
+        if let Some(code_protection) = &mut self.code_protection {
+            code_protection.execute(index, intcode.size());
+        }
+
+        if let Some(coverage) = &mut self.coverage {
+            coverage.hit(index);
+        }
+
+        if self.history.is_some() {
+            let trace = self.trace(index, memory_value, &intcode);
+
+            if let Some(history) = &mut self.history {
+                history.trace(trace);
+            }
+        }
+
+        match intcode {
+            Intcode::Add(ref first_value_mode, ref second_value_mode, ref third_value_mode)
+            | Intcode::Mul(ref first_value_mode, ref second_value_mode, ref third_value_mode) => {
+                let first_value = self.read_value(index + 1, first_value_mode)?;
+                let second_value = self.read_value(index + 2, second_value_mode)?;
+                let result_address = self.get_address(index + 3, third_value_mode)?;
+
+                let result_value = match intcode {
+                    Intcode::Add(..) => first_value + second_value,
+                    Intcode::Mul(..) => first_value * second_value,
+                    _ => unreachable!(),
+                };
+
+                if self.debug_flags.print_instructions {
+                    println!(
+                        "{}\t({})\t--\t{}({}), {}({}), {} => {}({})",
+                        intcode,
+                        memory_value,
+                        index + 1,
+                        first_value,
+                        index + 2,
+                        second_value,
+                        result_value,
+                        index + 3,
+                        result_address
+                    );
+                }
+
+                self.write_address(result_address, result_value)?;
+
+                index += 4;
+            }
+
+            Intcode::JumpIfTrue(ref first_value_mode, ref second_value_mode) => {
+                let first_value = self.read_value(index + 1, first_value_mode)?;
+                let second_value = self.read_value(index + 2, second_value_mode)?;
+
+                if self.debug_flags.print_instructions {
+                    println!(
+                        "{}\t({})\t--\t{}({}), {}({})",
+                        intcode,
+                        memory_value,
+                        index + 1,
+                        first_value,
+                        index + 2,
+                        second_value,
+                    );
+                }
+
+                if let Some(coverage) = &mut self.coverage {
+                    coverage.branch(index, first_value != 0);
+                }
+
+                if self.call_stack.is_some() {
+                    let target = if first_value != 0 {
+                        Some(second_value)
+                    } else {
+                        None
+                    };
+
+                    self.track_jump(index, second_value_mode, target);
+                }
+
+                if first_value != 0 {
+                    index = second_value
+                        .try_into()
+                        .map_err(|_| ComputerError::NegativeAddress(second_value))?;
+
+                    if self.debug_flags.print_jumps {
+                        dbg!(index);
+                    }
+                } else {
+                    index += 3
+                }
+            }
+
+            Intcode::JumpIfFalse(ref first_value_mode, ref second_value_mode) => {
+                let first_value = self.read_value(index + 1, first_value_mode)?;
+                let second_value = self.read_value(index + 2, second_value_mode)?;
+
+                if self.debug_flags.print_instructions {
+                    println!(
+                        "{}\t({})\t--\t{}({}), {}({})",
+                        intcode,
+                        memory_value,
+                        index + 1,
+                        first_value,
+                        index + 2,
+                        second_value,
+                    );
+                }
+
+                if let Some(coverage) = &mut self.coverage {
+                    coverage.branch(index, first_value == 0);
+                }
+
+                if self.call_stack.is_some() {
+                    let target = if first_value == 0 {
+                        Some(second_value)
+                    } else {
+                        None
+                    };
+
+                    self.track_jump(index, second_value_mode, target);
+                }
+
+                if first_value == 0 {
+                    index = second_value
+                        .try_into()
+                        .map_err(|_| ComputerError::NegativeAddress(second_value))?;
+
+                    if self.debug_flags.print_jumps {
+                        dbg!(index);
+                    }
+                } else {
+                    index += 3
+                }
+            }
+
+            Intcode::LessThan(
+                ref first_value_mode,
+                ref second_value_mode,
+                ref third_value_mode,
+            ) => {
+                let first_value = self.read_value(index + 1, first_value_mode)?;
+                let second_value = self.read_value(index + 2, second_value_mode)?;
+                let result_address = self.get_address(index + 3, third_value_mode)?;
+                let result_value = if first_value < second_value { 1 } else { 0 };
+
+                if self.debug_flags.print_instructions {
+                    println!(
+                        "{}\t({})\t--\t{}({}), {}({}), {} => {}({})",
+                        intcode,
+                        memory_value,
+                        index + 1,
+                        first_value,
+                        index + 2,
+                        second_value,
+                        result_value,
+                        index + 3,
+                        result_address
+                    );
+                }
+
+                self.write_address(result_address, result_value)?;
+
+                index += 4;
+            }
+
+            Intcode::Equals(ref first_value_mode, ref second_value_mode, ref third_value_mode) => {
+                let first_value = self.read_value(index + 1, first_value_mode)?;
+                let second_value = self.read_value(index + 2, second_value_mode)?;
+                let result_address = self.get_address(index + 3, third_value_mode)?;
+                let result_value = if first_value == second_value { 1 } else { 0 };
+
+                if self.debug_flags.print_instructions {
+                    println!(
+                        "{}\t({})\t--\t{}({}), {}({}), {} => {}({})",
+                        intcode,
+                        memory_value,
+                        index + 1,
+                        first_value,
+                        index + 2,
+                        second_value,
+                        result_value,
+                        index + 3,
+                        result_address
+                    );
+                }
+
+                self.write_address(result_address, result_value)?;
+
+                index += 4;
+            }
+
+            Intcode::Read(ref value_mode) => {
+                let address = self.get_address(index + 1, value_mode)?;
+                let value = self.input.read()?;
+                self.statistics.inputs.push(value);
+
+                if let Some(journal) = &self.journal {
+                    journal.record(self.statistics.instructions, Event::Input(value));
+                }
+
+                if let Some(history) = &mut self.history {
+                    history.input(value);
+                }
+
+                if self.debug_flags.print_instructions {
+                    println!(
+                        "{}\t({})\t--\t{} => {}({})",
+                        intcode,
+                        memory_value,
+                        value,
+                        index + 1,
+                        address,
+                    );
+                }
+
+                self.write_address(address, value)?;
+                index += 2;
+            }
+
+            Intcode::Write(ref value_mode) => {
+                let value = self.read_value(index + 1, value_mode)?;
+
+                if self.debug_flags.print_instructions {
+                    println!(
+                        "{}\t({})\t--\t{}({})",
+                        intcode,
+                        memory_value,
+                        index + 1,
+                        value,
+                    );
+                }
+
+                if self.debug_flags.print_output {
+                    dbg!(value);
+                }
+
+                self.output.write(value)?;
+                self.statistics.outputs.push(value);
+
+                if let Some(journal) = &self.journal {
+                    journal.record(self.statistics.instructions, Event::Output(value));
+                }
+
+                if let Some(history) = &mut self.history {
+                    history.output(value);
+                }
+
+                index += 2;
+            }
+
+            Intcode::AdjustRelativeBase(ref value_mode) => {
+                let value = self.read_value(index + 1, value_mode)?;
+
+                if self.debug_flags.print_instructions {
+                    println!(
+                        "{}\t({})\t--\t{}({})",
+                        intcode,
+                        memory_value,
+                        index + 1,
+                        value,
+                    );
+                }
+
+                self.relative_base += value;
+
+                if let Some(call_stack) = &mut self.call_stack {
+                    call_stack.adjust_relative_base(self.relative_base);
+                }
+
+                if self.debug_flags.print_relative_base {
+                    dbg!(self.relative_base);
+                }
+
+                index += 2;
+            }
+
+            Intcode::Halt => {
+                if self.debug_flags.print_instructions {
+                    println!("{}\t({})", intcode, memory_value,);
+                }
+
+                return Ok(Some(HaltReason::Halt));
+            }
+            Intcode::Skip => {
+                if self.debug_flags.print_instructions {
+                    println!("{}\t({})", intcode, memory_value,);
+                }
+
+                index += 1
+            }
+
+            Intcode::Extension {
+                opcode, ref modes, ..
+            } => {
+                let parameters = modes
+                    .iter()
+                    .enumerate()
+                    .map(|(offset, mode)| self.parameter(index + 1 + offset, mode))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                if self.debug_flags.print_instructions {
+                    println!("{}\t({})\t--\t{:?}", intcode, memory_value, parameters);
+                }
+
+                // The instruction set is swapped out while the handler runs so the handler
+                // can borrow the computer mutably.
+                let instruction_set =
+                    std::mem::replace(&mut self.instruction_set, Box::new(Standard));
+                let flow = instruction_set.execute(opcode, self, &parameters);
+                self.instruction_set = instruction_set;
+
+                match flow? {
+                    Flow::Continue => index += 1 + parameters.len(),
+
+                    Flow::Jump(address) => {
+                        index = address;
+
+                        if self.debug_flags.print_jumps {
+                            dbg!(index);
+                        }
+                    }
+
+                    Flow::Halt => {
+                        return Ok(Some(HaltReason::Halt));
+                    }
+
+                    Flow::Exit(code) => {
+                        self.exit_code = Some(code);
+                        return Ok(Some(HaltReason::Exit(code)));
+                    }
+                }
+            }
+        }
+
+        self.pc = index;
+
+        Ok(None)
+    }
+
+    fn apply_patches(&mut self, pc: usize) -> Result<(), ComputerError> {
+        let mut index = 0;
+
+        while index < self.patches.len() {
+            if !self.patches[index].is_triggered(pc, &self.memory) {
+                index += 1;
+                continue;
+            }
+
+            let patch = self.patches.remove(index);
+            patch.apply(&mut self.memory)?;
+
+            if let Some(memory_sanitizer) = &mut self.memory_sanitizer {
+                for edit in &patch.edits {
+                    memory_sanitizer.initialize(edit.address);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn track_jump(&mut self, pc: usize, mode: &ParameterMode, target: Option<isize>) {
+        let source = self.operand_address(pc + 2, *mode);
+        let relative_base = self.relative_base;
+
+        if let Some(call_stack) = &mut self.call_stack {
+            let target = target.and_then(|target| target.try_into().ok());
+            call_stack.jump(pc, 3, target, source, relative_base);
+        }
+    }
+
+    /// Whether the next instruction reads input that is known to be missing,
+    /// so stepping would block or fail.
+    pub fn is_waiting_for_input(&self) -> bool {
+        let intcode = self.instruction_set.decode(self.peek(self.pc as isize));
+
+        matches!(intcode, Ok(Intcode::Read(_))) && self.input.pending() == Some(0)
+    }
+
+    /// Backtrace of the inferred call frames if the call stack is tracked.
+    pub fn backtrace(&self) -> Option<Backtrace> {
+        self.call_stack
+            .as_ref()
+            .map(|call_stack| call_stack.backtrace(self.pc))
+    }
+
+    /// Runs the software and turns any error into a crash report.
+    pub fn run_with_crash_report(&mut self) -> Result<RunReport, Box<CrashReport>> {
+        self.run()
+            .map_err(|error| Box::new(self.crash_report(error)))
+    }
+
+    pub fn crash_report(&self, error: ComputerError) -> CrashReport {
+        let raw = self.peek(self.pc as isize);
+        let intcode = self.instruction_set.decode(raw).unwrap_or(Intcode::Skip);
+        let instruction = self.trace(self.pc, raw, &intcode);
+
+        let history = self
+            .history
+            .iter()
+            .flat_map(History::traces)
+            .cloned()
+            .collect();
+
+        let memory = self
+            .memory
+            .range(CrashReport::memory_window(self.pc))
+            .map(|(address, value)| (*address, *value))
+            .collect();
+
+        let inputs = self
+            .history
+            .iter()
+            .flat_map(History::inputs)
+            .copied()
+            .collect();
+
+        let outputs = self
+            .history
+            .iter()
+            .flat_map(History::outputs)
+            .copied()
+            .collect();
+
+        CrashReport {
+            error,
+            pc: self.pc,
+            relative_base: self.relative_base,
+            instruction,
+            backtrace: self.backtrace(),
+            history,
+            memory,
+            inputs,
+            outputs,
+            pending_input: self.input.pending(),
+        }
+    }
+
+    /// Decodes the operands of an instruction without executing it and
+    /// without going through the memory checks.
+    fn trace(&self, pc: usize, raw: isize, intcode: &Intcode) -> Trace {
+        let parameters = intcode
+            .modes()
+            .into_iter()
+            .enumerate()
+            .map(|(offset, mode)| {
+                let cell = pc + 1 + offset;
+
+                let address = self.operand_address(cell, mode);
+
+                Parameter {
+                    mode,
+                    address,
+                    value: self.peek(address),
+                }
+            })
+            .collect();
+
+        Trace {
+            pc,
+            raw,
+            intcode: intcode.clone(),
+            parameters,
+            relative_base: self.relative_base,
+        }
+    }
+
+    /// Address an operand refers to without going through the memory checks.
+    fn operand_address(&self, cell: usize, mode: ParameterMode) -> isize {
+        match mode {
+            ParameterMode::Position => self.peek(cell as isize),
+            ParameterMode::Immediate => cell as isize,
+            ParameterMode::Relative => self.peek(cell as isize) + self.relative_base,
+        }
+    }
+
+    fn peek(&self, address: isize) -> isize {
+        if address < 0 {
+            return 0;
+        }
+
+        *self.memory.get(&(address as usize)).unwrap_or(&0)
+    }
+
+    fn parameter(
+        &mut self,
+        index: usize,
+        mode: &ParameterMode,
+    ) -> Result<Parameter, ComputerError> {
+        let address = self.get_address(index, mode)?;
+        let value = self.read_address(address)?;
+
+        Ok(Parameter {
+            mode: *mode,
+            address,
+            value,
+        })
+    }
+
+    fn get_address(&mut self, index: usize, mode: &ParameterMode) -> Result<isize, ComputerError> {
+        let address = match mode {
+            ParameterMode::Position => self.load(index)?,
+            ParameterMode::Immediate => index as isize,
+            ParameterMode::Relative => self.load(index)? + self.relative_base,
+        };
+
+        Ok(address)
+    }
+
+    fn read_value(&mut self, index: usize, mode: &ParameterMode) -> Result<isize, ComputerError> {
+        let address = self.get_address(index, mode)?;
+
+        self.read_address(address)
+    }
+
+    fn read_address(&mut self, address: isize) -> Result<isize, ComputerError> {
+        if address < 0 {
+            return Err(ComputerError::NegativeAddress(address));
+        }
+
+        self.load(address as usize)
+    }
+
+    fn load(&mut self, address: usize) -> Result<isize, ComputerError> {
+        self.statistics.touch(address);
+
+        if let Some(memory_sanitizer) = &mut self.memory_sanitizer {
+            memory_sanitizer.check_read(self.pc, address)?;
+        }
+
+        Ok(*self.memory.get(&address).unwrap_or(&0))
+    }
+
+    /// Writes the result of an instruction to an address it computed.
+    fn write_address(&mut self, address: isize, value: isize) -> Result<(), ComputerError> {
+        let address = address
+            .try_into()
+            .map_err(|_| ComputerError::NegativeAddress(address))?;
+
+        self.write_value(address, value)
+    }
+
+    pub fn write_value(&mut self, address: usize, value: isize) -> Result<(), ComputerError> {
+        if let Some(code_protection) = &self.code_protection {
+            code_protection.check_write(self.pc, address)?;
+        }
+
+        if let Some(memory_sanitizer) = &mut self.memory_sanitizer {
+            memory_sanitizer.initialize(address);
+        }
+
+        if let Some(call_stack) = &mut self.call_stack {
+            call_stack.store(address, value);
+        }
+
+        self.statistics.touch(address);
+
+        self.memory.insert(address, value);
+
+        Ok(())
+    }
+}
+
+/// A decoded parameter of an instruction. `address` is where the value was
+/// read from and is the target for instructions that write their result.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Parameter {
+    pub mode: ParameterMode,
+    pub address: isize,
+    pub value: isize,
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone)]
+pub enum Intcode {
+    Add(ParameterMode, ParameterMode, ParameterMode),
+    Mul(ParameterMode, ParameterMode, ParameterMode),
+    Read(ParameterMode),
+    Write(ParameterMode),
+    JumpIfTrue(ParameterMode, ParameterMode),
+    JumpIfFalse(ParameterMode, ParameterMode),
+    LessThan(ParameterMode, ParameterMode, ParameterMode),
+    Equals(ParameterMode, ParameterMode, ParameterMode),
+    AdjustRelativeBase(ParameterMode),
+
+    Halt,
+    Skip,
+
+    Extension {
+        opcode: usize,
+        name: String,
+        modes: Vec<ParameterMode>,
+    },
+}
+
+impl Intcode {
+    /// Number of memory cells the instruction occupies including its
+    /// parameters.
+    pub fn size(&self) -> usize {
+        use Intcode::*;
+
+        match self {
+            Add(..) | Mul(..) | LessThan(..) | Equals(..) => 4,
+            JumpIfTrue(..) | JumpIfFalse(..) => 3,
+            Read(..) | Write(..) | AdjustRelativeBase(..) => 2,
+            Halt | Skip => 1,
+            Extension { modes, .. } => modes.len() + 1,
+        }
+    }
+
+    pub fn modes(&self) -> Vec<ParameterMode> {
+        use Intcode::*;
+
+        match self {
+            Add(first, second, third)
+            | Mul(first, second, third)
+            | LessThan(first, second, third)
+            | Equals(first, second, third) => vec![*first, *second, *third],
+            JumpIfTrue(first, second) | JumpIfFalse(first, second) => vec![*first, *second],
+            Read(first) | Write(first) | AdjustRelativeBase(first) => vec![*first],
+            Halt | Skip => Vec::new(),
+            Extension { modes, .. } => modes.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for Intcode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Intcode::*;
+
+        match self {
+            Add(first_mode, second_mode, third_mode) => {
+                write!(f, "ADD\t[{}, {}, {}]", first_mode, second_mode, third_mode)
+            }
+
+            Mul(first_mode, second_mode, third_mode) => {
+                write!(f, "MUL\t[{}, {}, {}]", first_mode, second_mode, third_mode)
+            }
+
+            Read(mode) => write!(f, "READ\t[{}]        ", mode),
+
+            Write(mode) => write!(f, "WRITE\t[{}]        ", mode),
+
+            JumpIfTrue(first_mode, second_mode) => {
+                write!(f, "JMPT\t[{}, {}]    ", first_mode, second_mode)
+            }
+
+            JumpIfFalse(first_mode, second_mode) => {
+                write!(f, "JMPF\t[{}, {}]    ", first_mode, second_mode)
+            }
+
+            LessThan(first_mode, second_mode, third_mode) => {
+                write!(f, "LESS\t[{}, {}, {}]", first_mode, second_mode, third_mode)
+            }
+
+            Equals(first_mode, second_mode, third_mode) => write!(
+                f,
+                "EQUAL\t[{}, {}, {}]",
+                first_mode, second_mode, third_mode
+            ),
+
+            AdjustRelativeBase(mode) => write!(f, "ADJREL\t[{}]        ", mode),
+
+            Halt => write!(f, "HALT\t          "),
+
+            Skip => write!(f, "SKIP\t          "),
+
+            Extension { name, modes, .. } => {
+                let modes = modes
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                write!(f, "{}\t[{}]", name, modes)
+            }
+        }
+    }
+}
+
+impl From<&isize> for Intcode {
+    fn from(val: &isize) -> Self {
+        (*val).into()
+    }
+}
+
+/// Decodes a value like the computer does, but decodes values with an invalid
+/// parameter mode as `Skip` instead of failing.
+impl From<isize> for Intcode {
+    fn from(value: isize) -> Self {
+        Self::decode(value).unwrap_or(Intcode::Skip)
+    }
+}
+
+impl Intcode {
+    /// Decodes a value. Unknown opcodes are decoded as `Skip`.
+    pub fn decode(value: isize) -> Result<Self, ComputerError> {
+        use Intcode::*;
+
+        if value < 0 {
+            return Ok(Skip);
+        }
+
+        let value = value as usize;
+
+        let mut digits = digits_reverse(value);
+        let optcode = digits.next().unwrap_or_default() + digits.next().unwrap_or_default() * 10;
+
+        let first_mode = ParameterMode::try_from(digits.next().unwrap_or_default())?;
+        let second_mode = ParameterMode::try_from(digits.next().unwrap_or_default())?;
+        let third_mode = ParameterMode::try_from(digits.next().unwrap_or_default())?;
+
+        Ok(match optcode {
+            1 => Add(first_mode, second_mode, third_mode),
+            2 => Mul(first_mode, second_mode, third_mode),
+            3 => Read(first_mode),
+            4 => Write(first_mode),
+            5 => JumpIfTrue(first_mode, second_mode),
+            6 => JumpIfFalse(first_mode, second_mode),
+            7 => LessThan(first_mode, second_mode, third_mode),
+            8 => Equals(first_mode, second_mode, third_mode),
+            9 => AdjustRelativeBase(first_mode),
+            99 => Halt,
+            _ => Skip,
+        })
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
+pub enum ParameterMode {
+    Position,
+    Immediate,
+    Relative,
+}
+
+impl std::fmt::Display for ParameterMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use ParameterMode::*;
+
+        match self {
+            Position => write!(f, "p"),
+            Immediate => write!(f, "i"),
+            Relative => write!(f, "r"),
+        }
+    }
+}
+
+impl TryFrom<u8> for ParameterMode {
+    type Error = ComputerError;
+
+    fn try_from(val: u8) -> Result<Self, Self::Error> {
+        use ParameterMode::*;
+
+        match val {
+            0 => Ok(Position),
+            1 => Ok(Immediate),
+            2 => Ok(Relative),
+            _ => Err(ComputerError::InvalidParameterMode(val)),
+        }
+    }
+}
+
+/// Decodes `count` parameter modes from the digits above the opcode.
+pub fn parameter_modes(value: usize, count: usize) -> Result<Vec<ParameterMode>, ComputerError> {
+    digits_reverse(value / 100)
+        .chain(std::iter::repeat(0))
+        .take(count)
+        .map(ParameterMode::try_from)
+        .collect()
+}
+
+pub fn digits_reverse(mut password: usize) -> impl Iterator<Item = u8> {
+    std::iter::from_fn(move || match password {
+        0 => None,
+        _ => {
+            let digit = password % 10;
+            password /= 10;
+            Some(digit as _)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate test;
+
+    use super::{
+        Computer,
+        Reader,
+        Recording,
+        Writer,
+    };
+    use crate::intcode::program::Program;
+    use pretty_assertions::assert_eq;
+    use test::Bencher;
+
+    #[test]
+    fn computer_run_example_text() {
+        let input = vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+        let expected = vec![3500, 9, 10, 70, 2, 3, 11, 0, 99, 30, 40, 50];
+        let got = Computer::from(input).run().unwrap().memory.to_vec();
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn computer_run_example01() {
+        let input = vec![1, 0, 0, 0, 99];
+        let expected = vec![2, 0, 0, 0, 99];
+        let got = Computer::from(input).run().unwrap().memory.to_vec();
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn computer_run_example02() {
+        let input = vec![2, 3, 0, 3, 99];
+        let expected = vec![2, 3, 0, 6, 99];
+        let got = Computer::from(input).run().unwrap().memory.to_vec();
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn computer_run_example03() {
+        let input = vec![2, 4, 4, 5, 99, 0];
+        let expected = vec![2, 4, 4, 5, 99, 9801];
+        let got = Computer::from(input).run().unwrap().memory.to_vec();
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn computer_run_example04() {
+        let input = vec![1, 1, 1, 4, 99, 5, 6, 0, 99];
+        let expected = vec![30, 1, 1, 4, 2, 5, 6, 0, 99];
+        let got = Computer::from(input).run().unwrap().memory.to_vec();
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn computer_run_day_05_example01() {
+        let input_software = vec![3, 0, 4, 0, 99];
+        let input_values = vec![42];
+
+        let expected_software = vec![42, 0, 4, 0, 99];
+        let expected_output = Writer::Tester { values: vec![42] };
+
+        let mut computer = Computer {
+            input: Reader::Tester(Box::new(input_values.into_iter())),
+            output: Writer::Tester { values: Vec::new() },
+            ..Computer::default()
+        }
+        .with_software(input_software);
+
+        let got_software = computer.run().unwrap().memory.to_vec();
+        let got_output = computer.output;
+
+        assert_eq!(expected_software, got_software);
+        assert_eq!(expected_output.values(), got_output.values());
+    }
+
+    #[test]
+    fn intcodes_simple() {
+        use super::{
+            Intcode::*,
+            ParameterMode::*,
+        };
+
+        assert_eq!(Add(Position, Position, Position), 1.into());
+        assert_eq!(Add(Relative, Position, Position), 201.into());
+        assert_eq!(Add(Position, Relative, Position), 2001.into());
+        assert_eq!(Add(Position, Relative, Position), 2001.into());
+        assert_eq!(Add(Position, Position, Relative), 20001.into());
+        assert_eq!(Mul(Position, Position, Position), 2.into());
+        assert_eq!(Read(Position), 3.into());
+        assert_eq!(Write(Position), 4.into());
+        assert_eq!(Halt, 99.into());
+        assert_eq!(Skip, 50.into());
+
+        assert_eq!(Write(Immediate), 104.into());
+    }
+
+    #[test]
+    fn intcodes_with_modes_day_05_example_01() {
+        use super::ParameterMode::*;
+
+        let input = 1002;
+        let expected = super::Intcode::Mul(Position, Immediate, Position);
+
+        let got: super::Intcode = (&input).into();
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn intcodes_with_modes_day_05_add() {
+        use super::{
+            Intcode::*,
+            ParameterMode::*,
+        };
+
+        assert_eq!(Add(Position, Position, Position), 1.into());
+        assert_eq!(Skip, 11.into());
+        assert_eq!(Add(Immediate, Position, Position), 101.into());
+        assert_eq!(Add(Position, Immediate, Position), 1001.into());
+        assert_eq!(Add(Immediate, Immediate, Position), 1101.into());
+    }
+
+    #[test]
+    fn computer_run_day_05_example02() {
+        let input_software = vec![1002, 4, 3, 4, 33];
+        let expected_software = vec![1002, 4, 3, 4, 99];
+
+        let mut computer = Computer::from(input_software);
+
+        let got_software = computer.run().unwrap().memory.to_vec();
+
+        assert_eq!(expected_software, got_software);
+    }
+
+    #[test]
+    fn computer_run_day_05_example03() {
+        let input_software = vec![1101, 100, -1, 4, 0];
+        let expected_software = vec![1101, 100, -1, 4, 99];
+
+        let mut computer = Computer::from(input_software);
+
+        let got_software = computer.run().unwrap().memory.to_vec();
+
+        assert_eq!(expected_software, got_software);
+    }
+
+    #[test]
+    fn computer_run_day_05_example04_equal() {
+        let input_software = vec![3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8];
+        let input_values = vec![8];
+
+        let expected_output = Writer::Tester { values: vec![1] };
+
+        let mut computer = Computer {
+            input: Reader::Tester(Box::new(input_values.into_iter())),
+            output: Writer::Tester { values: Vec::new() },
+            ..Computer::default()
+        }
+        .with_software(input_software);
+
+        computer.run().unwrap();
+        let got_output = computer.output;
+
+        assert_eq!(expected_output.values(), got_output.values());
+    }
+
+    #[test]
+    fn computer_run_day_05_example04_not_equal() {
+        let input_software = vec![3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8];
+        let input_values = vec![7];
+
+        let expected_output = Writer::Tester { values: vec![0] };
+
+        let mut computer = Computer {
+            input: Reader::Tester(Box::new(input_values.into_iter())),
+            output: Writer::Tester { values: Vec::new() },
+            ..Computer::default()
+        }
+        .with_software(input_software);
+
+        computer.run().unwrap();
+        let got_output = computer.output;
+
+        assert_eq!(expected_output.values(), got_output.values());
+    }
+
+    #[test]
+    fn computer_run_day_05_example05_less() {
+        let input_software = vec![3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8];
+        let input_values = vec![7];
+
+        let expected_output = Writer::Tester { values: vec![1] };
+
+        let mut computer = Computer {
+            input: Reader::Tester(Box::new(input_values.into_iter())),
+            output: Writer::Tester { values: Vec::new() },
+            ..Computer::default()
+        }
+        .with_software(input_software);
+
+        computer.run().unwrap();
+        let got_output = computer.output;
+
+        assert_eq!(expected_output.values(), got_output.values());
+    }
+
+    #[test]
+    fn computer_run_day_05_example05_equal() {
+        let input_software = vec![3, 9, 7, 9, 10, 9, 4, 9, 99, -1, 8];
+        let input_values = vec![8];
+
+        let expected_output = Writer::Tester { values: vec![0] };
+
+        let mut computer = Computer {
+            input: Reader::Tester(Box::new(input_values.into_iter())),
+            output: Writer::Tester { values: Vec::new() },
+            ..Computer::default()
+        }
+        .with_software(input_software);
+
+        computer.run().unwrap();
+        let got_output = computer.output;
+
+        assert_eq!(expected_output.values(), got_output.values());
+    }
+
+    #[test]
+    fn computer_run_day_05_example06_equal() {
+        let input_software = vec![3, 3, 1108, -1, 8, 3, 4, 3, 99];
+        let input_values = vec![8];
+
+        let expected_output = Writer::Tester { values: vec![1] };
+
+        let mut computer = Computer {
+            input: Reader::Tester(Box::new(input_values.into_iter())),
+            output: Writer::Tester { values: Vec::new() },
+            ..Computer::default()
+        }
+        .with_software(input_software);
+
+        computer.run().unwrap();
+        let got_output = computer.output;
+
+        assert_eq!(expected_output.values(), got_output.values());
+    }
+
+    #[test]
+    fn computer_run_day_05_example06_not_equal() {
+        let input_software = vec![3, 3, 1108, -1, 8, 3, 4, 3, 99];
+        let input_values = vec![42];
+
+        let expected_output = Writer::Tester { values: vec![0] };
+
+        let mut computer = Computer {
+            input: Reader::Tester(Box::new(input_values.into_iter())),
+            output: Writer::Tester { values: Vec::new() },
+            ..Computer::default()
+        }
+        .with_software(input_software);
+
+        computer.run().unwrap();
+        let got_output = computer.output;
+
+        assert_eq!(expected_output.values(), got_output.values());
+    }
+
+    #[test]
+    fn computer_run_day_05_example07_less() {
+        let input_software = vec![3, 3, 1107, -1, 8, 3, 4, 3, 99];
+        let input_values = vec![-42];
+
+        let expected_output = Writer::Tester { values: vec![1] };
+
+        let mut computer = Computer {
+            input: Reader::Tester(Box::new(input_values.into_iter())),
+            output: Writer::Tester { values: Vec::new() },
+            ..Computer::default()
+        }
+        .with_software(input_software);
+
+        computer.run().unwrap();
+        let got_output = computer.output;
+
+        assert_eq!(expected_output.values(), got_output.values());
+    }
+
+    #[test]
+    fn computer_run_day_05_example07_equal() {
+        let input_software = vec![3, 3, 1107, -1, 8, 3, 4, 3, 99];
+        let input_values = vec![8];
+
+        let expected_output = Writer::Tester { values: vec![0] };
+
+        let mut computer = Computer {
+            input: Reader::Tester(Box::new(input_values.into_iter())),
+            output: Writer::Tester { values: Vec::new() },
+            ..Computer::default()
+        }
+        .with_software(input_software);
+
+        computer.run().unwrap();
+        let got_output = computer.output;
+
+        assert_eq!(expected_output.values(), got_output.values());
+    }
+
+    #[test]
+    fn computer_run_day_05_example08_zero() {
+        let input_software = vec![3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9];
+        let input_values = vec![0];
+
+        let expected_output = Writer::Tester { values: vec![0] };
+
+        let mut computer = Computer {
+            input: Reader::Tester(Box::new(input_values.into_iter())),
+            output: Writer::Tester { values: Vec::new() },
+            ..Computer::default()
+        }
+        .with_software(input_software);
+
+        computer.run().unwrap();
+        let got_output = computer.output;
+
+        assert_eq!(expected_output.values(), got_output.values());
+    }
+
+    #[test]
+    fn computer_run_day_05_example08_not_zero() {
+        let input_software = vec![3, 12, 6, 12, 15, 1, 13, 14, 13, 4, 13, 99, -1, 0, 1, 9];
+        let input_values = vec![42];
+
+        let expected_output = Writer::Tester { values: vec![1] };
+
+        let mut computer = Computer {
+            input: Reader::Tester(Box::new(input_values.into_iter())),
+            output: Writer::Tester { values: Vec::new() },
+            ..Computer::default()
+        }
+        .with_software(input_software);
+
+        computer.run().unwrap();
+        let got_output = computer.output;
+
+        assert_eq!(expected_output.values(), got_output.values());
+    }
+
+    #[test]
+    fn computer_run_day_05_example09_zero() {
+        let input_software = vec![3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1];
+        let input_values = vec![0];
+
+        let expected_output = Writer::Tester { values: vec![0] };
+
+        let mut computer = Computer {
+            input: Reader::Tester(Box::new(input_values.into_iter())),
+            output: Writer::Tester { values: Vec::new() },
+            ..Computer::default()
+        }
+        .with_software(input_software);
+
+        computer.run().unwrap();
+        let got_output = computer.output;
+
+        assert_eq!(expected_output.values(), got_output.values());
+    }
+
+    #[test]
+    fn computer_run_day_05_example09_not_zero() {
+        let input_software = vec![3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1];
+        let input_values = vec![42];
+
+        let expected_output = Writer::Tester { values: vec![1] };
+
+        let mut computer = Computer {
+            input: Reader::Tester(Box::new(input_values.into_iter())),
+            output: Writer::Tester { values: Vec::new() },
+            ..Computer::default()
+        }
+        .with_software(input_software);
+
+        computer.run().unwrap();
+        let got_output = computer.output;
+
+        assert_eq!(expected_output.values(), got_output.values());
+    }
+
+    #[test]
+    fn computer_run_day_05_example10_below_eight() {
+        let input_software = vec![
+            3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0,
+            0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4,
+            20, 1105, 1, 46, 98, 99,
+        ];
+        let input_values = vec![-42];
+
+        let expected_output = Writer::Tester { values: vec![999] };
+
+        let mut computer = Computer {
+            input: Reader::Tester(Box::new(input_values.into_iter())),
+            output: Writer::Tester { values: Vec::new() },
+            ..Computer::default()
+        }
+        .with_software(input_software);
+
+        computer.run().unwrap();
+        let got_output = computer.output;
+
+        assert_eq!(expected_output.values(), got_output.values());
+    }
+
+    #[test]
+    fn computer_run_day_05_example10_equal_eight() {
+        let input_software = vec![
+            3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0,
+            0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4,
+            20, 1105, 1, 46, 98, 99,
+        ];
+        let input_values = vec![8];
+
+        let expected_output = Writer::Tester { values: vec![1000] };
+
+        let mut computer = Computer {
+            input: Reader::Tester(Box::new(input_values.into_iter())),
+            output: Writer::Tester { values: Vec::new() },
+            ..Computer::default()
+        }
+        .with_software(input_software);
+
+        computer.run().unwrap();
+        let got_output = computer.output;
+
+        assert_eq!(expected_output.values(), got_output.values());
+    }
+
+    #[test]
+    fn computer_run_day_05_example10_above_eight() {
+        let input_software = vec![
+            3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0,
+            0, 1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4,
+            20, 1105, 1, 46, 98, 99,
+        ];
+        let input_values = vec![42];
+
+        let expected_output = Writer::Tester { values: vec![1001] };
+
+        let mut computer = Computer {
+            input: Reader::Tester(Box::new(input_values.into_iter())),
+            output: Writer::Tester { values: Vec::new() },
+            ..Computer::default()
+        }
+        .with_software(input_software);
+
+        computer.run().unwrap();
+        let got_output = computer.output;
+
+        assert_eq!(expected_output.values(), got_output.values());
+    }
+
+    #[test]
+    fn computer_run_day_09_example01() {
+        let input_software = vec![109, 19, 204, -34, 99];
+        let expected = vec![42];
+
+        let mut computer = Computer::default()
+            .with_software(input_software)
+            .with_relative_base(2000)
+            .print_relative_base()
+            .print_output()
+            .print_instructions();
+
+        computer.write_value(1985, 42).unwrap();
+        computer.run().unwrap();
+
+        let got = computer.output.values();
+
+        assert_eq!(expected, got)
+    }
+
+    #[test]
+    fn computer_run_day_09_example02() {
+        let input_software = vec![
+            109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+        ];
+
+        let expected_output = input_software.clone();
+
+        let mut computer = Computer::default()
+            .with_software(input_software)
+            .print_relative_base()
+            .print_output()
+            .print_instructions();
+
+        computer.run().unwrap();
+        let got_output = computer.output.values();
+
+        assert_eq!(expected_output, got_output);
+    }
+
+    #[test]
+    fn computer_run_day_09_example03() {
+        let input_software = vec![1102, 34_915_192, 34_915_192, 7, 4, 7, 99, 0];
+        let expected_output = vec![1_219_070_632_396_864];
+
+        let mut computer = Computer::default()
+            .with_software(input_software)
+            .print_relative_base()
+            .print_output()
+            .print_instructions();
+
+        computer.run().unwrap();
+        let got_output = computer.output.values();
+
+        assert_eq!(expected_output, got_output);
+    }
+
+    #[test]
+    fn computer_run_day_09_example04() {
+        let input_software = vec![104, 1_125_899_906_842_624, 99];
+        let expected_output = vec![1_125_899_906_842_624];
+
+        let mut computer = Computer::default()
+            .with_software(input_software)
+            .print_relative_base()
+            .print_output()
+            .print_instructions();
+
+        computer.run().unwrap();
+        let got_output = computer.output.values();
+
+        assert_eq!(expected_output, got_output);
+    }
+
+    // Reads three values and writes them back.
+    const ECHO_THREE: &[isize] = &[3, 20, 4, 20, 3, 20, 4, 20, 3, 20, 4, 20, 99];
+
+    #[test]
+    fn reader_chain() {
+        let input = Reader::chain(vec![1, 2], Reader::Tester(Box::new(vec![3].into_iter())));
+
+        let mut computer = Computer::default()
+            .with_software(ECHO_THREE.to_vec())
+            .with_input(input);
+
+        computer.run().unwrap();
+
+        assert_eq!(vec![1, 2, 3], computer.output.values());
+    }
+
+    #[test]
+    fn reader_map_filter_record() {
+        let recording = Recording::default();
+
+        let input = Reader::Tester(Box::new(1..))
+            .filter(|value| value % 2 == 0)
+            .map(|value| value * 10)
+            .record(&recording);
+
+        let mut computer = Computer::default()
+            .with_software(ECHO_THREE.to_vec())
+            .with_input(input);
+
+        computer.run().unwrap();
+
+        assert_eq!(vec![20, 40, 60], computer.output.values());
+        assert_eq!(vec![20, 40, 60], recording.values());
+    }
+
+    #[test]
+    fn writer_tee_map_filter_record() {
+        let all = Recording::default();
+        let odd = Recording::default();
+
+        let output = Writer::tee(vec![
+            Writer::Tester { values: Vec::new() }.record(&all),
+            Writer::Tester { values: Vec::new() }
+                .record(&odd)
+                .map(|value| -value)
+                .filter(|value| value % 2 == 1),
+        ]);
+
+        let mut computer = Computer::default()
+            .with_software(ECHO_THREE.to_vec())
+            .with_input(Reader::Tester(Box::new(vec![1, 2, 3].into_iter())))
+            .with_output(output);
+
+        computer.run().unwrap();
+
+        assert_eq!(vec![1, 2, 3], all.values());
+        assert_eq!(vec![-1, -3], odd.values());
+    }
+
+    #[bench]
+    fn bench_computer_run_example_text(b: &mut Bencher) {
+        let input = vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+
+        let mut computer = Computer::from(input);
+        b.iter(|| computer.run());
+    }
+
+    #[bench]
+    fn bench_computer_run_example01(b: &mut Bencher) {
+        let input = vec![1, 0, 0, 0, 99];
+
+        let mut computer = Computer::from(input);
+        b.iter(|| computer.run());
+    }
+
+    #[bench]
+    fn bench_computer_run_example02(b: &mut Bencher) {
+        let input = vec![2, 3, 0, 3, 99];
+
+        let mut computer = Computer::from(input);
+        b.iter(|| computer.run());
+    }
+
+    #[bench]
+    fn bench_computer_run_example03(b: &mut Bencher) {
+        let input = vec![2, 4, 4, 5, 99, 0];
+
+        let mut computer = Computer::from(input);
+        b.iter(|| computer.run());
+    }
+
+    #[bench]
+    fn bench_computer_run_example04(b: &mut Bencher) {
+        let input = vec![1, 1, 1, 4, 99, 5, 6, 0, 99];
+
+        let mut computer = Computer::from(input);
+        b.iter(|| computer.run());
+    }
+
+    #[bench]
+    fn bench_computer_run_restore_gravity_assist_program(b: &mut Bencher) {
+        let input: Program = crate::day_02::part_1::INPUT.parse().unwrap();
+
+        let mut computer = Computer::from(input.to_vec());
+        b.iter(|| computer.run());
+    }
+}
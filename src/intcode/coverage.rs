@@ -0,0 +1,271 @@
+use super::computer::Intcode;
+use std::collections::BTreeMap;
+
+/// Executed addresses and taken branches recorded while running a computer
+/// with coverage enabled.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Coverage {
+    pub hits: BTreeMap<usize, usize>,
+    pub branches: BTreeMap<usize, Branch>,
+}
+
+/// How often the jump at an address was taken and how often execution fell
+/// through to the next instruction.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct Branch {
+    pub taken: usize,
+    pub not_taken: usize,
+}
+
+impl Branch {
+    pub fn is_covered(&self) -> bool {
+        self.taken > 0 && self.not_taken > 0
+    }
+}
+
+impl Coverage {
+    pub fn hit(&mut self, address: usize) {
+        *self.hits.entry(address).or_default() += 1;
+    }
+
+    pub fn branch(&mut self, address: usize, taken: bool) {
+        let branch = self.branches.entry(address).or_default();
+
+        if taken {
+            branch.taken += 1;
+        } else {
+            branch.not_taken += 1;
+        }
+    }
+
+    /// Adds the counts of another run, e.g. from a different test of the same
+    /// software.
+    pub fn merge(&mut self, other: &Self) {
+        for (address, hits) in &other.hits {
+            *self.hits.entry(*address).or_default() += hits;
+        }
+
+        for (address, other_branch) in &other.branches {
+            let branch = self.branches.entry(*address).or_default();
+            branch.taken += other_branch.taken;
+            branch.not_taken += other_branch.not_taken;
+        }
+    }
+
+    pub fn report<'a>(&'a self, software: &'a [isize]) -> Report<'a> {
+        Report {
+            coverage: self,
+            software,
+        }
+    }
+}
+
+/// Disassembly of the software annotated with the recorded coverage.
+pub struct Report<'a> {
+    coverage: &'a Coverage,
+    software: &'a [isize],
+}
+
+const VALUES_PER_LINE: usize = 8;
+
+impl<'a> std::fmt::Display for Report<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut address = 0;
+        let mut covered = 0;
+
+        while address < self.software.len() {
+            if let Some(hits) = self.coverage.hits.get(&address) {
+                let intcode = Intcode::from(self.software[address]);
+                let end = (address + intcode.size()).min(self.software.len());
+
+                let parameters = self.software[address + 1..end]
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                write!(f, "{:>8} {:>6}  {}\t{}", hits, address, intcode, parameters)?;
+
+                if let Some(branch) = self.coverage.branches.get(&address) {
+                    write!(
+                        f,
+                        "\t; taken {}, not taken {}",
+                        branch.taken, branch.not_taken
+                    )?;
+
+                    if !branch.is_covered() {
+                        write!(f, " (partial)")?;
+                    }
+                }
+
+                writeln!(f)?;
+
+                covered += end - address;
+                address = end;
+            } else {
+                let end = self
+                    .coverage
+                    .hits
+                    .range(address..)
+                    .next()
+                    .map(|(next, _)| *next)
+                    .unwrap_or_else(|| self.software.len())
+                    .min(self.software.len());
+
+                writeln!(
+                    f,
+                    "{:>8} {:>6}  ; never executed: addresses {} to {}",
+                    "-",
+                    address,
+                    address,
+                    end - 1
+                )?;
+
+                for (index, chunk) in self.software[address..end]
+                    .chunks(VALUES_PER_LINE)
+                    .enumerate()
+                {
+                    let values = chunk
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", ");
+
+                    writeln!(
+                        f,
+                        "{:>8} {:>6}  {}",
+                        "-",
+                        address + index * VALUES_PER_LINE,
+                        values
+                    )?;
+                }
+
+                address = end;
+            }
+        }
+
+        let branches_covered = self
+            .coverage
+            .branches
+            .values()
+            .filter(|branch| branch.is_covered())
+            .count();
+
+        writeln!(
+            f,
+            "covered {} of {} addresses, {} of {} branches",
+            covered,
+            self.software.len(),
+            branches_covered,
+            self.coverage.branches.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Branch,
+        Coverage,
+    };
+    use crate::intcode::computer::{
+        Computer,
+        Reader,
+    };
+    use pretty_assertions::assert_eq;
+
+    const SOFTWARE: &[isize] = &[3, 3, 1105, -1, 9, 1101, 0, 0, 12, 4, 12, 99, 1];
+
+    fn run(input: isize) -> Coverage {
+        let mut computer = Computer::default()
+            .with_software(SOFTWARE.to_vec())
+            .with_input(Reader::Tester(Box::new(vec![input].into_iter())))
+            .with_coverage();
+
+        computer.run().unwrap();
+        computer.coverage.unwrap()
+    }
+
+    #[test]
+    fn coverage_not_taken() {
+        let got = run(0);
+
+        assert_eq!(
+            vec![0, 2, 5, 9, 11],
+            got.hits.keys().copied().collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            Some(&Branch {
+                taken: 0,
+                not_taken: 1,
+            }),
+            got.branches.get(&2)
+        );
+    }
+
+    #[test]
+    fn coverage_merge() {
+        let mut got = run(0);
+        got.merge(&run(42));
+
+        assert_eq!(Some(&2), got.hits.get(&0));
+        assert_eq!(Some(&1), got.hits.get(&5));
+
+        assert_eq!(
+            Some(&Branch {
+                taken: 1,
+                not_taken: 1,
+            }),
+            got.branches.get(&2)
+        );
+    }
+
+    #[test]
+    fn coverage_report() {
+        let coverage = run(42);
+
+        let expected = "       1      0  READ\t[p]        \t3
+       1      2  JMPT\t[i, i]    \t-1, 9\t; taken 1, not taken 0 (partial)
+       -      5  ; never executed: addresses 5 to 8
+       -      5  1101, 0, 0, 12
+       1      9  WRITE\t[p]        \t12
+       1     11  HALT\t          \t
+       -     12  ; never executed: addresses 12 to 12
+       -     12  1
+covered 8 of 13 addresses, 0 of 1 branches
+";
+
+        let got = coverage.report(SOFTWARE).to_string();
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn coverage_report_past_software() {
+        let software = vec![1101, 99, 0, 10, 1106, 0, 10, 0];
+
+        let mut computer = Computer::default()
+            .with_software(software.clone())
+            .with_coverage();
+
+        computer.run().unwrap();
+        let coverage = computer.coverage.unwrap();
+
+        assert_eq!(
+            vec![0, 4, 10],
+            coverage.hits.keys().copied().collect::<Vec<_>>()
+        );
+
+        let expected = "       1      0  ADD\t[i, i, p]\t99, 0, 10
+       1      4  JMPF\t[i, i]    \t0, 10\t; taken 1, not taken 0 (partial)
+       -      7  ; never executed: addresses 7 to 7
+       -      7  0
+covered 7 of 8 addresses, 0 of 1 branches
+";
+
+        let got = coverage.report(&software).to_string();
+
+        assert_eq!(expected, got);
+    }
+}
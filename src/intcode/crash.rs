@@ -0,0 +1,275 @@
+use super::{
+    call_stack::Backtrace,
+    computer::{
+        ComputerError,
+        Intcode,
+        Parameter,
+    },
+};
+use std::collections::VecDeque;
+
+/// Number of addresses before and after the pc included in a crash report.
+const MEMORY_EXCERPT: usize = 8;
+
+/// The last executed instructions and the last values that went through the
+/// input and output of a computer.
+#[derive(Debug, Clone)]
+pub struct History {
+    capacity: usize,
+    traces: VecDeque<Trace>,
+    inputs: VecDeque<isize>,
+    outputs: VecDeque<isize>,
+}
+
+/// A decoded instruction with the values of its operands before it was
+/// executed.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Trace {
+    pub pc: usize,
+    pub raw: isize,
+    pub intcode: Intcode,
+    pub parameters: Vec<Parameter>,
+    pub relative_base: isize,
+}
+
+#[derive(Debug)]
+pub struct CrashReport {
+    pub error: ComputerError,
+    pub pc: usize,
+    pub relative_base: isize,
+    pub instruction: Trace,
+    /// Only available if the computer tracked its call stack.
+    pub backtrace: Option<Backtrace>,
+    pub history: Vec<Trace>,
+    pub memory: Vec<(usize, isize)>,
+    pub inputs: Vec<isize>,
+    pub outputs: Vec<isize>,
+    pub pending_input: Option<usize>,
+}
+
+impl History {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            traces: VecDeque::with_capacity(capacity),
+            inputs: VecDeque::with_capacity(capacity),
+            outputs: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn trace(&mut self, trace: Trace) {
+        push_bounded(&mut self.traces, trace, self.capacity);
+    }
+
+    pub fn input(&mut self, value: isize) {
+        push_bounded(&mut self.inputs, value, self.capacity);
+    }
+
+    pub fn output(&mut self, value: isize) {
+        push_bounded(&mut self.outputs, value, self.capacity);
+    }
+
+    pub fn traces(&self) -> impl Iterator<Item = &Trace> {
+        self.traces.iter()
+    }
+
+    pub fn inputs(&self) -> impl Iterator<Item = &isize> {
+        self.inputs.iter()
+    }
+
+    pub fn outputs(&self) -> impl Iterator<Item = &isize> {
+        self.outputs.iter()
+    }
+}
+
+fn push_bounded<T>(queue: &mut VecDeque<T>, value: T, capacity: usize) {
+    if capacity == 0 {
+        return;
+    }
+
+    if queue.len() == capacity {
+        queue.pop_front();
+    }
+
+    queue.push_back(value);
+}
+
+impl CrashReport {
+    /// Addresses around `pc` that should be included in the report.
+    pub fn memory_window(pc: usize) -> std::ops::RangeInclusive<usize> {
+        pc.saturating_sub(MEMORY_EXCERPT)..=pc + MEMORY_EXCERPT
+    }
+}
+
+impl std::fmt::Display for Trace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parameters = self
+            .parameters
+            .iter()
+            .map(|parameter| format!("{}({})", parameter.address, parameter.value))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        write!(
+            f,
+            "{:>6}  {}\t({})\t--\t{}",
+            self.pc, self.intcode, self.raw, parameters
+        )
+    }
+}
+
+impl std::fmt::Display for CrashReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "error: {:?}", self.error)?;
+        writeln!(f, "pc: {}", self.pc)?;
+        writeln!(f, "relative base: {}", self.relative_base)?;
+
+        writeln!(f)?;
+        writeln!(f, "instruction:")?;
+        writeln!(f, "{}", self.instruction)?;
+
+        if let Some(backtrace) = &self.backtrace {
+            writeln!(f)?;
+            writeln!(f, "backtrace:")?;
+            write!(f, "{}", backtrace)?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "history (oldest first):")?;
+        for trace in &self.history {
+            writeln!(f, "{}", trace)?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "memory:")?;
+        for (address, value) in &self.memory {
+            let marker = if *address == self.pc { ">" } else { " " };
+            writeln!(f, "{} {:>6}  {}", marker, address, value)?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "inputs: {:?}", self.inputs)?;
+        writeln!(f, "outputs: {:?}", self.outputs)?;
+
+        match self.pending_input {
+            Some(pending) => writeln!(f, "pending input: {}", pending),
+            None => writeln!(f, "pending input: unknown"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::intcode::computer::{
+        Computer,
+        ComputerError,
+        Reader,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn crash_report_negative_address() {
+        let input_software = vec![3, 7, 1001, 7, -5, 7, 4, 0, 99];
+        let input_values = vec![2];
+
+        let mut computer = Computer::default()
+            .with_software(input_software)
+            .with_input(Reader::Tester(Box::new(input_values.into_iter())))
+            .with_history(2);
+
+        let report = computer.run_with_crash_report().unwrap_err();
+
+        match report.error {
+            ComputerError::NegativeAddress(address) => assert_eq!(-3, address),
+            ref got => panic!("unexpected error: {:?}", got),
+        }
+
+        assert_eq!(6, report.pc);
+        assert_eq!(6, report.instruction.pc);
+        assert_eq!(
+            vec![2, 6],
+            report
+                .history
+                .iter()
+                .map(|trace| trace.pc)
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(vec![2], report.inputs);
+        assert_eq!(Some(0), report.pending_input);
+        assert_eq!(Some(&(6, 4)), report.memory.iter().find(|(a, _)| *a == 6));
+    }
+
+    #[test]
+    fn crash_report_negative_write_address() {
+        for software in [vec![1101, 1, 1, -1, 99], vec![109, -5, 21101, 1, 1, 2, 99]] {
+            let mut computer = Computer::from(software);
+
+            let report = computer.run_with_crash_report().unwrap_err();
+
+            assert!(
+                matches!(report.error, ComputerError::NegativeAddress(_)),
+                "{:?}",
+                report.error
+            );
+        }
+    }
+
+    #[test]
+    fn crash_report_invalid_parameter_mode() {
+        let mut computer = Computer::from(vec![301]);
+
+        let report = computer.run_with_crash_report().unwrap_err();
+
+        match report.error {
+            ComputerError::InvalidParameterMode(mode) => assert_eq!(3, mode),
+            ref got => panic!("unexpected error: {:?}", got),
+        }
+
+        assert_eq!(0, report.pc);
+        assert!(!computer.is_waiting_for_input());
+    }
+
+    #[test]
+    fn crash_report_input_exhausted() {
+        let input_software = vec![3, 0, 3, 0, 99];
+        let input_values = vec![1];
+
+        let mut computer = Computer::default()
+            .with_software(input_software)
+            .with_input(Reader::Tester(Box::new(input_values.into_iter())))
+            .with_history(8);
+
+        let report = computer.run_with_crash_report().unwrap_err();
+
+        match report.error {
+            ComputerError::InputExhausted => {}
+            ref got => panic!("unexpected error: {:?}", got),
+        }
+
+        let expected = "error: InputExhausted
+pc: 2
+relative base: 0
+
+instruction:
+     2  READ\t[p]        \t(3)\t--\t0(1)
+
+history (oldest first):
+     0  READ\t[p]        \t(3)\t--\t0(3)
+     2  READ\t[p]        \t(3)\t--\t0(1)
+
+memory:
+       0  1
+       1  0
+>      2  3
+       3  0
+       4  99
+
+inputs: [1]
+outputs: []
+pending input: 0
+";
+
+        assert_eq!(expected, report.to_string());
+    }
+}
@@ -0,0 +1,786 @@
+//! Debug Adapter Protocol server for Intcode software.
+//!
+//! Editors talk to the server over stdio or a local TCP socket. The `launch`
+//! request takes the `program` path, the `inputs` of the software and
+//! `stopOnEntry`. Breakpoints are set by address, either with
+//! `setInstructionBreakpoints` or with `setBreakpoints` where every line is
+//! read as an address, line 1 being address 0 unless the client counts lines
+//! from 0 with `linesStartAt1`. The stack view is built from the frames of the call
+//! stack and the variables show the registers and memory around `pc`.
+
+use super::{
+    computer::{
+        Computer,
+        Reader,
+        Recording,
+        Writer,
+    },
+    crash::CrashReport,
+    debugger::{
+        Debugger,
+        Stop,
+    },
+    program::{
+        LoadProgramError,
+        Program,
+    },
+    report::HaltReason,
+};
+use log::debug;
+use serde_json::{
+    json,
+    Value,
+};
+use std::{
+    io::{
+        BufRead,
+        BufReader,
+        Write,
+    },
+    net::{
+        TcpListener,
+        ToSocketAddrs,
+    },
+};
+
+/// The only thread of a computer.
+const THREAD_ID: i64 = 1;
+const REGISTERS_REFERENCE: i64 = 1;
+const MEMORY_REFERENCE: i64 = 2;
+/// Largest message a client may send, far more than any request needs.
+const MAX_CONTENT_LENGTH: usize = 1 << 20;
+
+#[derive(Debug)]
+pub enum DapError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    MissingContentLength,
+    InvalidHeader(String),
+    ContentTooLong(usize),
+}
+
+impl From<std::io::Error> for DapError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for DapError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+/// Reads one message framed by a `Content-Length` header. Returns `None` at
+/// the end of the stream.
+pub fn read_message<R: BufRead>(reader: &mut R) -> Result<Option<Value>, DapError> {
+    let mut content_length = None;
+
+    loop {
+        let mut line = String::new();
+
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let line = line.trim_end();
+
+        if line.is_empty() {
+            break;
+        }
+
+        match line.split_once(':') {
+            Some((name, value)) if name.eq_ignore_ascii_case("Content-Length") => {
+                let length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| DapError::InvalidHeader(line.to_string()))?;
+
+                if length > MAX_CONTENT_LENGTH {
+                    return Err(DapError::ContentTooLong(length));
+                }
+
+                content_length = Some(length);
+            }
+            Some(_) => {}
+            None => return Err(DapError::InvalidHeader(line.to_string())),
+        }
+    }
+
+    let mut content = vec![0; content_length.ok_or(DapError::MissingContentLength)?];
+    reader.read_exact(&mut content)?;
+
+    Ok(Some(serde_json::from_slice(&content)?))
+}
+
+pub fn write_message<W: Write>(writer: &mut W, message: &Value) -> Result<(), DapError> {
+    let content = serde_json::to_string(message)?;
+
+    write!(
+        writer,
+        "Content-Length: {}\r\n\r\n{}",
+        content.len(),
+        content
+    )?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Serves a single client over stdin and stdout.
+pub fn serve_stdio() -> Result<(), DapError> {
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+
+    Server::new(stdout.lock()).serve(stdin.lock())
+}
+
+/// Waits for a single client on `address` and serves it.
+pub fn serve_tcp<A: ToSocketAddrs>(address: A) -> Result<(), DapError> {
+    serve_listener(TcpListener::bind(address)?)
+}
+
+pub fn serve_listener(listener: TcpListener) -> Result<(), DapError> {
+    let (stream, peer) = listener.accept()?;
+    debug!("debug adapter client connected from {}", peer);
+
+    Server::new(stream.try_clone()?).serve(BufReader::new(stream))
+}
+
+pub struct Server<W: Write> {
+    writer: W,
+    seq: i64,
+    debugger: Option<Debugger>,
+    output: Recording,
+    /// Number of output values that were sent as events.
+    output_sent: usize,
+    stop_on_entry: bool,
+    /// Number of the first line and column of the client.
+    first_line: usize,
+    first_column: usize,
+}
+
+impl<W: Write> Server<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            seq: 0,
+            debugger: None,
+            output: Recording::default(),
+            output_sent: 0,
+            stop_on_entry: false,
+            first_line: 1,
+            first_column: 1,
+        }
+    }
+
+    /// Handles requests until the client disconnects.
+    pub fn serve<R: BufRead>(mut self, mut reader: R) -> Result<(), DapError> {
+        while let Some(request) = read_message(&mut reader)? {
+            let command = request["command"].as_str().unwrap_or_default().to_string();
+            let arguments = &request["arguments"];
+            debug!("debug adapter request {}", command);
+
+            let result = match command.as_str() {
+                "initialize" => Ok(self.initialize(arguments)),
+                "launch" => self.launch(arguments),
+                "setBreakpoints" => self.set_breakpoints(arguments),
+                "setInstructionBreakpoints" => self.set_instruction_breakpoints(arguments),
+                "setExceptionBreakpoints" => Ok(json!({ "breakpoints": [] })),
+                "configurationDone" => Ok(Value::Null),
+                "threads" => Ok(json!({
+                    "threads": [{ "id": THREAD_ID, "name": "intcode" }],
+                })),
+                "stackTrace" => self.stack_trace(),
+                "scopes" => Ok(json!({
+                    "scopes": [
+                        { "name": "Registers", "variablesReference": REGISTERS_REFERENCE, "expensive": false },
+                        { "name": "Memory", "variablesReference": MEMORY_REFERENCE, "expensive": false },
+                    ],
+                })),
+                "variables" => self.variables(arguments),
+                "continue" => self
+                    .debugger()
+                    .map(|_| json!({ "allThreadsContinued": true })),
+                "next" | "stepIn" | "stepOut" | "disconnect" => Ok(Value::Null),
+                _ => Err(format!("unsupported request {}", command)),
+            };
+
+            let succeeded = result.is_ok();
+            self.respond(&request, result)?;
+
+            if !succeeded {
+                continue;
+            }
+
+            match command.as_str() {
+                "initialize" => self.event("initialized", Value::Null)?,
+                "configurationDone" if self.stop_on_entry => {
+                    self.stopped("entry", None)?;
+                }
+                "configurationDone" | "continue" => self.execute(Debugger::resume)?,
+                "next" => self.execute(Debugger::step_over)?,
+                "stepIn" => self.execute(Debugger::step)?,
+                "stepOut" => self.execute(Debugger::step_out)?,
+                "disconnect" => return Ok(()),
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn initialize(&mut self, arguments: &Value) -> Value {
+        let first = |starts_at_1: &Value| usize::from(starts_at_1.as_bool().unwrap_or(true));
+
+        self.first_line = first(&arguments["linesStartAt1"]);
+        self.first_column = first(&arguments["columnsStartAt1"]);
+
+        json!({
+            "supportsConfigurationDoneRequest": true,
+            "supportsInstructionBreakpoints": true,
+        })
+    }
+
+    fn launch(&mut self, arguments: &Value) -> Result<Value, String> {
+        let path = arguments["program"]
+            .as_str()
+            .ok_or_else(|| "missing program".to_string())?;
+
+        let program = Program::from_file(path).map_err(|err| match err {
+            LoadProgramError::Io(err) => format!("can't read {}: {}", path, err),
+            LoadProgramError::Parse(err) => format!("can't parse {}: {:?}", path, err),
+        })?;
+
+        let inputs = arguments["inputs"]
+            .as_array()
+            .map(|inputs| inputs.iter().filter_map(Value::as_i64))
+            .into_iter()
+            .flatten()
+            .map(|value| value as isize)
+            .collect::<Vec<_>>();
+
+        let computer = Computer::default()
+            .with_software(program.into())
+            .with_input(Reader::Tester(Box::new(inputs.into_iter())))
+            .with_output(Writer::Tester { values: Vec::new() }.record(&self.output))
+            .with_call_stack();
+
+        self.debugger = Some(Debugger::new(computer));
+        self.stop_on_entry = arguments["stopOnEntry"].as_bool().unwrap_or_default();
+
+        Ok(Value::Null)
+    }
+
+    fn set_breakpoints(&mut self, arguments: &Value) -> Result<Value, String> {
+        let first_line = self.first_line;
+
+        let addresses = arguments["breakpoints"]
+            .as_array()
+            .map(|breakpoints| {
+                breakpoints
+                    .iter()
+                    .map(|breakpoint| {
+                        let line = breakpoint["line"].as_u64()? as usize;
+                        line.checked_sub(first_line)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        self.replace_breakpoints(addresses)
+    }
+
+    fn set_instruction_breakpoints(&mut self, arguments: &Value) -> Result<Value, String> {
+        let addresses = arguments["breakpoints"]
+            .as_array()
+            .map(|breakpoints| {
+                breakpoints
+                    .iter()
+                    .map(|breakpoint| {
+                        let reference = breakpoint["instructionReference"].as_str()?;
+                        let offset = breakpoint["offset"].as_i64().unwrap_or_default();
+
+                        let address = reference.parse::<i64>().ok()? + offset;
+
+                        if address < 0 {
+                            None
+                        } else {
+                            Some(address as usize)
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        self.replace_breakpoints(addresses)
+    }
+
+    /// `None` addresses are reported as unverified breakpoints.
+    fn replace_breakpoints(&mut self, addresses: Vec<Option<usize>>) -> Result<Value, String> {
+        let first_line = self.first_line;
+        let debugger = self.debugger_mut()?;
+        debugger.breakpoints = addresses.iter().flatten().copied().collect();
+
+        let breakpoints = addresses
+            .iter()
+            .map(|address| match address {
+                Some(address) => json!({
+                    "verified": true,
+                    "line": address + first_line,
+                    "instructionReference": address.to_string(),
+                }),
+                None => json!({ "verified": false, "message": "invalid address" }),
+            })
+            .collect::<Vec<_>>();
+
+        Ok(json!({ "breakpoints": breakpoints }))
+    }
+
+    fn stack_trace(&self) -> Result<Value, String> {
+        let debugger = self.debugger()?;
+
+        let frames = debugger
+            .computer
+            .backtrace()
+            .map(|backtrace| backtrace.frames)
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .map(|(id, frame)| {
+                let name = match frame.return_offset {
+                    Some(_) => format!("function at {}", frame.entry),
+                    None => "entry".to_string(),
+                };
+
+                json!({
+                    "id": id,
+                    "name": name,
+                    "line": frame.pc + self.first_line,
+                    "column": self.first_column,
+                    "instructionPointerReference": frame.pc.to_string(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok(json!({ "stackFrames": frames, "totalFrames": frames.len() }))
+    }
+
+    fn variables(&self, arguments: &Value) -> Result<Value, String> {
+        let computer = &self.debugger()?.computer;
+
+        let variables = match arguments["variablesReference"].as_i64() {
+            Some(REGISTERS_REFERENCE) => vec![
+                ("pc".to_string(), computer.pc as isize),
+                ("relative_base".to_string(), computer.relative_base),
+            ],
+            Some(MEMORY_REFERENCE) => CrashReport::memory_window(computer.pc)
+                .map(|address| {
+                    let value = computer.memory.get(&address).copied().unwrap_or_default();
+
+                    (format!("[{}]", address), value)
+                })
+                .collect(),
+            _ => return Err("unknown variables reference".to_string()),
+        };
+
+        let variables = variables
+            .into_iter()
+            .map(|(name, value)| {
+                json!({
+                    "name": name,
+                    "value": value.to_string(),
+                    "variablesReference": 0,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Ok(json!({ "variables": variables }))
+    }
+
+    /// Runs the debugger and reports where it stopped.
+    fn execute<F: FnOnce(&mut Debugger) -> Stop>(&mut self, run: F) -> Result<(), DapError> {
+        let stop = match self.debugger.as_mut() {
+            Some(debugger) => run(debugger),
+            None => return Ok(()),
+        };
+
+        self.flush_output()?;
+
+        match stop {
+            Stop::Step => self.stopped("step", None),
+            Stop::Breakpoint(_) => self.stopped("breakpoint", None),
+            Stop::Error(err) => self.stopped("exception", Some(format!("{:?}", err))),
+            Stop::Halt(halt) => {
+                let exit_code = match halt {
+                    HaltReason::Halt => 0,
+                    HaltReason::Exit(code) => code,
+                };
+
+                self.event("exited", json!({ "exitCode": exit_code }))?;
+                self.event("terminated", Value::Null)
+            }
+            Stop::Finished => self.event("terminated", Value::Null),
+        }
+    }
+
+    /// Sends the values the software wrote since the last call.
+    fn flush_output(&mut self) -> Result<(), DapError> {
+        let values = self.output.values();
+
+        for value in &values[self.output_sent..] {
+            self.event(
+                "output",
+                json!({ "category": "stdout", "output": format!("{}\n", value) }),
+            )?;
+        }
+
+        self.output_sent = values.len();
+
+        Ok(())
+    }
+
+    fn stopped(&mut self, reason: &str, description: Option<String>) -> Result<(), DapError> {
+        self.event(
+            "stopped",
+            json!({
+                "reason": reason,
+                "description": description,
+                "threadId": THREAD_ID,
+                "allThreadsStopped": true,
+            }),
+        )
+    }
+
+    fn debugger(&self) -> Result<&Debugger, String> {
+        self.debugger
+            .as_ref()
+            .ok_or_else(|| "no program launched".to_string())
+    }
+
+    fn debugger_mut(&mut self) -> Result<&mut Debugger, String> {
+        self.debugger
+            .as_mut()
+            .ok_or_else(|| "no program launched".to_string())
+    }
+
+    fn respond(&mut self, request: &Value, result: Result<Value, String>) -> Result<(), DapError> {
+        let mut response = json!({
+            "type": "response",
+            "request_seq": request["seq"],
+            "command": request["command"],
+            "success": result.is_ok(),
+        });
+
+        match result {
+            Ok(Value::Null) => {}
+            Ok(body) => response["body"] = body,
+            Err(message) => response["message"] = message.into(),
+        }
+
+        self.send(response)
+    }
+
+    fn event(&mut self, event: &str, body: Value) -> Result<(), DapError> {
+        let mut message = json!({ "type": "event", "event": event });
+
+        if !body.is_null() {
+            message["body"] = body;
+        }
+
+        self.send(message)
+    }
+
+    fn send(&mut self, mut message: Value) -> Result<(), DapError> {
+        self.seq += 1;
+        message["seq"] = self.seq.into();
+
+        write_message(&mut self.writer, &message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DapError,
+        read_message,
+        serve_listener,
+        write_message,
+    };
+    use crate::intcode::{
+        compiler::compile,
+        computer::{
+            Computer,
+            Reader,
+        },
+        debugger::Debugger,
+    };
+    use pretty_assertions::assert_eq;
+    use serde_json::{
+        json,
+        Value,
+    };
+    use std::{
+        io::BufReader,
+        net::{
+            TcpListener,
+            TcpStream,
+        },
+    };
+
+    /// Scripted client that talks to a server on localhost.
+    struct Client {
+        reader: BufReader<TcpStream>,
+        writer: TcpStream,
+        seq: i64,
+    }
+
+    impl Client {
+        fn connect() -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let address = listener.local_addr().unwrap();
+
+            std::thread::spawn(move || serve_listener(listener).unwrap());
+
+            let stream = TcpStream::connect(address).unwrap();
+
+            Self {
+                reader: BufReader::new(stream.try_clone().unwrap()),
+                writer: stream,
+                seq: 0,
+            }
+        }
+
+        /// Sends a request and returns its response.
+        fn request(&mut self, command: &str, arguments: Value) -> Value {
+            self.seq += 1;
+
+            let request = json!({
+                "seq": self.seq,
+                "type": "request",
+                "command": command,
+                "arguments": arguments,
+            });
+
+            write_message(&mut self.writer, &request).unwrap();
+
+            let response = self.receive();
+            assert_eq!(json!("response"), response["type"]);
+            assert_eq!(json!(self.seq), response["request_seq"]);
+            assert_eq!(json!(command), response["command"]);
+
+            response
+        }
+
+        fn receive(&mut self) -> Value {
+            read_message(&mut self.reader).unwrap().unwrap()
+        }
+
+        fn event(&mut self, event: &str) -> Value {
+            let message = self.receive();
+            assert_eq!(json!(event), message["event"], "{}", message);
+
+            message["body"].clone()
+        }
+    }
+
+    const DOUBLE: &str = "
+        fn double(n) {
+            return n + n;
+        }
+
+        fn main() {
+            output(double(input()));
+        }
+    ";
+
+    /// Address of the function the software calls first.
+    fn entry_of_first_call(software: &[isize]) -> usize {
+        let computer = Computer::default()
+            .with_software(software.to_vec())
+            .with_input(Reader::Tester(Box::new(vec![0].into_iter())))
+            .with_call_stack();
+        let mut debugger = Debugger::new(computer);
+
+        while debugger.depth() == 0 {
+            debugger.step();
+        }
+
+        debugger.computer.pc
+    }
+
+    #[test]
+    fn dap_session() {
+        let software = compile(DOUBLE).unwrap();
+        let path = std::env::temp_dir().join(format!("dap_session_{}.int", std::process::id()));
+
+        std::fs::write(
+            &path,
+            software
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(","),
+        )
+        .unwrap();
+
+        let mut client = Client::connect();
+
+        let response = client.request("initialize", json!({ "adapterID": "intcode" }));
+        assert_eq!(
+            json!(true),
+            response["body"]["supportsInstructionBreakpoints"]
+        );
+        client.event("initialized");
+
+        let response = client.request(
+            "launch",
+            json!({ "program": path, "inputs": [21], "stopOnEntry": true }),
+        );
+        assert_eq!(json!(true), response["success"]);
+
+        let entry = entry_of_first_call(&software);
+
+        let response = client.request(
+            "setInstructionBreakpoints",
+            json!({ "breakpoints": [{ "instructionReference": entry.to_string() }] }),
+        );
+        assert_eq!(json!(true), response["body"]["breakpoints"][0]["verified"]);
+
+        client.request("configurationDone", json!({}));
+        assert_eq!(json!("entry"), client.event("stopped")["reason"]);
+
+        client.request("stepIn", json!({ "threadId": 1 }));
+        assert_eq!(json!("step"), client.event("stopped")["reason"]);
+
+        client.request("continue", json!({ "threadId": 1 }));
+        assert_eq!(json!("breakpoint"), client.event("stopped")["reason"]);
+
+        let response = client.request("stackTrace", json!({ "threadId": 1 }));
+        let frames = response["body"]["stackFrames"].as_array().unwrap().clone();
+        assert_eq!(
+            json!(entry.to_string()),
+            frames[0]["instructionPointerReference"]
+        );
+        assert_eq!(json!(entry + 1), frames[0]["line"]);
+        assert_eq!(json!(format!("function at {}", entry)), frames[0]["name"]);
+        assert_eq!(json!("entry"), frames.last().unwrap()["name"]);
+
+        let response = client.request("scopes", json!({ "frameId": 0 }));
+        let scopes = response["body"]["scopes"].as_array().unwrap().clone();
+        assert_eq!(2, scopes.len());
+
+        let response = client.request(
+            "variables",
+            json!({ "variablesReference": scopes[0]["variablesReference"] }),
+        );
+        assert_eq!(json!("pc"), response["body"]["variables"][0]["name"]);
+        assert_eq!(
+            json!(entry.to_string()),
+            response["body"]["variables"][0]["value"]
+        );
+        assert_eq!(
+            json!("relative_base"),
+            response["body"]["variables"][1]["name"]
+        );
+
+        let response = client.request(
+            "variables",
+            json!({ "variablesReference": scopes[1]["variablesReference"] }),
+        );
+        let memory = response["body"]["variables"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|variable| variable["name"] == json!(format!("[{}]", entry)))
+            .cloned();
+        assert_eq!(
+            Some(json!("109")),
+            memory.map(|variable| variable["value"].clone())
+        );
+
+        client.request("next", json!({ "threadId": 1 }));
+        assert_eq!(json!("step"), client.event("stopped")["reason"]);
+
+        client.request("continue", json!({ "threadId": 1 }));
+        assert_eq!(json!("42\n"), client.event("output")["output"]);
+        assert_eq!(json!(0), client.event("exited")["exitCode"]);
+        client.event("terminated");
+
+        let response = client.request("disconnect", json!({}));
+        assert_eq!(json!(true), response["success"]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn dap_lines() {
+        let path = std::env::temp_dir().join(format!("dap_lines_{}.int", std::process::id()));
+        std::fs::write(&path, "104,1,104,2,99").unwrap();
+
+        // Lines count from 1 unless the client says otherwise.
+        for (starts_at_1, first_line) in [(None, 1), (Some(false), 0)] {
+            let mut client = Client::connect();
+
+            let arguments = match starts_at_1 {
+                Some(starts_at_1) => json!({ "linesStartAt1": starts_at_1 }),
+                None => json!({}),
+            };
+            client.request("initialize", arguments);
+            client.event("initialized");
+
+            client.request("launch", json!({ "program": path }));
+
+            let response = client.request(
+                "setBreakpoints",
+                json!({ "breakpoints": [{ "line": first_line + 2 }, { "line": -1 }] }),
+            );
+            let breakpoints = &response["body"]["breakpoints"];
+            assert_eq!(json!(true), breakpoints[0]["verified"]);
+            assert_eq!(json!(first_line + 2), breakpoints[0]["line"]);
+            assert_eq!(json!("2"), breakpoints[0]["instructionReference"]);
+            assert_eq!(json!(false), breakpoints[1]["verified"]);
+
+            client.request("configurationDone", json!({}));
+            assert_eq!(json!("1\n"), client.event("output")["output"]);
+            assert_eq!(json!("breakpoint"), client.event("stopped")["reason"]);
+
+            let response = client.request("stackTrace", json!({ "threadId": 1 }));
+            assert_eq!(
+                json!(first_line + 2),
+                response["body"]["stackFrames"][0]["line"]
+            );
+
+            client.request("disconnect", json!({}));
+        }
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn dap_message_too_long() {
+        let mut reader = "Content-Length: 1000000000\r\n\r\n{}".as_bytes();
+
+        assert!(matches!(
+            read_message(&mut reader),
+            Err(DapError::ContentTooLong(1_000_000_000))
+        ));
+    }
+
+    #[test]
+    fn dap_errors() {
+        let mut client = Client::connect();
+
+        let response = client.request("stackTrace", json!({ "threadId": 1 }));
+        assert_eq!(json!(false), response["success"]);
+        assert_eq!(json!("no program launched"), response["message"]);
+
+        let response = client.request("launch", json!({ "program": "/nonexistent.int" }));
+        assert_eq!(json!(false), response["success"]);
+
+        let response = client.request("evaluate", json!({}));
+        assert_eq!(json!("unsupported request evaluate"), response["message"]);
+
+        client.request("disconnect", json!({}));
+    }
+}
@@ -0,0 +1,201 @@
+use super::{
+    computer::{
+        Computer,
+        ComputerError,
+    },
+    report::HaltReason,
+};
+use std::collections::BTreeSet;
+
+/// Runs a computer instruction by instruction and stops at breakpoints.
+pub struct Debugger {
+    pub computer: Computer,
+    /// Addresses of instructions to stop at before they are executed.
+    pub breakpoints: BTreeSet<usize>,
+    finished: bool,
+}
+
+/// Why the debugger stopped running the computer.
+#[derive(Debug)]
+pub enum Stop {
+    Step,
+    Breakpoint(usize),
+    Halt(HaltReason),
+    Error(ComputerError),
+    /// The computer halted or failed before and can't continue.
+    Finished,
+}
+
+impl Debugger {
+    /// The computer should track its call stack to step over calls.
+    pub fn new(computer: Computer) -> Self {
+        Self {
+            computer,
+            breakpoints: BTreeSet::new(),
+            finished: false,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Executes a single instruction.
+    pub fn step(&mut self) -> Stop {
+        if self.finished {
+            return Stop::Finished;
+        }
+
+        match self.computer.step() {
+            Ok(None) => Stop::Step,
+            Ok(Some(halt)) => {
+                self.finished = true;
+                Stop::Halt(halt)
+            }
+            Err(err) => {
+                self.finished = true;
+                Stop::Error(err)
+            }
+        }
+    }
+
+    /// Runs until a breakpoint is hit or the computer halts. The instruction
+    /// at the current address is executed even if it has a breakpoint.
+    pub fn resume(&mut self) -> Stop {
+        self.run_while(|_| true)
+    }
+
+    /// Executes a single instruction and runs through any call it makes.
+    pub fn step_over(&mut self) -> Stop {
+        let depth = self.depth();
+
+        self.run_while(|debugger| debugger.depth() > depth)
+    }
+
+    /// Runs until the current function returns.
+    pub fn step_out(&mut self) -> Stop {
+        let depth = self.depth();
+
+        if depth == 0 {
+            return self.resume();
+        }
+
+        self.run_while(|debugger| debugger.depth() >= depth)
+    }
+
+    /// Number of active call frames.
+    pub fn depth(&self) -> usize {
+        self.computer
+            .call_stack
+            .as_ref()
+            .map_or(0, |call_stack| call_stack.frames.len())
+    }
+
+    fn run_while<F: Fn(&Self) -> bool>(&mut self, running: F) -> Stop {
+        match self.step() {
+            Stop::Step => {}
+            stop => return stop,
+        }
+
+        while running(self) {
+            if self.breakpoints.contains(&self.computer.pc) {
+                return Stop::Breakpoint(self.computer.pc);
+            }
+
+            match self.step() {
+                Stop::Step => {}
+                stop => return stop,
+            }
+        }
+
+        Stop::Step
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Debugger,
+        Stop,
+    };
+    use crate::intcode::{
+        compiler::compile,
+        computer::{
+            Computer,
+            Reader,
+            Writer,
+        },
+        report::HaltReason,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn debugger(source: &str, input: Vec<isize>) -> Debugger {
+        let computer = Computer::default()
+            .with_software(compile(source).unwrap())
+            .with_input(Reader::Tester(Box::new(input.into_iter())))
+            .with_call_stack();
+
+        Debugger::new(computer)
+    }
+
+    const DOUBLE: &str = "
+        fn double(n) {
+            return n + n;
+        }
+
+        fn main() {
+            output(double(input()));
+        }
+    ";
+
+    #[test]
+    fn debugger_breakpoint() {
+        let mut debugger = debugger(DOUBLE, vec![21]);
+        debugger.breakpoints.insert(2);
+
+        match debugger.resume() {
+            Stop::Breakpoint(2) => {}
+            got => panic!("unexpected stop: {:?}", got),
+        }
+
+        assert_eq!(2, debugger.computer.pc);
+
+        match debugger.resume() {
+            Stop::Halt(HaltReason::Halt) => {}
+            got => panic!("unexpected stop: {:?}", got),
+        }
+
+        match &debugger.computer.output {
+            Writer::Tester { values } => assert_eq!(&vec![42], values),
+            _ => unreachable!(),
+        }
+
+        match debugger.step() {
+            Stop::Finished => {}
+            got => panic!("unexpected stop: {:?}", got),
+        }
+    }
+
+    #[test]
+    fn debugger_step_over_calls() {
+        let mut stepped = debugger(DOUBLE, vec![21]);
+        let mut stepped_over = debugger(DOUBLE, vec![21]);
+
+        let depth = loop {
+            let depth = stepped.depth();
+            stepped.step();
+            stepped_over.step_over();
+
+            if stepped.depth() > depth {
+                break depth;
+            }
+        };
+
+        assert_eq!(depth, stepped_over.depth());
+        assert_ne!(stepped_over.computer.pc, stepped.computer.pc);
+
+        stepped.step_out();
+        assert_eq!(depth, stepped.depth());
+        assert_eq!(stepped_over.computer.pc, stepped.computer.pc);
+    }
+}
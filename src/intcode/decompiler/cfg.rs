@@ -0,0 +1,852 @@
+use super::expression::{
+    Expression,
+    Location,
+    Operator,
+};
+use crate::intcode::computer::{
+    Intcode,
+    ParameterMode,
+};
+use std::collections::{
+    BTreeMap,
+    BTreeSet,
+};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Operand {
+    Immediate(isize),
+    Position(isize),
+    Relative(isize),
+}
+
+#[derive(Debug, Clone)]
+pub struct Instruction {
+    pub address: usize,
+    pub intcode: Intcode,
+    pub operands: Vec<Operand>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Statement {
+    Assign(Location, Expression),
+    /// Write to a computed address.
+    Store(Expression, Expression),
+    Output(Expression),
+    AdjustRelativeBase(Expression),
+    /// Call of the function at the address with its arguments.
+    Call(usize, Vec<Expression>),
+}
+
+/// How control leaves a basic block.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Terminator {
+    /// Continues with the block right behind.
+    Fallthrough,
+    Goto(usize),
+    /// Jumps to the address if the condition is not 0 and falls through
+    /// otherwise.
+    Branch(Expression, usize),
+    Return(Option<Expression>),
+    Halt,
+    /// Jumps to an address computed at runtime.
+    IndirectJump(Expression),
+    /// The next value is no instruction.
+    End,
+}
+
+#[derive(Debug, Clone)]
+pub struct Block {
+    pub start: usize,
+    /// Address right behind the last instruction.
+    pub end: usize,
+    pub statements: Vec<Statement>,
+    pub terminator: Terminator,
+}
+
+#[derive(Debug, Clone)]
+pub struct Function {
+    pub entry: usize,
+    /// Slots the prologue moves the relative base by.
+    pub frame_size: isize,
+    /// Most arguments any call passes.
+    pub arity: usize,
+    pub blocks: BTreeMap<usize, Block>,
+}
+
+/// Effect of an instruction on the control flow.
+enum Control {
+    Continue,
+    Call(usize),
+    Goto(usize),
+    Branch(usize),
+    Return,
+    Stop,
+}
+
+/// Finds every function reachable from address 0 and rebuilds the
+/// expressions of their blocks.
+pub fn analyze(software: &[isize]) -> Vec<Function> {
+    // Software that writes into its own code uses the parameters it
+    // overwrites as pointers, so the code is discovered again once they are
+    // known.
+    let patched = patched(&functions(software, &BTreeSet::new()));
+    let mut functions = functions(software, &patched);
+    let scratch = Scratch::new(&functions);
+
+    for function in &mut functions {
+        let entry = function.entry;
+
+        for block in function.blocks.values_mut() {
+            fold(block, |location| scratch.contains(entry, location));
+            collect_arguments(block);
+
+            if let Terminator::Branch(Expression::Constant(condition), target) = block.terminator {
+                block.terminator = if condition != 0 {
+                    Terminator::Goto(target)
+                } else {
+                    Terminator::Fallthrough
+                };
+            }
+        }
+
+        prune(&mut function.blocks, entry);
+    }
+
+    let arities = functions
+        .iter()
+        .flat_map(|function| function.blocks.values())
+        .flat_map(|block| &block.statements)
+        .filter_map(|statement| match statement {
+            Statement::Call(target, arguments) => Some((*target, arguments.len())),
+            _ => None,
+        })
+        .fold(BTreeMap::new(), |mut arities, (target, count)| {
+            let arity = arities.entry(target).or_insert(0);
+            *arity = count.max(*arity);
+            arities
+        });
+
+    for function in &mut functions {
+        let entry = function.entry;
+        let frame_size = function.frame_size;
+        function.arity = arities.get(&entry).copied().unwrap_or_default();
+
+        for block in function.blocks.values_mut() {
+            fold_results(block, |location| scratch.contains(entry, location));
+
+            if let Terminator::Return(None) = block.terminator {
+                if let Some(Statement::Assign(Location::Slot(offset), _)) = block.statements.last()
+                {
+                    if *offset == 1 - frame_size {
+                        if let Some(Statement::Assign(_, value)) = block.statements.pop() {
+                            block.terminator = Terminator::Return(Some(value));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    functions
+}
+
+/// Every function reachable from address 0.
+fn functions(software: &[isize], patched: &BTreeSet<usize>) -> Vec<Function> {
+    let mut functions = BTreeMap::new();
+    let mut queue = vec![0];
+
+    while let Some(entry) = queue.pop() {
+        if functions.contains_key(&entry) {
+            continue;
+        }
+
+        let function = discover(software, entry, patched);
+
+        for block in function.blocks.values() {
+            for statement in &block.statements {
+                if let Statement::Call(target, _) = statement {
+                    queue.push(*target);
+                }
+            }
+        }
+
+        functions.insert(entry, function);
+    }
+
+    functions.into_values().collect()
+}
+
+/// Code cells that some instruction writes to.
+fn patched(functions: &[Function]) -> BTreeSet<usize> {
+    let blocks = functions
+        .iter()
+        .flat_map(|function| function.blocks.values())
+        .collect::<Vec<_>>();
+
+    blocks
+        .iter()
+        .flat_map(|block| &block.statements)
+        .filter_map(|statement| match statement {
+            Statement::Assign(Location::Memory(address), _) if *address >= 0 => {
+                Some(*address as usize)
+            }
+            _ => None,
+        })
+        .filter(|address| {
+            blocks
+                .iter()
+                .any(|block| (block.start..block.end).contains(address))
+        })
+        .collect()
+}
+
+pub fn decode(software: &[isize], address: usize) -> Option<Instruction> {
+    let value = *software.get(address)?;
+
+    let valid_modes = value >= 0
+        && (value / 100)
+            .to_string()
+            .chars()
+            .all(|digit| ('0'..='2').contains(&digit));
+
+    if !valid_modes {
+        return None;
+    }
+
+    let intcode = Intcode::from(value);
+
+    if intcode == Intcode::Skip {
+        return None;
+    }
+
+    let operands = intcode
+        .modes()
+        .iter()
+        .enumerate()
+        .map(|(index, mode)| {
+            let value = *software.get(address + 1 + index)?;
+
+            Some(match mode {
+                ParameterMode::Immediate => Operand::Immediate(value),
+                ParameterMode::Position => Operand::Position(value),
+                ParameterMode::Relative => Operand::Relative(value),
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    Some(Instruction {
+        address,
+        intcode,
+        operands,
+    })
+}
+
+/// Follows every jump from the entry but not into called functions.
+fn discover(software: &[isize], entry: usize, patched: &BTreeSet<usize>) -> Function {
+    let frame_size = match decode(software, entry) {
+        Some(Instruction {
+            intcode: Intcode::AdjustRelativeBase(_),
+            operands,
+            ..
+        }) if entry != 0 => match operands[0] {
+            Operand::Immediate(size) if size > 0 => size,
+            _ => 0,
+        },
+        _ => 0,
+    };
+
+    let mut instructions = BTreeMap::new();
+    let mut leaders = BTreeSet::new();
+    let mut stack = vec![entry];
+
+    leaders.insert(entry);
+
+    while let Some(start) = stack.pop() {
+        let mut address = start;
+        let mut previous: Option<Instruction> = None;
+
+        while let Some(instruction) = decode(software, address) {
+            if instructions.contains_key(&address) {
+                // Two paths join here.
+                leaders.insert(address);
+                break;
+            }
+
+            let next = address + instruction.intcode.size();
+
+            instructions.insert(address, instruction.clone());
+
+            match control(&instruction, previous.as_ref()) {
+                Control::Continue | Control::Call(_) => {}
+                Control::Goto(target) => {
+                    leaders.insert(target);
+                    stack.push(target);
+                    break;
+                }
+                Control::Branch(target) => {
+                    leaders.insert(target);
+                    leaders.insert(next);
+                    stack.push(target);
+                }
+                Control::Return | Control::Stop => break,
+            }
+
+            previous = Some(instruction);
+            address = next;
+        }
+    }
+
+    let mut blocks = BTreeMap::new();
+    let mut current: Option<Block> = None;
+    let mut previous: Option<Instruction> = None;
+
+    for (address, instruction) in instructions {
+        if let Some(mut block) = current.take() {
+            if leaders.contains(&address) || block.end != address {
+                if block.end != address {
+                    block.terminator = Terminator::End;
+                }
+
+                blocks.insert(block.start, block);
+            } else {
+                current = Some(block);
+            }
+        }
+
+        let block = current.get_or_insert_with(|| Block {
+            start: address,
+            end: address,
+            statements: Vec::new(),
+            terminator: Terminator::Fallthrough,
+        });
+
+        block.end = address + instruction.intcode.size();
+
+        let control = control(&instruction, previous.as_ref());
+
+        match control {
+            Control::Continue => {
+                if !(address == entry && frame_size > 0) {
+                    block.statements.extend(translate(&instruction, patched));
+                }
+            }
+            Control::Call(target) => {
+                // The return address is part of the call.
+                block.statements.pop();
+                block.statements.push(Statement::Call(target, Vec::new()));
+            }
+            Control::Goto(target) => block.terminator = Terminator::Goto(target),
+            Control::Branch(target) => {
+                let condition = instruction.operands[0].expression();
+
+                block.terminator = Terminator::Branch(
+                    match instruction.intcode {
+                        Intcode::JumpIfFalse(..) => !condition,
+                        _ => condition,
+                    },
+                    target,
+                );
+            }
+            Control::Return => {
+                if block.statements.last()
+                    == Some(&Statement::AdjustRelativeBase(Expression::Constant(
+                        -frame_size,
+                    )))
+                {
+                    block.statements.pop();
+                }
+
+                block.terminator = Terminator::Return(None);
+            }
+            Control::Stop => {
+                block.terminator = match instruction.intcode {
+                    Intcode::Halt => Terminator::Halt,
+                    _ => Terminator::IndirectJump(instruction.operands[1].expression()),
+                };
+            }
+        }
+
+        if !matches!(control, Control::Continue | Control::Call(_)) {
+            blocks.insert(block.start, current.take().unwrap());
+        }
+
+        previous = Some(instruction);
+    }
+
+    if let Some(mut block) = current {
+        if block.terminator == Terminator::Fallthrough {
+            block.terminator = Terminator::End;
+        }
+
+        blocks.insert(block.start, block);
+    }
+
+    inline_returns(&mut blocks, entry);
+
+    Function {
+        entry,
+        frame_size,
+        arity: 0,
+        blocks,
+    }
+}
+
+fn control(instruction: &Instruction, previous: Option<&Instruction>) -> Control {
+    let when = match instruction.intcode {
+        Intcode::Halt => return Control::Stop,
+        Intcode::JumpIfTrue(..) => true,
+        Intcode::JumpIfFalse(..) => false,
+        _ => return Control::Continue,
+    };
+
+    let taken = match instruction.operands[0] {
+        Operand::Immediate(value) => Some((value != 0) == when),
+        _ => None,
+    };
+
+    match (taken, instruction.operands[1]) {
+        (Some(false), _) => Control::Continue,
+        (Some(true), Operand::Immediate(target)) if target >= 0 => {
+            let return_address = (instruction.address + instruction.intcode.size()) as isize;
+
+            if previous.and_then(stored_return_address) == Some(return_address) {
+                Control::Call(target as usize)
+            } else {
+                Control::Goto(target as usize)
+            }
+        }
+        (Some(true), Operand::Relative(0)) => Control::Return,
+        (None, Operand::Immediate(target)) if target >= 0 => Control::Branch(target as usize),
+        _ => Control::Stop,
+    }
+}
+
+/// The constant an instruction stores at `rb+0` where the return address of
+/// a call goes.
+fn stored_return_address(instruction: &Instruction) -> Option<isize> {
+    match (&instruction.intcode, instruction.operands.as_slice()) {
+        (
+            Intcode::Add(..),
+            [Operand::Immediate(left), Operand::Immediate(right), Operand::Relative(0)],
+        ) => Some(left + right),
+        (
+            Intcode::Mul(..),
+            [Operand::Immediate(left), Operand::Immediate(right), Operand::Relative(0)],
+        ) => Some(left * right),
+        _ => None,
+    }
+}
+
+fn translate(instruction: &Instruction, patched: &BTreeSet<usize>) -> Option<Statement> {
+    let operand = |index: usize| {
+        let cell = instruction.address + 1 + index;
+        let operand = instruction.operands[index];
+
+        if !patched.contains(&cell) {
+            return operand.expression();
+        }
+
+        match operand {
+            Operand::Immediate(_) => Expression::Memory(cell as isize),
+            Operand::Position(_) => Expression::Memory(cell as isize).dereference(),
+            Operand::Relative(_) => operand.expression(),
+        }
+    };
+
+    let store = |index: usize, value| {
+        let cell = instruction.address + 1 + index;
+
+        match instruction.operands[index] {
+            Operand::Position(_) if patched.contains(&cell) => {
+                Statement::Store(Expression::Memory(cell as isize), value)
+            }
+            destination => Statement::Assign(destination.location(), value),
+        }
+    };
+
+    let binary = |operator| store(2, Expression::binary(operator, operand(0), operand(1)));
+
+    Some(match instruction.intcode {
+        Intcode::Add(..) => binary(Operator::Add),
+        Intcode::Mul(..) => binary(Operator::Multiply),
+        Intcode::LessThan(..) => binary(Operator::Less),
+        Intcode::Equals(..) => binary(Operator::Equal),
+        Intcode::Read(_) => store(0, Expression::Input),
+        Intcode::Write(_) => Statement::Output(operand(0)),
+        Intcode::AdjustRelativeBase(_) => Statement::AdjustRelativeBase(operand(0)),
+        _ => return None,
+    })
+}
+
+/// Jumps to a block that only returns return right away, so the value
+/// stored before the jump becomes the returned value.
+fn inline_returns(blocks: &mut BTreeMap<usize, Block>, entry: usize) {
+    let returns = blocks
+        .values()
+        .filter(|block| block.statements.is_empty() && block.terminator == Terminator::Return(None))
+        .map(|block| block.start)
+        .collect::<BTreeSet<_>>();
+
+    for block in blocks.values_mut() {
+        let target = match block.terminator {
+            Terminator::Goto(target) => target,
+            Terminator::Fallthrough => block.end,
+            _ => continue,
+        };
+
+        if returns.contains(&target) {
+            block.terminator = Terminator::Return(None);
+        }
+    }
+
+    prune(blocks, entry);
+}
+
+/// Drops the blocks that can't be reached from the entry.
+fn prune(blocks: &mut BTreeMap<usize, Block>, entry: usize) {
+    let mut reachable = BTreeSet::new();
+    let mut stack = vec![entry];
+
+    while let Some(start) = stack.pop() {
+        let block = match blocks.get(&start) {
+            Some(block) if reachable.insert(start) => block,
+            _ => continue,
+        };
+
+        match &block.terminator {
+            Terminator::Fallthrough => stack.push(block.end),
+            Terminator::Goto(target) => stack.push(*target),
+            Terminator::Branch(_, target) => stack.extend(&[*target, block.end]),
+            _ => {}
+        }
+    }
+
+    blocks.retain(|start, _| reachable.contains(start));
+}
+
+/// Locations that never carry a value from one block to another: every read
+/// in a block comes after a write in the same block. Values in these
+/// locations can be folded into the expression that reads them.
+struct Scratch {
+    /// Memory read before it is written in some block.
+    memory: BTreeSet<isize>,
+    /// Slots per function read before they are written in some block.
+    slots: BTreeSet<(usize, isize)>,
+}
+
+impl Scratch {
+    fn new(functions: &[Function]) -> Self {
+        let mut scratch = Self {
+            memory: BTreeSet::new(),
+            slots: BTreeSet::new(),
+        };
+
+        for function in functions {
+            for block in function.blocks.values() {
+                let mut written = BTreeSet::new();
+
+                for statement in &block.statements {
+                    for location in statement.reads() {
+                        if !written.contains(&location) {
+                            scratch.exposed(function.entry, location);
+                        }
+                    }
+
+                    match statement {
+                        Statement::Assign(location, _) => {
+                            written.insert(*location);
+                        }
+                        // The result of a call is in the first slot behind
+                        // the frame.
+                        Statement::Call(..) => {
+                            written.insert(Location::Slot(1));
+                        }
+                        _ => {}
+                    }
+                }
+
+                for location in block.terminator.reads() {
+                    if !written.contains(&location) {
+                        scratch.exposed(function.entry, location);
+                    }
+                }
+            }
+        }
+
+        scratch
+    }
+
+    fn exposed(&mut self, function: usize, location: Location) {
+        match location {
+            Location::Memory(address) => self.memory.insert(address),
+            Location::Slot(offset) => self.slots.insert((function, offset)),
+        };
+    }
+
+    fn contains(&self, function: usize, location: Location) -> bool {
+        match location {
+            Location::Memory(address) => !self.memory.contains(&address),
+            Location::Slot(offset) => !self.slots.contains(&(function, offset)),
+        }
+    }
+}
+
+/// Folds a value into the statement right behind if that statement is the
+/// only one that reads it, e.g. `[381] = [382] < 35` and a jump if `[381]`
+/// becomes a jump if `[382] < 35`.
+fn fold<F: Fn(Location) -> bool>(block: &mut Block, scratch: F) {
+    let mut index = 0;
+
+    while index < block.statements.len() {
+        let (location, value) = match &block.statements[index] {
+            Statement::Assign(location, value) => (*location, value.clone()),
+            _ => {
+                index += 1;
+                continue;
+            }
+        };
+
+        let folded = match block.statements.get_mut(index + 1) {
+            Some(next) => {
+                let foldable = count(&next.reads(), location) == 1
+                    && (next.writes() == Some(location) || scratch(location))
+                    && !(value.has_side_effects() && next.has_side_effects());
+
+                if foldable {
+                    next.replace(location, &value);
+                }
+
+                foldable
+            }
+            None => {
+                let foldable = count(&block.terminator.reads(), location) == 1
+                    && scratch(location)
+                    && !value.has_side_effects();
+
+                if foldable {
+                    block.terminator.replace(location, &value);
+                }
+
+                foldable
+            }
+        };
+
+        if folded {
+            block.statements.remove(index);
+        } else {
+            index += 1;
+        }
+    }
+}
+
+/// Moves the values stored right before a call at `rb+1` and up into its
+/// arguments.
+fn collect_arguments(block: &mut Block) {
+    let mut index = 0;
+
+    while index < block.statements.len() {
+        if !matches!(block.statements[index], Statement::Call(..)) {
+            index += 1;
+            continue;
+        }
+
+        let mut stored = BTreeMap::new();
+        let mut first = index;
+
+        while first > 0 {
+            match &block.statements[first - 1] {
+                Statement::Assign(Location::Slot(offset), value)
+                    if *offset >= 1 && !stored.contains_key(offset) =>
+                {
+                    stored.insert(*offset, (first - 1, value.clone()));
+                    first -= 1;
+                }
+                _ => break,
+            }
+        }
+
+        let arguments = (1..)
+            .map_while(|offset| stored.remove(&offset))
+            .collect::<Vec<_>>();
+
+        let mut positions = arguments
+            .iter()
+            .map(|(position, _)| *position)
+            .collect::<Vec<_>>();
+        positions.sort_unstable();
+
+        for position in positions.iter().rev() {
+            block.statements.remove(*position);
+        }
+
+        index -= positions.len();
+
+        if let Statement::Call(_, call_arguments) = &mut block.statements[index] {
+            *call_arguments = arguments.into_iter().map(|(_, value)| value).collect();
+        }
+
+        index += 1;
+    }
+}
+
+/// Folds a call into the statement right behind if that is the only one that
+/// reads its result.
+fn fold_results<F: Fn(Location) -> bool>(block: &mut Block, scratch: F) {
+    let result = Location::Slot(1);
+
+    if !scratch(result) {
+        return;
+    }
+
+    let mut index = 0;
+
+    while index < block.statements.len() {
+        let call = match &block.statements[index] {
+            Statement::Call(target, arguments) => Expression::Call(*target, arguments.clone()),
+            _ => {
+                index += 1;
+                continue;
+            }
+        };
+
+        let folded = match block.statements.get_mut(index + 1) {
+            Some(next) if count(&next.reads(), result) == 1 && !next.has_side_effects() => {
+                next.replace(result, &call);
+                true
+            }
+            Some(_) => false,
+            None => {
+                let foldable = count(&block.terminator.reads(), result) == 1;
+
+                if foldable {
+                    block.terminator.replace(result, &call);
+                }
+
+                foldable
+            }
+        };
+
+        if folded {
+            block.statements.remove(index);
+        } else {
+            index += 1;
+        }
+    }
+}
+
+fn count(reads: &[Location], location: Location) -> usize {
+    reads.iter().filter(|read| **read == location).count()
+}
+
+impl Operand {
+    pub fn expression(self) -> Expression {
+        match self {
+            Operand::Immediate(value) => Expression::Constant(value),
+            Operand::Position(address) => Expression::Memory(address),
+            Operand::Relative(offset) => Expression::Slot(offset),
+        }
+    }
+
+    /// Where a write with this operand goes. Immediate operands write to
+    /// the address they name like position operands.
+    pub fn location(self) -> Location {
+        match self {
+            Operand::Immediate(address) | Operand::Position(address) => Location::Memory(address),
+            Operand::Relative(offset) => Location::Slot(offset),
+        }
+    }
+}
+
+impl Statement {
+    pub fn reads(&self) -> Vec<Location> {
+        let mut reads = Vec::new();
+
+        match self {
+            Statement::Assign(_, value)
+            | Statement::Output(value)
+            | Statement::AdjustRelativeBase(value) => value.reads(&mut reads),
+            Statement::Store(address, value) => {
+                address.reads(&mut reads);
+                value.reads(&mut reads);
+            }
+            Statement::Call(_, arguments) => {
+                for argument in arguments {
+                    argument.reads(&mut reads);
+                }
+            }
+        }
+
+        reads
+    }
+
+    pub fn writes(&self) -> Option<Location> {
+        match self {
+            Statement::Assign(location, _) => Some(*location),
+            _ => None,
+        }
+    }
+
+    /// Whether evaluating the statement reads input or calls a function
+    /// before it takes effect, so nothing that does the same can be folded
+    /// into it.
+    pub fn has_side_effects(&self) -> bool {
+        match self {
+            Statement::Assign(_, value)
+            | Statement::Output(value)
+            | Statement::AdjustRelativeBase(value) => value.has_side_effects(),
+            Statement::Store(address, value) => {
+                address.has_side_effects() || value.has_side_effects()
+            }
+            Statement::Call(..) => true,
+        }
+    }
+
+    fn replace(&mut self, location: Location, with: &Expression) {
+        let replace = |value: &mut Expression| {
+            *value = std::mem::replace(value, Expression::Input).replace(location, with);
+        };
+
+        match self {
+            Statement::Assign(_, value)
+            | Statement::Output(value)
+            | Statement::AdjustRelativeBase(value) => replace(value),
+            Statement::Store(address, value) => {
+                replace(address);
+                replace(value);
+            }
+            Statement::Call(_, arguments) => arguments.iter_mut().for_each(replace),
+        }
+    }
+}
+
+impl Terminator {
+    pub fn reads(&self) -> Vec<Location> {
+        let mut reads = Vec::new();
+
+        match self {
+            Terminator::Branch(condition, _) => condition.reads(&mut reads),
+            Terminator::IndirectJump(target) => target.reads(&mut reads),
+            Terminator::Return(Some(value)) => value.reads(&mut reads),
+            _ => {}
+        }
+
+        reads
+    }
+
+    /// Address the block jumps to other than the one right behind.
+    pub fn target(&self) -> Option<usize> {
+        match self {
+            Terminator::Goto(target) | Terminator::Branch(_, target) => Some(*target),
+            _ => None,
+        }
+    }
+
+    fn replace(&mut self, location: Location, with: &Expression) {
+        if let Terminator::Branch(value, _)
+        | Terminator::IndirectJump(value)
+        | Terminator::Return(Some(value)) = self
+        {
+            *value = std::mem::replace(value, Expression::Input).replace(location, with);
+        }
+    }
+}
@@ -0,0 +1,224 @@
+/// Memory an instruction writes to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Location {
+    Memory(isize),
+    /// Offset from the relative base of the function after its prologue.
+    Slot(isize),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Expression {
+    Constant(isize),
+    Memory(isize),
+    Slot(isize),
+    Input,
+    /// Memory at the computed address.
+    Dereference(Box<Expression>),
+    /// Call of the function at the address with its arguments.
+    Call(usize, Vec<Expression>),
+    Negate(Box<Expression>),
+    Not(Box<Expression>),
+    Binary(Operator, Box<Expression>, Box<Expression>),
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Less,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Location {
+    pub fn expression(self) -> Expression {
+        match self {
+            Location::Memory(address) => Expression::Memory(address),
+            Location::Slot(offset) => Expression::Slot(offset),
+        }
+    }
+}
+
+impl Expression {
+    /// Builds a binary expression and folds constants and neutral elements.
+    pub fn binary(operator: Operator, left: Expression, right: Expression) -> Self {
+        use Expression::Constant;
+        use Operator::*;
+
+        match (operator, left, right) {
+            (Add, Constant(a), Constant(b)) => Constant(a.wrapping_add(b)),
+            (Subtract, Constant(a), Constant(b)) => Constant(a.wrapping_sub(b)),
+            (Multiply, Constant(a), Constant(b)) => Constant(a.wrapping_mul(b)),
+            (Less, Constant(a), Constant(b)) => Constant((a < b) as isize),
+            (GreaterEqual, Constant(a), Constant(b)) => Constant((a >= b) as isize),
+            (Equal, Constant(a), Constant(b)) => Constant((a == b) as isize),
+            (NotEqual, Constant(a), Constant(b)) => Constant((a != b) as isize),
+
+            (Add, Constant(0), other)
+            | (Add, other, Constant(0))
+            | (Subtract, other, Constant(0)) => other,
+            (Add, other, Constant(c)) | (Add, Constant(c), other) if c < 0 => {
+                Self::binary(Subtract, other, Constant(-c))
+            }
+
+            (Multiply, Constant(0), other) | (Multiply, other, Constant(0))
+                if !other.has_side_effects() =>
+            {
+                Constant(0)
+            }
+            (Multiply, Constant(1), other) | (Multiply, other, Constant(1)) => other,
+            (Multiply, Constant(-1), other) | (Multiply, other, Constant(-1)) => other.negate(),
+
+            (operator, left, right) => {
+                Expression::Binary(operator, Box::new(left), Box::new(right))
+            }
+        }
+    }
+
+    pub fn dereference(self) -> Self {
+        match self {
+            Expression::Constant(address) => Expression::Memory(address),
+            address => Expression::Dereference(Box::new(address)),
+        }
+    }
+
+    pub fn negate(self) -> Self {
+        match self {
+            Expression::Constant(value) => Expression::Constant(value.wrapping_neg()),
+            Expression::Negate(expression) => *expression,
+            expression => Expression::Negate(Box::new(expression)),
+        }
+    }
+
+    /// Whether evaluating the expression reads input or calls a function.
+    pub fn has_side_effects(&self) -> bool {
+        match self {
+            Expression::Input | Expression::Call(..) => true,
+            Expression::Constant(_) | Expression::Memory(_) | Expression::Slot(_) => false,
+            Expression::Dereference(expression)
+            | Expression::Negate(expression)
+            | Expression::Not(expression) => expression.has_side_effects(),
+            Expression::Binary(_, left, right) => {
+                left.has_side_effects() || right.has_side_effects()
+            }
+        }
+    }
+
+    /// Every location the expression reads in evaluation order. Reads through
+    /// a computed address are left out.
+    pub fn reads(&self, reads: &mut Vec<Location>) {
+        match self {
+            Expression::Memory(address) => reads.push(Location::Memory(*address)),
+            Expression::Slot(offset) => reads.push(Location::Slot(*offset)),
+            Expression::Constant(_) | Expression::Input => {}
+            Expression::Call(_, arguments) => {
+                for argument in arguments {
+                    argument.reads(reads);
+                }
+            }
+            Expression::Dereference(expression)
+            | Expression::Negate(expression)
+            | Expression::Not(expression) => expression.reads(reads),
+            Expression::Binary(_, left, right) => {
+                left.reads(reads);
+                right.reads(reads);
+            }
+        }
+    }
+
+    /// Replaces every read of `location` and simplifies again.
+    pub fn replace(self, location: Location, with: &Expression) -> Self {
+        match self {
+            Expression::Memory(address) if location == Location::Memory(address) => with.clone(),
+            Expression::Slot(offset) if location == Location::Slot(offset) => with.clone(),
+            Expression::Call(target, arguments) => Expression::Call(
+                target,
+                arguments
+                    .into_iter()
+                    .map(|argument| argument.replace(location, with))
+                    .collect(),
+            ),
+            Expression::Dereference(expression) => expression.replace(location, with).dereference(),
+            Expression::Negate(expression) => expression.replace(location, with).negate(),
+            Expression::Not(expression) => !expression.replace(location, with),
+            Expression::Binary(operator, left, right) => Self::binary(
+                operator,
+                left.replace(location, with),
+                right.replace(location, with),
+            ),
+            expression => expression,
+        }
+    }
+}
+
+impl std::ops::Not for Expression {
+    type Output = Self;
+
+    /// Logical negation that flips comparisons instead of wrapping them.
+    fn not(self) -> Self {
+        use Operator::*;
+
+        match self {
+            Expression::Constant(value) => Expression::Constant((value == 0) as isize),
+            Expression::Not(expression) => *expression,
+            Expression::Binary(operator, left, right) => {
+                let flipped = match operator {
+                    Less => GreaterEqual,
+                    GreaterEqual => Less,
+                    Equal => NotEqual,
+                    NotEqual => Equal,
+                    operator => {
+                        return Expression::Not(Box::new(Expression::Binary(operator, left, right)))
+                    }
+                };
+
+                Expression::Binary(flipped, left, right)
+            }
+            expression => Expression::Not(Box::new(expression)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Expression,
+        Location,
+        Operator,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn expression_simplify() {
+        use Expression::*;
+
+        assert_eq!(
+            Memory(5),
+            Expression::binary(Operator::Multiply, Constant(1), Memory(5))
+        );
+
+        assert_eq!(
+            Expression::binary(Operator::Subtract, Slot(1), Constant(3)),
+            Expression::binary(Operator::Add, Constant(-3), Slot(1))
+        );
+
+        assert_eq!(
+            Negate(Box::new(Memory(7))),
+            Expression::binary(Operator::Multiply, Memory(7), Constant(-1))
+        );
+
+        let less = Expression::binary(Operator::Less, Memory(1), Constant(4));
+        assert_eq!(
+            Expression::binary(Operator::GreaterEqual, Memory(1), Constant(4)),
+            !less
+        );
+
+        assert_eq!(
+            Constant(7),
+            Expression::binary(Operator::Add, Memory(1), Constant(2))
+                .replace(Location::Memory(1), &Constant(5))
+        );
+    }
+}
@@ -0,0 +1,196 @@
+//! Decompiler from Intcode software to structured pseudo-code.
+//!
+//! Code is found by following every jump from address 0. Functions are found
+//! through the calling convention of the compiler, which the puzzle software
+//! shares: a call stores the return address at `rb+0` and jumps to a function
+//! that starts with `rb += size` and returns with `rb -= size` and a jump
+//! through `rb+0`. Values that only pass from one instruction to the next are
+//! folded into expressions, parameters the software overwrites become
+//! pointers and the blocks of each function are folded into `if`, `while`
+//! and `do`-`while`. The arcade cabinet of day 13 scores a block like this:
+//!
+//! ```text
+//! fn fn_393(arg1, arg2) {
+//!     fn_549(arg1, arg2, 0);
+//!     mem[386] = mem[386] + mem[fn_601(arg1, arg2)];
+//!     ...
+//! }
+//!
+//! fn fn_601(arg1, arg2) {
+//!     return fn_456(21 * arg1 + arg2, 373, 96, 735) + 1374;
+//! }
+//! ```
+
+pub mod cfg;
+pub mod expression;
+pub mod printer;
+pub mod structure;
+
+use cfg::Function;
+use printer::Printer;
+use structure::Node;
+
+/// Every function of some software with its structured code.
+#[derive(Debug, Clone)]
+pub struct Decompilation {
+    pub functions: Vec<(Function, Vec<Node>)>,
+}
+
+pub fn decompile(software: &[isize]) -> Decompilation {
+    Decompilation {
+        functions: cfg::analyze(software)
+            .into_iter()
+            .map(|function| {
+                let nodes = structure::structure(&function);
+                (function, nodes)
+            })
+            .collect(),
+    }
+}
+
+impl Decompilation {
+    /// The function with the given name, like `main` or `fn_601`.
+    pub fn function(&self, name: &str) -> Option<&(Function, Vec<Node>)> {
+        self.functions
+            .iter()
+            .find(|(function, _)| printer::name(function.entry) == name)
+    }
+}
+
+impl std::fmt::Display for Decompilation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, (function, nodes)) in self.functions.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+
+            Printer::new(function).write(f, nodes)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decompile;
+    use crate::{
+        day_05::part_1::INPUT_MEMORY as TEST_SOFTWARE,
+        day_09::part_1::BOOST_SOFTWARE,
+        day_13::arcade,
+        intcode::{
+            compiler::compile,
+            program::Program,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    const FACTORIAL: &str = "
+        fn factorial(n) {
+            if n < 2 {
+                return 1;
+            }
+
+            return n * factorial(n - 1);
+        }
+
+        fn main() {
+            let n = input();
+
+            while n > 0 {
+                output(factorial(n));
+                n = n - 1;
+            }
+        }
+    ";
+
+    #[test]
+    fn decompile_expressions() {
+        // mem[13] = input(); mem[14] = mem[13] * 3; mem[14] = mem[14] + 5;
+        // output(mem[14])
+        let software = vec![3, 13, 1002, 13, 3, 14, 1001, 14, 5, 14, 4, 14, 99, 0, 0];
+
+        assert_eq!(
+            "fn main() {\n    output(input() * 3 + 5);\n    halt;\n}\n",
+            decompile(&software).to_string()
+        );
+    }
+
+    #[test]
+    fn decompile_compiled() {
+        let software = compile(FACTORIAL).unwrap();
+
+        assert_eq!(
+            "fn main() {
+    rb += 116;
+    fn_68();
+    halt;
+}
+
+fn fn_10(arg1) {
+    if arg1 < 2 {
+        return 1;
+    }
+    return arg1 * fn_10(arg1 - 1);
+}
+
+fn fn_68() {
+    local2 = input();
+    while 0 < local2 {
+        output(fn_10(local2));
+        local2 = local2 - 1;
+    }
+    return 0;
+}
+",
+            decompile(&software).to_string()
+        );
+    }
+
+    #[test]
+    fn decompile_arcade_score() {
+        let software = Vec::from(arcade::software());
+        let decompiled = decompile(&software);
+        let text = decompiled.to_string();
+
+        // Breaking a block adds a value from a table behind the screen that
+        // is picked by a hash of the position of the block.
+        assert!(
+            text.contains("mem[386] = mem[386] + mem[fn_601(arg1, arg2)];"),
+            "{}",
+            text
+        );
+        assert!(
+            text.contains("return fn_456(21 * arg1 + arg2, 373, 96, 735) + 1374;"),
+            "{}",
+            text
+        );
+
+        let (modulo, _) = decompiled.function("fn_456").unwrap();
+        assert_eq!(4, modulo.arity);
+    }
+
+    #[test]
+    fn decompile_self_tests() {
+        let test = Vec::from(TEST_SOFTWARE.parse::<Program>().unwrap());
+
+        // TEST turns the value at 6 into an instruction by adding the input.
+        assert_eq!(
+            "fn main() {\n    mem[6] = input() + mem[6];\n    // no instruction at 6\n}\n",
+            decompile(&test).to_string()
+        );
+
+        let boost = Vec::from(BOOST_SOFTWARE.parse::<Program>().unwrap());
+
+        assert!(decompile(&boost).to_string().ends_with(
+            "fn fn_922(arg1) {
+    if arg1 >= 3 {
+        local2 = fn_922(arg1 - 1);
+        return fn_922(arg1 - 3) + local2;
+    }
+    return arg1;
+}
+"
+        ));
+    }
+}
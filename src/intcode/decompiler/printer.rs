@@ -0,0 +1,253 @@
+use super::{
+    cfg::{
+        Function,
+        Statement,
+    },
+    expression::{
+        Expression,
+        Location,
+        Operator,
+    },
+    structure::Node,
+};
+use std::fmt::{
+    Formatter,
+    Result,
+    Write,
+};
+
+const INDENT: &str = "    ";
+
+/// Writes the structured code of a function as pseudo-code. Memory is
+/// `mem[address]`, slots of the frame are `argN` and `localN`, values for
+/// the next call `outN` and the value a call returned `result`. Functions
+/// that move the relative base more than once, like software that doesn't
+/// follow the calling convention, use `rb[offset]` instead.
+pub struct Printer<'a> {
+    function: &'a Function,
+    raw_slots: bool,
+}
+
+impl<'a> Printer<'a> {
+    pub fn new(function: &'a Function) -> Self {
+        let moves = function
+            .blocks
+            .values()
+            .flat_map(|block| &block.statements)
+            .filter(|statement| matches!(statement, Statement::AdjustRelativeBase(_)))
+            .count();
+
+        Self {
+            function,
+            raw_slots: moves > 1,
+        }
+    }
+
+    pub fn write(&self, f: &mut Formatter<'_>, nodes: &[Node]) -> Result {
+        let parameters = (1..=self.function.arity)
+            .map(|slot| format!("arg{}", slot))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        writeln!(f, "fn {}({}) {{", name(self.function.entry), parameters)?;
+        self.nodes(f, nodes, 1)?;
+        writeln!(f, "}}")
+    }
+
+    fn nodes(&self, f: &mut Formatter<'_>, nodes: &[Node], depth: usize) -> Result {
+        for node in nodes {
+            self.node(f, node, depth)?;
+        }
+
+        Ok(())
+    }
+
+    fn node(&self, f: &mut Formatter<'_>, node: &Node, depth: usize) -> Result {
+        let indent = INDENT.repeat(depth);
+
+        match node {
+            Node::Statement(statement) => {
+                writeln!(f, "{}{};", indent, self.statement(statement))
+            }
+            Node::If(condition, then, otherwise) => {
+                writeln!(f, "{}if {} {{", indent, self.expression(condition))?;
+                self.nodes(f, then, depth + 1)?;
+                self.otherwise(f, otherwise, depth)
+            }
+            Node::Loop(body) => {
+                writeln!(f, "{}loop {{", indent)?;
+                self.nodes(f, body, depth + 1)?;
+                writeln!(f, "{}}}", indent)
+            }
+            Node::While(condition, body) => {
+                writeln!(f, "{}while {} {{", indent, self.expression(condition))?;
+                self.nodes(f, body, depth + 1)?;
+                writeln!(f, "{}}}", indent)
+            }
+            Node::DoWhile(body, condition) => {
+                writeln!(f, "{}do {{", indent)?;
+                self.nodes(f, body, depth + 1)?;
+                writeln!(f, "{}}} while {};", indent, self.expression(condition))
+            }
+            Node::Break => writeln!(f, "{}break;", indent),
+            Node::Continue => writeln!(f, "{}continue;", indent),
+            Node::Goto(target) => writeln!(f, "{}goto label_{};", indent, target),
+            Node::Label(address) => {
+                writeln!(f, "{}label_{}:", INDENT.repeat(depth - 1), address)
+            }
+            Node::Return(None) => writeln!(f, "{}return;", indent),
+            Node::Return(Some(value)) => {
+                writeln!(f, "{}return {};", indent, self.expression(value))
+            }
+            Node::Halt => writeln!(f, "{}halt;", indent),
+            Node::IndirectJump(target) => {
+                writeln!(f, "{}goto *{};", indent, self.expression(target))
+            }
+            Node::End(address) => writeln!(f, "{}// no instruction at {}", indent, address),
+        }
+    }
+
+    /// Writes the `else` of an `if` and chains a lone `if` as `else if`.
+    fn otherwise(&self, f: &mut Formatter<'_>, otherwise: &[Node], depth: usize) -> Result {
+        let indent = INDENT.repeat(depth);
+
+        match otherwise {
+            [] => writeln!(f, "{}}}", indent),
+            [Node::If(condition, then, otherwise)] => {
+                writeln!(f, "{}}} else if {} {{", indent, self.expression(condition))?;
+                self.nodes(f, then, depth + 1)?;
+                self.otherwise(f, otherwise, depth)
+            }
+            otherwise => {
+                writeln!(f, "{}}} else {{", indent)?;
+                self.nodes(f, otherwise, depth + 1)?;
+                writeln!(f, "{}}}", indent)
+            }
+        }
+    }
+
+    fn statement(&self, statement: &Statement) -> String {
+        match statement {
+            Statement::Assign(location, value) => {
+                format!("{} = {}", self.location(*location), self.expression(value))
+            }
+            Statement::Store(address, value) => format!(
+                "mem[{}] = {}",
+                self.expression(address),
+                self.expression(value)
+            ),
+            Statement::Output(value) => format!("output({})", self.expression(value)),
+            Statement::AdjustRelativeBase(value) => format!("rb += {}", self.expression(value)),
+            Statement::Call(target, arguments) => self.call(*target, arguments),
+        }
+    }
+
+    fn location(&self, location: Location) -> String {
+        match location {
+            Location::Memory(address) => format!("mem[{}]", address),
+            Location::Slot(offset) if offset >= 1 && !self.raw_slots => format!("out{}", offset),
+            Location::Slot(offset) => self.slot(offset),
+        }
+    }
+
+    fn slot(&self, offset: isize) -> String {
+        if self.raw_slots {
+            return format!("rb[{}]", offset);
+        }
+
+        match offset {
+            1 => return "result".to_string(),
+            offset if offset > 1 => return format!("out{}", offset),
+            _ => {}
+        }
+
+        let slot = offset + self.function.frame_size;
+
+        if slot == 0 {
+            "return_address".to_string()
+        } else if slot >= 1 && slot as usize <= self.function.arity {
+            format!("arg{}", slot)
+        } else {
+            format!("local{}", slot)
+        }
+    }
+
+    fn call(&self, target: usize, arguments: &[Expression]) -> String {
+        let arguments = arguments
+            .iter()
+            .map(|argument| self.expression(argument))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("{}({})", name(target), arguments)
+    }
+
+    fn expression(&self, expression: &Expression) -> String {
+        let mut out = String::new();
+        self.write_expression(&mut out, expression, 0);
+        out
+    }
+
+    /// Writes the expression with parentheses if it binds weaker than
+    /// `precedence`.
+    fn write_expression(&self, out: &mut String, expression: &Expression, precedence: u8) {
+        match expression {
+            Expression::Constant(value) => {
+                let _ = write!(out, "{}", value);
+            }
+            Expression::Memory(address) => {
+                let _ = write!(out, "mem[{}]", address);
+            }
+            Expression::Slot(offset) => out.push_str(&self.slot(*offset)),
+            Expression::Input => out.push_str("input()"),
+            Expression::Dereference(address) => {
+                out.push_str("mem[");
+                self.write_expression(out, address, 0);
+                out.push(']');
+            }
+            Expression::Call(target, arguments) => out.push_str(&self.call(*target, arguments)),
+            Expression::Negate(operand) => {
+                out.push('-');
+                self.write_expression(out, operand, 4);
+            }
+            Expression::Not(operand) => {
+                out.push('!');
+                self.write_expression(out, operand, 4);
+            }
+            Expression::Binary(operator, left, right) => {
+                let (symbol, own) = match operator {
+                    Operator::Less => ("<", 1),
+                    Operator::GreaterEqual => (">=", 1),
+                    Operator::Equal => ("==", 1),
+                    Operator::NotEqual => ("!=", 1),
+                    Operator::Add => ("+", 2),
+                    Operator::Subtract => ("-", 2),
+                    Operator::Multiply => ("*", 3),
+                };
+
+                if own < precedence {
+                    out.push('(');
+                }
+
+                self.write_expression(out, left, own);
+                let _ = write!(out, " {} ", symbol);
+                // Only `+` and `*` may leave out parentheses on the right.
+                let associative = matches!(operator, Operator::Add | Operator::Multiply);
+                self.write_expression(out, right, if associative { own } else { own + 1 });
+
+                if own < precedence {
+                    out.push(')');
+                }
+            }
+        }
+    }
+}
+
+/// Name of the function at the address.
+pub fn name(entry: usize) -> String {
+    if entry == 0 {
+        "main".to_string()
+    } else {
+        format!("fn_{}", entry)
+    }
+}
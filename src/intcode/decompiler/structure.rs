@@ -0,0 +1,275 @@
+use super::{
+    cfg::{
+        Block,
+        Function,
+        Statement,
+        Terminator,
+    },
+    expression::Expression,
+};
+use std::collections::{
+    BTreeMap,
+    BTreeSet,
+};
+
+/// Structured code of a function.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Node {
+    Statement(Statement),
+    If(Expression, Vec<Node>, Vec<Node>),
+    /// Loop that only ends with a `break` or `return`.
+    Loop(Vec<Node>),
+    While(Expression, Vec<Node>),
+    DoWhile(Vec<Node>, Expression),
+    Break,
+    Continue,
+    Goto(usize),
+    Label(usize),
+    Return(Option<Expression>),
+    Halt,
+    IndirectJump(Expression),
+    /// The value at the address is no instruction.
+    End(usize),
+}
+
+/// Innermost loop around the code being structured.
+#[derive(Debug, Default, Copy, Clone)]
+struct Context {
+    header: Option<usize>,
+    exit: Option<usize>,
+}
+
+struct Structurer<'a> {
+    blocks: &'a BTreeMap<usize, Block>,
+    /// Blocks whose terminator is part of an enclosing `if` or loop.
+    suppressed: BTreeSet<usize>,
+    labels: BTreeSet<usize>,
+    gotos: BTreeSet<usize>,
+}
+
+/// Folds the blocks of a function into `if`, `while` and `do`-`while` by the
+/// order of their addresses. Jumps that don't fit are kept as `goto`.
+pub fn structure(function: &Function) -> Vec<Node> {
+    let mut structurer = Structurer {
+        blocks: &function.blocks,
+        suppressed: BTreeSet::new(),
+        labels: BTreeSet::new(),
+        gotos: BTreeSet::new(),
+    };
+
+    let start = function
+        .blocks
+        .keys()
+        .next()
+        .copied()
+        .unwrap_or(function.entry);
+    let end = function
+        .blocks
+        .values()
+        .map(|block| block.end)
+        .max()
+        .unwrap_or(start);
+
+    if start != function.entry {
+        structurer.gotos.insert(function.entry);
+    }
+
+    let mut nodes = structurer.range(start, end, Context::default(), false);
+
+    // Gotos are only known once everything is structured, so a second pass
+    // places their labels.
+    if !structurer.gotos.is_empty() {
+        structurer.labels = std::mem::take(&mut structurer.gotos);
+        nodes = structurer.range(start, end, Context::default(), false);
+    }
+
+    if start != function.entry {
+        nodes.insert(0, Node::Goto(function.entry));
+    }
+
+    nodes
+}
+
+impl Structurer<'_> {
+    /// Structures the blocks in `start..end`. `in_loop` is set for the body
+    /// of a loop that starts at `start`.
+    fn range(&mut self, start: usize, end: usize, context: Context, in_loop: bool) -> Vec<Node> {
+        let mut nodes = Vec::new();
+        let mut address = start;
+        let mut in_loop = in_loop;
+
+        while let Some(block) = self
+            .blocks
+            .range(address..end)
+            .next()
+            .map(|(_, block)| block)
+        {
+            if self.labels.contains(&block.start) && !in_loop {
+                nodes.push(Node::Label(block.start));
+            }
+
+            let latch = if in_loop {
+                None
+            } else {
+                self.latch(block.start, end)
+            };
+            in_loop = false;
+
+            if let Some(latch) = latch {
+                address = self.r#loop(block.start, latch, &mut nodes);
+                continue;
+            }
+
+            nodes.extend(block.statements.iter().cloned().map(Node::Statement));
+
+            address = block.end;
+
+            if self.suppressed.contains(&block.start) {
+                continue;
+            }
+
+            match &block.terminator {
+                Terminator::Fallthrough => {}
+                Terminator::End => nodes.push(Node::End(block.end)),
+                Terminator::Goto(target) if *target == block.end => {}
+                Terminator::Goto(target) => nodes.push(self.jump(*target, context)),
+                Terminator::Branch(condition, target) => {
+                    if Some(*target) == context.header || Some(*target) == context.exit {
+                        let jump = self.jump(*target, context);
+                        nodes.push(Node::If(condition.clone(), vec![jump], Vec::new()));
+                    } else if *target >= block.end && *target <= end {
+                        address = self.r#if(block, condition, *target, end, context, &mut nodes);
+                    } else {
+                        let jump = self.jump(*target, context);
+                        nodes.push(Node::If(condition.clone(), vec![jump], Vec::new()));
+                    }
+                }
+                Terminator::Return(value) => nodes.push(Node::Return(value.clone())),
+                Terminator::Halt => nodes.push(Node::Halt),
+                Terminator::IndirectJump(target) => nodes.push(Node::IndirectJump(target.clone())),
+            }
+        }
+
+        nodes
+    }
+
+    /// The last block in `header..end` that jumps back to the header.
+    fn latch(&self, header: usize, end: usize) -> Option<usize> {
+        self.blocks
+            .range(header..end)
+            .filter(|(_, block)| block.terminator.target() == Some(header))
+            .map(|(start, _)| *start)
+            .next_back()
+    }
+
+    /// Structures the loop from `header` to the end of `latch` and returns
+    /// the address behind it.
+    fn r#loop(&mut self, header: usize, latch: usize, nodes: &mut Vec<Node>) -> usize {
+        let blocks = self.blocks;
+        let latch = &blocks[&latch];
+        let exit = latch.end;
+        let context = Context {
+            header: Some(header),
+            exit: Some(exit),
+        };
+
+        match &latch.terminator {
+            Terminator::Branch(condition, _) => {
+                self.suppressed.insert(latch.start);
+                let body = self.range(header, exit, context, true);
+                self.suppressed.remove(&latch.start);
+
+                nodes.push(Node::DoWhile(body, condition.clone()));
+            }
+            _ => {
+                let mut body = self.range(header, exit, context, true);
+
+                if body.last() == Some(&Node::Continue) {
+                    body.pop();
+                }
+
+                nodes.push(match body.first() {
+                    Some(Node::If(condition, then, otherwise))
+                        if then == &[Node::Break] && otherwise.is_empty() =>
+                    {
+                        let condition = !condition.clone();
+                        body.remove(0);
+
+                        Node::While(condition, body)
+                    }
+                    _ => Node::Loop(body),
+                });
+            }
+        }
+
+        exit
+    }
+
+    /// Structures a forward branch of `block` to `target` as an `if` with an
+    /// `else` if the code skipped by the branch ends with a jump past
+    /// `target`, and returns the address behind it.
+    fn r#if(
+        &mut self,
+        block: &Block,
+        condition: &Expression,
+        target: usize,
+        end: usize,
+        context: Context,
+        nodes: &mut Vec<Node>,
+    ) -> usize {
+        let blocks = self.blocks;
+        let join = blocks
+            .range(block.end..target)
+            .next_back()
+            .and_then(|(start, last)| match last.terminator {
+                Terminator::Goto(join)
+                    if join > target
+                        && join <= end
+                        && Some(join) != context.exit
+                        && Some(join) != context.header =>
+                {
+                    Some((*start, join))
+                }
+                _ => None,
+            });
+
+        let (then, otherwise, next) = match join {
+            Some((last, join)) => {
+                self.suppressed.insert(last);
+                let then = self.range(block.end, target, context, false);
+                self.suppressed.remove(&last);
+
+                let otherwise = self.range(target, join, context, false);
+
+                (then, otherwise, join)
+            }
+            None => (
+                self.range(block.end, target, context, false),
+                Vec::new(),
+                target,
+            ),
+        };
+
+        if !then.is_empty() || !otherwise.is_empty() || condition.has_side_effects() {
+            let condition = !condition.clone();
+
+            nodes.push(match (then.is_empty(), otherwise.is_empty()) {
+                (true, false) => Node::If(!condition, otherwise, then),
+                _ => Node::If(condition, then, otherwise),
+            });
+        }
+
+        next
+    }
+
+    fn jump(&mut self, target: usize, context: Context) -> Node {
+        if Some(target) == context.header {
+            Node::Continue
+        } else if Some(target) == context.exit {
+            Node::Break
+        } else {
+            self.gotos.insert(target);
+            Node::Goto(target)
+        }
+    }
+}
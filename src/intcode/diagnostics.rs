@@ -0,0 +1,534 @@
+//! Health check of a computer with the self-test programs of the puzzles.
+//!
+//! The TEST diagnostic program from day 5 outputs 0 for every check that
+//! passed and a diagnostic code at the end. The BOOST program from day 9
+//! outputs its keycode if the computer works and otherwise the opcodes, with
+//! parameter modes, that it found to be faulty.
+//!
+//! The programs are the puzzle inputs of both days and given by the caller.
+
+use super::{
+    computer::{
+        Computer,
+        ComputerError,
+        Intcode,
+        ParameterMode,
+        Reader,
+    },
+    crash::Trace,
+    program::Program,
+};
+
+/// System IDs the TEST program is run with. 1 checks the air conditioner
+/// unit and 5 the thermal radiator controller that also uses jumps and
+/// comparisons.
+pub const TEST_SYSTEM_IDS: &[isize] = &[1, 5];
+/// Puts BOOST into test mode.
+pub const BOOST_TEST_MODE: isize = 1;
+
+/// Instructions of TEST kept to find the instruction every check exercises.
+const TEST_HISTORY: usize = 1000;
+
+/// Largest value BOOST could report as a faulty opcode.
+const MAX_OPCODE: isize = 22299;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SelfTest {
+    Test,
+    Boost,
+}
+
+#[derive(Debug)]
+pub struct Report {
+    pub self_test: SelfTest,
+    pub system_id: isize,
+    pub outcome: Outcome,
+}
+
+#[derive(Debug)]
+pub enum Outcome {
+    /// The diagnostic code of TEST or the keycode of BOOST.
+    Passed(isize),
+    Failed(Vec<Fault>),
+    Crashed(ComputerError),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Fault {
+    /// A check of TEST output `value` instead of 0. `check` counts from 0
+    /// and exercises `intcode` if the run showed which instruction computed
+    /// the value.
+    Check {
+        check: usize,
+        value: isize,
+        intcode: Option<Intcode>,
+    },
+    /// BOOST found the instruction with these parameter modes to be faulty.
+    Opcode { opcode: isize, intcode: Intcode },
+    /// The software halted without writing a result.
+    MissingOutput,
+}
+
+/// Reports of all self-tests.
+#[derive(Debug)]
+pub struct Diagnostics {
+    pub reports: Vec<Report>,
+}
+
+impl Diagnostics {
+    /// Runs every self-test on a fresh computer from `computer` which sets up
+    /// the backend to check. The software and input are replaced by the
+    /// `test` and `boost` programs.
+    pub fn run<F: Fn() -> Computer>(computer: F, test: &Program, boost: &Program) -> Self {
+        let mut reports = TEST_SYSTEM_IDS
+            .iter()
+            .map(|system_id| run_test(computer(), test, *system_id))
+            .collect::<Vec<_>>();
+
+        reports.push(run_boost(computer(), boost));
+
+        Self { reports }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.reports.iter().all(Report::passed)
+    }
+}
+
+impl Report {
+    pub fn passed(&self) -> bool {
+        matches!(self.outcome, Outcome::Passed(_))
+    }
+
+    /// The diagnostic code of TEST or the keycode of BOOST if it passed.
+    pub fn code(&self) -> Option<isize> {
+        match self.outcome {
+            Outcome::Passed(code) => Some(code),
+            _ => None,
+        }
+    }
+}
+
+/// Runs the TEST diagnostic program with the given system ID.
+pub fn run_test(computer: Computer, software: &Program, system_id: isize) -> Report {
+    let computer = computer.with_history(TEST_HISTORY);
+
+    let outcome = match run(computer, software, system_id) {
+        Ok((outputs, traces)) => interpret_test(&outputs, &traces),
+        Err(err) => Outcome::Crashed(err),
+    };
+
+    Report {
+        self_test: SelfTest::Test,
+        system_id,
+        outcome,
+    }
+}
+
+/// Runs BOOST in test mode.
+pub fn run_boost(computer: Computer, software: &Program) -> Report {
+    let outcome = match run(computer, software, BOOST_TEST_MODE) {
+        Ok((outputs, _)) => interpret_boost(&outputs),
+        Err(err) => Outcome::Crashed(err),
+    };
+
+    Report {
+        self_test: SelfTest::Boost,
+        system_id: BOOST_TEST_MODE,
+        outcome,
+    }
+}
+
+/// Every output but the last is a check that passed with 0, the last is the
+/// diagnostic code. `traces` are the instructions of the run, which show what
+/// every check exercises if none are missing.
+pub fn interpret_test(outputs: &[isize], traces: &[Trace]) -> Outcome {
+    let (code, checks) = match outputs.split_last() {
+        Some(split) => split,
+        None => return Outcome::Failed(vec![Fault::MissingOutput]),
+    };
+
+    let mut checked = checked_instructions(traces);
+
+    if checked.len() != outputs.len() {
+        checked = Vec::new();
+    }
+
+    let faults = checks
+        .iter()
+        .enumerate()
+        .filter(|(_, value)| **value != 0)
+        .map(|(check, value)| Fault::Check {
+            check,
+            value: *value,
+            intcode: checked.get(check).cloned().flatten(),
+        })
+        .collect::<Vec<_>>();
+
+    if faults.is_empty() {
+        Outcome::Passed(*code)
+    } else {
+        Outcome::Failed(faults)
+    }
+}
+
+/// A single output that can't be an opcode is the keycode, anything else is a
+/// list of faulty opcodes that ends with a 0.
+pub fn interpret_boost(outputs: &[isize]) -> Outcome {
+    match outputs {
+        [] => Outcome::Failed(vec![Fault::MissingOutput]),
+        [keycode] if !is_opcode(*keycode) => Outcome::Passed(*keycode),
+        opcodes => Outcome::Failed(
+            opcodes
+                .iter()
+                .filter(|opcode| **opcode != 0)
+                .map(|opcode| Fault::Opcode {
+                    opcode: *opcode,
+                    intcode: if is_opcode(*opcode) {
+                        Intcode::from(opcode)
+                    } else {
+                        Intcode::Skip
+                    },
+                })
+                .collect(),
+        ),
+    }
+}
+
+/// The instruction that computed the value of every `Write` in `traces`.
+pub fn checked_instructions(traces: &[Trace]) -> Vec<Option<Intcode>> {
+    traces
+        .iter()
+        .enumerate()
+        .filter(|(_, trace)| matches!(trace.intcode, Intcode::Write(_)))
+        .map(|(index, write)| computed_by(&traces[..index], write))
+        .collect()
+}
+
+/// Walks back from `write` through the instructions that wrote its operand.
+/// Instructions that only adjust the value in place, like subtracting the
+/// expected result, lead to the one that computed it from other values. The
+/// instruction is decoded from its raw value, so it is the one the software
+/// meant to run even if the computer decoded it as another.
+fn computed_by(before: &[Trace], write: &Trace) -> Option<Intcode> {
+    let operand = write.parameters.first()?;
+
+    if operand.mode == ParameterMode::Immediate {
+        return Intcode::decode(write.raw).ok();
+    }
+
+    let address = operand.address;
+
+    for trace in before.iter().rev() {
+        let (sources, target) = match (&trace.intcode, trace.parameters.as_slice()) {
+            (
+                Intcode::Add(..) | Intcode::Mul(..) | Intcode::LessThan(..) | Intcode::Equals(..),
+                [first, second, target],
+            ) => (vec![first, second], target),
+            (Intcode::Read(_), [target]) => (Vec::new(), target),
+            _ => continue,
+        };
+
+        if target.address != address {
+            continue;
+        }
+
+        let in_place = sources
+            .iter()
+            .any(|source| source.mode != ParameterMode::Immediate && source.address == address);
+
+        if !in_place {
+            return Intcode::decode(trace.raw).ok();
+        }
+    }
+
+    None
+}
+
+fn is_opcode(value: isize) -> bool {
+    (1..=MAX_OPCODE).contains(&value)
+        && (value / 100)
+            .to_string()
+            .chars()
+            .all(|digit| ('0'..='2').contains(&digit))
+}
+
+/// The outputs and the instructions the computer kept in its history.
+fn run(
+    computer: Computer,
+    software: &Program,
+    input: isize,
+) -> Result<(Vec<isize>, Vec<Trace>), ComputerError> {
+    let mut computer = computer
+        .with_software(software.to_vec())
+        .with_input(Reader::Tester(Box::new(vec![input].into_iter())));
+
+    let outputs = computer.run()?.outputs;
+    let traces = computer
+        .history
+        .map(|history| history.traces().cloned().collect())
+        .unwrap_or_default();
+
+    Ok((outputs, traces))
+}
+
+impl std::fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for report in &self.reports {
+            writeln!(f, "{}", report)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self.self_test {
+            SelfTest::Test => "TEST",
+            SelfTest::Boost => "BOOST",
+        };
+
+        write!(f, "{} ({}): ", name, self.system_id)?;
+
+        match &self.outcome {
+            Outcome::Passed(code) => write!(f, "passed with code {}", code),
+            Outcome::Crashed(err) => write!(f, "crashed: {:?}", err),
+            Outcome::Failed(faults) => {
+                write!(f, "failed")?;
+
+                for fault in faults {
+                    write!(f, "\n  {}", fault)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Fault {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Fault::Check {
+                check,
+                value,
+                intcode: Some(intcode),
+            } => write!(
+                f,
+                "check {} reported {}: {} is faulty",
+                check,
+                value,
+                describe(intcode)
+            ),
+            Fault::Check { check, value, .. } => {
+                write!(f, "check {} reported {}", check, value)
+            }
+            Fault::Opcode {
+                opcode,
+                intcode: Intcode::Skip,
+            } => write!(f, "reported unknown opcode {}", opcode),
+            Fault::Opcode { opcode, intcode } => {
+                write!(f, "opcode {} is faulty: {}", opcode, describe(intcode))
+            }
+            Fault::MissingOutput => write!(f, "no output"),
+        }
+    }
+}
+
+/// Name and parameter modes of an instruction like
+/// `READ with modes [relative]`.
+fn describe(intcode: &Intcode) -> String {
+    let modes = intcode
+        .modes()
+        .iter()
+        .map(|mode| format!("{:?}", mode).to_lowercase())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let name = intcode.to_string();
+    let name = name.split('\t').next().unwrap_or_default();
+
+    format!("{} with modes [{}]", name, modes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        interpret_boost, interpret_test, run_boost, run_test, Diagnostics, Fault, Outcome,
+    };
+    use crate::intcode::{
+        computer::{
+            Computer,
+            ComputerError,
+            Intcode,
+            Parameter,
+            ParameterMode,
+        },
+        instruction_set::{
+            Flow,
+            InstructionSet,
+            Standard,
+        },
+        program::Program,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn test_software() -> Program {
+        include_str!("../../inputs/day_05.txt").parse().unwrap()
+    }
+
+    fn boost_software() -> Program {
+        include_str!("../../inputs/day_09.txt").parse().unwrap()
+    }
+
+    /// Backend that decodes one value as another instruction.
+    struct Faulty {
+        value: isize,
+        intcode: Intcode,
+    }
+
+    impl InstructionSet for Faulty {
+        fn decode(&self, value: isize) -> Result<Intcode, ComputerError> {
+            if value == self.value {
+                Ok(self.intcode.clone())
+            } else {
+                Standard.decode(value)
+            }
+        }
+
+        fn execute(
+            &self,
+            opcode: usize,
+            computer: &mut Computer,
+            parameters: &[Parameter],
+        ) -> Result<Flow, ComputerError> {
+            Standard.execute(opcode, computer, parameters)
+        }
+    }
+
+    fn faulty(value: isize, intcode: Intcode) -> impl Fn() -> Computer {
+        move || {
+            Computer::default().with_instruction_set(Faulty {
+                value,
+                intcode: intcode.clone(),
+            })
+        }
+    }
+
+    #[test]
+    fn diagnostics_standard() {
+        let diagnostics = Diagnostics::run(Computer::default, &test_software(), &boost_software());
+
+        assert!(diagnostics.passed(), "{}", diagnostics);
+        assert_eq!(3, diagnostics.reports.len());
+    }
+
+    #[test]
+    fn diagnostics_interpret_test() {
+        // Without the instructions of the run the check can't be blamed.
+        match interpret_test(&[0, 0, 3, 0, 42], &[]) {
+            Outcome::Failed(faults) => {
+                assert_eq!(
+                    vec![Fault::Check {
+                        check: 2,
+                        value: 3,
+                        intcode: None,
+                    }],
+                    faults
+                )
+            }
+            got => panic!("unexpected outcome: {:?}", got),
+        }
+
+        match interpret_test(&[0, 0, 42], &[]) {
+            Outcome::Passed(42) => {}
+            got => panic!("unexpected outcome: {:?}", got),
+        }
+    }
+
+    #[test]
+    fn diagnostics_interpret_boost() {
+        match interpret_boost(&[203, 77, 0]) {
+            Outcome::Failed(faults) => assert_eq!(
+                vec![
+                    Fault::Opcode {
+                        opcode: 203,
+                        intcode: Intcode::Read(ParameterMode::Relative),
+                    },
+                    Fault::Opcode {
+                        opcode: 77,
+                        intcode: Intcode::Skip,
+                    },
+                ],
+                faults
+            ),
+            got => panic!("unexpected outcome: {:?}", got),
+        }
+
+        assert_eq!(
+            "opcode 203 is faulty: READ with modes [relative]",
+            Fault::Opcode {
+                opcode: 203,
+                intcode: Intcode::Read(ParameterMode::Relative),
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn diagnostics_faulty_backends() {
+        use ParameterMode::*;
+
+        // Adds with immediate parameters multiply instead.
+        let report = run_test(
+            faulty(1101, Intcode::Mul(Immediate, Immediate, Position))(),
+            &test_software(),
+            1,
+        );
+
+        match report.outcome {
+            Outcome::Failed(faults) => assert_eq!(
+                vec![Fault::Check {
+                    check: 7,
+                    value: 1187,
+                    intcode: Some(Intcode::Add(Immediate, Immediate, Position)),
+                }],
+                faults
+            ),
+            got => panic!("unexpected outcome: {:?}", got),
+        }
+
+        // Multiplications with position parameters add instead.
+        let report = run_test(
+            faulty(2, Intcode::Add(Position, Position, Position))(),
+            &test_software(),
+            1,
+        );
+
+        match &report.outcome {
+            Outcome::Failed(faults) => assert_eq!(
+                vec!["check 6 reported -1109: MUL with modes [position, position, position] is faulty"],
+                faults
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+            ),
+            got => panic!("unexpected outcome: {:?}", got),
+        }
+
+        // Reads with relative parameters write to an absolute address.
+        let report = run_boost(faulty(203, Intcode::Read(Position))(), &boost_software());
+
+        match report.outcome {
+            Outcome::Failed(faults) => assert_eq!(
+                vec![Fault::Opcode {
+                    opcode: 203,
+                    intcode: Intcode::Read(Relative),
+                }],
+                faults
+            ),
+            got => panic!("unexpected outcome: {:?}", got),
+        }
+    }
+}
@@ -0,0 +1,532 @@
+//! GDB remote serial protocol stub for Intcode software.
+//!
+//! Register 0 is `pc` and register 1 is the relative base, both sent as 64 bit
+//! little endian values and described to GDB by `target.xml`. Memory is
+//! addressed by byte like GDB expects: cell `n` holds the bytes `8n` to
+//! `8n + 7` of its 64 bit little endian value, so `m 50,8` reads cell 10.
+//! `pc` and software breakpoints are byte addresses of instructions too.
+
+use super::{
+    computer::Computer,
+    debugger::{
+        Debugger,
+        Stop,
+    },
+    report::HaltReason,
+};
+use log::debug;
+use std::{
+    io::{
+        BufRead,
+        BufReader,
+        Write,
+    },
+    net::{
+        TcpListener,
+        ToSocketAddrs,
+    },
+};
+
+const CELL_SIZE: usize = std::mem::size_of::<isize>();
+/// Largest packet the stub accepts and sends. Replies to memory reads are
+/// cut to fit.
+const PACKET_SIZE: usize = 0x4000;
+/// Description of the registers.
+const TARGET_XML: &str = r#"<?xml version="1.0"?>
+<!DOCTYPE target SYSTEM "gdb-target.dtd">
+<target version="1.0">
+  <feature name="org.gnu.gdb.intcode.core">
+    <reg name="pc" bitsize="64" type="code_ptr" regnum="0"/>
+    <reg name="rb" bitsize="64" type="int64" regnum="1"/>
+  </feature>
+</target>
+"#;
+/// Signal that is reported when the computer stopped after a step or at a
+/// breakpoint.
+const SIGTRAP: u8 = 5;
+/// Signal that is reported when the computer failed.
+const SIGSEGV: u8 = 11;
+
+#[derive(Debug)]
+pub enum GdbError {
+    Io(std::io::Error),
+}
+
+impl From<std::io::Error> for GdbError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Waits for a single client on `address` and lets it debug the computer.
+pub fn serve_tcp<A: ToSocketAddrs>(address: A, computer: Computer) -> Result<(), GdbError> {
+    serve_listener(TcpListener::bind(address)?, computer)
+}
+
+pub fn serve_listener(listener: TcpListener, computer: Computer) -> Result<(), GdbError> {
+    let (stream, peer) = listener.accept()?;
+    debug!("gdb client connected from {}", peer);
+
+    Stub::new(stream.try_clone()?, computer).serve(BufReader::new(stream))
+}
+
+/// Reads the next packet and acknowledges it. Returns `None` at the end of
+/// the stream. Packets with a wrong checksum are rejected and skipped.
+pub fn read_packet<R: BufRead, W: Write>(
+    reader: &mut R,
+    writer: &mut W,
+    acknowledge: bool,
+) -> Result<Option<String>, GdbError> {
+    loop {
+        let mut skipped = Vec::new();
+
+        // Acknowledgements and interrupts while the computer is stopped are
+        // ignored.
+        if reader.read_until(b'$', &mut skipped)? == 0 || skipped.last() != Some(&b'$') {
+            return Ok(None);
+        }
+
+        let mut data = Vec::new();
+        reader.read_until(b'#', &mut data)?;
+
+        if data.pop() != Some(b'#') {
+            return Ok(None);
+        }
+
+        let mut checksum = [0; 2];
+        reader.read_exact(&mut checksum)?;
+
+        let valid = std::str::from_utf8(&checksum)
+            .ok()
+            .and_then(|checksum| u8::from_str_radix(checksum, 16).ok())
+            == Some(self::checksum(&data));
+
+        if acknowledge {
+            writer.write_all(if valid { b"+" } else { b"-" })?;
+            writer.flush()?;
+        }
+
+        if valid {
+            return Ok(Some(String::from_utf8_lossy(&data).into_owned()));
+        }
+    }
+}
+
+pub fn write_packet<W: Write>(writer: &mut W, data: &str) -> Result<(), GdbError> {
+    write!(writer, "${}#{:02x}", data, checksum(data.as_bytes()))?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+fn checksum(data: &[u8]) -> u8 {
+    data.iter().fold(0, |sum, byte| sum.wrapping_add(*byte))
+}
+
+pub fn encode_value(value: isize) -> String {
+    value
+        .to_le_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+pub fn decode_value(hex: &str) -> Option<isize> {
+    if hex.len() != 2 * CELL_SIZE {
+        return None;
+    }
+
+    let mut bytes = [0; CELL_SIZE];
+
+    for (index, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(2 * index..2 * index + 2)?, 16).ok()?;
+    }
+
+    Some(isize::from_le_bytes(bytes))
+}
+
+pub struct Stub<W: Write> {
+    writer: W,
+    debugger: Debugger,
+    acknowledge: bool,
+}
+
+impl<W: Write> Stub<W> {
+    pub fn new(writer: W, computer: Computer) -> Self {
+        Self {
+            writer,
+            debugger: Debugger::new(computer),
+            acknowledge: true,
+        }
+    }
+
+    /// Handles packets until the client kills the computer or detaches.
+    pub fn serve<R: BufRead>(mut self, mut reader: R) -> Result<(), GdbError> {
+        while let Some(packet) = read_packet(&mut reader, &mut self.writer, self.acknowledge)? {
+            debug!("gdb packet {}", packet);
+
+            let reply = match packet.as_bytes().first() {
+                Some(b'?') => Some(stop_reply(SIGTRAP)),
+                Some(b'g') => Some(format!(
+                    "{}{}",
+                    encode_value((self.debugger.computer.pc * CELL_SIZE) as isize),
+                    encode_value(self.debugger.computer.relative_base)
+                )),
+                Some(b'p') => self.read_register(&packet[1..]),
+                Some(b'P') => self.write_register(&packet[1..]),
+                Some(b'm') => self.read_memory(&packet[1..]),
+                Some(b'M') => self.write_memory(&packet[1..]),
+                Some(b'Z') => self.breakpoint(&packet[1..], true),
+                Some(b'z') => self.breakpoint(&packet[1..], false),
+                Some(b's') => Some(self.execute(Debugger::step)),
+                Some(b'c') => Some(self.execute(Debugger::resume)),
+                Some(b'H') => Some("OK".to_string()),
+                Some(b'k') => return Ok(()),
+                Some(b'D') => {
+                    write_packet(&mut self.writer, "OK")?;
+                    return Ok(());
+                }
+                _ => match packet.as_str() {
+                    "QStartNoAckMode" => {
+                        write_packet(&mut self.writer, "OK")?;
+                        self.acknowledge = false;
+                        continue;
+                    }
+                    "qAttached" => Some("1".to_string()),
+                    "qC" => Some("QC1".to_string()),
+                    "qfThreadInfo" => Some("m1".to_string()),
+                    "qsThreadInfo" => Some("l".to_string()),
+                    packet if packet.starts_with("qSupported") => Some(format!(
+                        "PacketSize={:x};QStartNoAckMode+;swbreak+;qXfer:features:read+",
+                        PACKET_SIZE
+                    )),
+                    packet if packet.starts_with("qXfer:features:read:") => {
+                        read_features(&packet["qXfer:features:read:".len()..])
+                    }
+                    _ => Some(String::new()),
+                },
+            };
+
+            write_packet(
+                &mut self.writer,
+                &reply.unwrap_or_else(|| "E01".to_string()),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn read_register(&self, arguments: &str) -> Option<String> {
+        let computer = &self.debugger.computer;
+
+        match usize::from_str_radix(arguments, 16).ok()? {
+            0 => Some(encode_value((computer.pc * CELL_SIZE) as isize)),
+            1 => Some(encode_value(computer.relative_base)),
+            _ => None,
+        }
+    }
+
+    fn write_register(&mut self, arguments: &str) -> Option<String> {
+        let (register, value) = arguments.split_once('=')?;
+        let value = decode_value(value)?;
+        let computer = &mut self.debugger.computer;
+
+        match usize::from_str_radix(register, 16).ok()? {
+            0 if value >= 0 && (value as usize).is_multiple_of(CELL_SIZE) => {
+                computer.pc = value as usize / CELL_SIZE
+            }
+            1 => computer.relative_base = value,
+            _ => return None,
+        }
+
+        Some("OK".to_string())
+    }
+
+    /// `addr,length` in bytes. Reads longer than fits into a packet return
+    /// fewer bytes.
+    fn read_memory(&self, arguments: &str) -> Option<String> {
+        let (address, length) = parse_range(arguments)?;
+        let end = address.checked_add(length.min(PACKET_SIZE / 2))?;
+        let memory = &self.debugger.computer.memory;
+
+        Some(
+            (address..end)
+                .map(|address| {
+                    let cell = memory
+                        .get(&(address / CELL_SIZE))
+                        .copied()
+                        .unwrap_or_default();
+
+                    format!("{:02x}", cell.to_le_bytes()[address % CELL_SIZE])
+                })
+                .collect(),
+        )
+    }
+
+    /// `addr,length:bytes` in bytes. Writes bypass code protection and the
+    /// memory sanitizer.
+    fn write_memory(&mut self, arguments: &str) -> Option<String> {
+        let (range, bytes) = arguments.split_once(':')?;
+        let (address, length) = parse_range(range)?;
+        address.checked_add(length)?;
+
+        if bytes.len() != length.checked_mul(2)? {
+            return None;
+        }
+
+        let bytes = bytes
+            .as_bytes()
+            .chunks(2)
+            .map(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok())
+            .collect::<Option<Vec<_>>>()?;
+
+        for (offset, byte) in bytes.into_iter().enumerate() {
+            let address = address + offset;
+            let cell = self
+                .debugger
+                .computer
+                .memory
+                .entry(address / CELL_SIZE)
+                .or_default();
+
+            let mut value = cell.to_le_bytes();
+            value[address % CELL_SIZE] = byte;
+            *cell = isize::from_le_bytes(value);
+        }
+
+        Some("OK".to_string())
+    }
+
+    /// `type,addr,kind` where only software breakpoints of type 0 are
+    /// supported.
+    fn breakpoint(&mut self, arguments: &str, insert: bool) -> Option<String> {
+        let mut fields = arguments.split(',');
+
+        if fields.next()? != "0" {
+            return Some(String::new());
+        }
+
+        let address = usize::from_str_radix(fields.next()?, 16).ok()?;
+
+        if !address.is_multiple_of(CELL_SIZE) {
+            return None;
+        }
+
+        let address = address / CELL_SIZE;
+
+        if insert {
+            self.debugger.breakpoints.insert(address);
+        } else {
+            self.debugger.breakpoints.remove(&address);
+        }
+
+        Some("OK".to_string())
+    }
+
+    fn execute<F: FnOnce(&mut Debugger) -> Stop>(&mut self, run: F) -> String {
+        match run(&mut self.debugger) {
+            Stop::Step | Stop::Breakpoint(_) => stop_reply(SIGTRAP),
+            Stop::Error(err) => {
+                debug!("computer failed: {:?}", err);
+                stop_reply(SIGSEGV)
+            }
+            Stop::Halt(HaltReason::Exit(code)) => format!("W{:02x}", code as u8),
+            Stop::Halt(HaltReason::Halt) | Stop::Finished => {
+                let code = self.debugger.computer.exit_code.unwrap_or_default();
+
+                format!("W{:02x}", code as u8)
+            }
+        }
+    }
+}
+
+fn stop_reply(signal: u8) -> String {
+    format!("S{:02x}", signal)
+}
+
+/// `annex:offset,length` of which only the annex `target.xml` exists.
+fn read_features(arguments: &str) -> Option<String> {
+    let (annex, range) = arguments.split_once(':')?;
+
+    if annex != "target.xml" {
+        return Some("E00".to_string());
+    }
+
+    let (offset, length) = parse_range(range)?;
+    let start = offset.min(TARGET_XML.len());
+    let end = start.saturating_add(length).min(TARGET_XML.len());
+    let last = if end == TARGET_XML.len() { 'l' } else { 'm' };
+
+    Some(format!("{}{}", last, &TARGET_XML[start..end]))
+}
+
+fn parse_range(arguments: &str) -> Option<(usize, usize)> {
+    let (address, length) = arguments.split_once(',')?;
+
+    Some((
+        usize::from_str_radix(address, 16).ok()?,
+        usize::from_str_radix(length, 16).ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        decode_value,
+        encode_value,
+        read_packet,
+        serve_listener,
+        write_packet,
+    };
+    use crate::intcode::computer::{
+        Computer,
+        Reader,
+        Recording,
+        Writer,
+    };
+    use pretty_assertions::assert_eq;
+    use std::{
+        io::{
+            BufReader,
+            Read,
+        },
+        net::{
+            TcpListener,
+            TcpStream,
+        },
+        thread::JoinHandle,
+    };
+
+    /// Reads a value, increments it and writes it.
+    const INCREMENT: &[isize] = &[3, 11, 1001, 11, 1, 11, 4, 11, 99, 0, 0, 0];
+
+    struct Client {
+        reader: BufReader<TcpStream>,
+        writer: TcpStream,
+        server: JoinHandle<()>,
+    }
+
+    impl Client {
+        /// The computer is built on the thread of the server as it can't be
+        /// sent to another thread.
+        fn connect<F: FnOnce() -> Computer + Send + 'static>(computer: F) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let address = listener.local_addr().unwrap();
+
+            let server = std::thread::spawn(move || serve_listener(listener, computer()).unwrap());
+            let stream = TcpStream::connect(address).unwrap();
+
+            Self {
+                reader: BufReader::new(stream.try_clone().unwrap()),
+                writer: stream,
+                server,
+            }
+        }
+
+        /// Sends a packet and returns the reply after checking that both
+        /// sides acknowledged.
+        fn send(&mut self, data: &str) -> String {
+            write_packet(&mut self.writer, data).unwrap();
+
+            let mut ack = [0];
+            self.reader.read_exact(&mut ack).unwrap();
+            assert_eq!(b'+', ack[0]);
+
+            read_packet(&mut self.reader, &mut self.writer, true)
+                .unwrap()
+                .unwrap()
+        }
+    }
+
+    #[test]
+    fn gdb_encode_value() {
+        assert_eq!("2a00000000000000", encode_value(42));
+        assert_eq!("ffffffffffffffff", encode_value(-1));
+        assert_eq!(Some(-1), decode_value("ffffffffffffffff"));
+        assert_eq!(Some(42), decode_value("2a00000000000000"));
+        assert_eq!(None, decode_value("2a"));
+    }
+
+    #[test]
+    fn gdb_session() {
+        let recording = Recording::default();
+
+        let output = recording.clone();
+
+        let mut client = Client::connect(move || {
+            Computer::default()
+                .with_software(INCREMENT.to_vec())
+                .with_input(Reader::Tester(Box::new(vec![41].into_iter())))
+                .with_output(Writer::Tester { values: Vec::new() }.record(&output))
+        });
+
+        assert!(client.send("qSupported:swbreak+").contains("swbreak+"));
+        assert_eq!("S05", client.send("?"));
+        assert_eq!("00000000000000000000000000000000", client.send("g"));
+
+        // The instruction at cell 6 starts at byte 0x30, cell 11 at 0x58.
+        assert_eq!("OK", client.send("Z0,30,1"));
+        assert_eq!("S05", client.send("c"));
+        assert_eq!("3000000000000000", client.send("p0"));
+        assert_eq!("2a00000000000000", client.send("m58,8"));
+        assert_eq!("002a00", client.send("m57,3"));
+
+        assert_eq!("OK", client.send("M58,8:6300000000000000"));
+        assert_eq!("OK", client.send("z0,30,1"));
+        assert_eq!("E01", client.send("Z0,31,1"));
+        assert_eq!("S05", client.send("s"));
+        assert_eq!("4000000000000000", client.send("p0"));
+        assert_eq!(vec![99], recording.values());
+
+        assert_eq!("OK", client.send("P1=0500000000000000"));
+        assert_eq!("40000000000000000500000000000000", client.send("g"));
+
+        assert_eq!("OK", client.send("M59,1:01"));
+        assert_eq!("6301000000000000", client.send("m58,8"));
+
+        assert_eq!("W00", client.send("c"));
+        assert_eq!("E01", client.send("p7"));
+        assert_eq!("", client.send("vMustReplyEmpty"));
+
+        assert_eq!("OK", client.send("D"));
+        client.server.join().unwrap();
+    }
+
+    #[test]
+    fn gdb_target_description() {
+        let mut client = Client::connect(|| Computer::default().with_software(INCREMENT.to_vec()));
+
+        assert!(client
+            .send("qSupported:xmlRegisters=i386")
+            .contains("qXfer:features:read+"));
+
+        let description = client.send("qXfer:features:read:target.xml:0,fff");
+        assert!(description.starts_with("l<?xml"));
+        assert!(description.contains(r#"<reg name="pc" bitsize="64" type="code_ptr""#));
+
+        let start = client.send("qXfer:features:read:target.xml:0,5");
+        assert_eq!("m<?xml", start);
+        assert_eq!("E00", client.send("qXfer:features:read:other.xml:0,fff"));
+
+        assert_eq!("OK", client.send("D"));
+        client.server.join().unwrap();
+    }
+
+    #[test]
+    fn gdb_invalid_memory_packets() {
+        let mut client = Client::connect(|| Computer::default().with_software(INCREMENT.to_vec()));
+
+        // Reads are cut to the packet size and addresses must not overflow.
+        assert_eq!(super::PACKET_SIZE, client.send("m0,ffffffffffffffff").len());
+        assert_eq!("E01", client.send("mffffffffffffffff,2"));
+
+        // A byte that isn't UTF-8 arrives as a replacement character.
+        assert_eq!("E01", client.send("M0,2:\u{fffd}00"));
+        assert_eq!("E01", client.send("M0,3:\u{fffd}000"));
+        assert_eq!("E01", client.send("M0,1:0"));
+        assert_eq!("0300000000000000", client.send("m0,8"));
+
+        assert_eq!("OK", client.send("D"));
+        client.server.join().unwrap();
+    }
+}
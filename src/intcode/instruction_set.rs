@@ -0,0 +1,262 @@
+use super::computer::{
+    parameter_modes,
+    Computer,
+    ComputerError,
+    Intcode,
+    Parameter,
+};
+use std::collections::BTreeMap;
+
+/// Decodes memory values into instructions and executes the ones that are not
+/// built into the computer.
+pub trait InstructionSet {
+    fn decode(&self, value: isize) -> Result<Intcode, ComputerError>;
+
+    fn execute(
+        &self,
+        opcode: usize,
+        computer: &mut Computer,
+        parameters: &[Parameter],
+    ) -> Result<Flow, ComputerError>;
+}
+
+/// A single opcode that can be registered with an `Extended` instruction set.
+pub trait Instruction {
+    fn name(&self) -> &str;
+
+    fn arity(&self) -> usize;
+
+    fn execute(
+        &self,
+        computer: &mut Computer,
+        parameters: &[Parameter],
+    ) -> Result<Flow, ComputerError>;
+}
+
+/// What the computer should do after an instruction was executed.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Flow {
+    Continue,
+    Jump(usize),
+    Halt,
+    Exit(isize),
+}
+
+/// The instruction set from the puzzles. Unknown opcodes are skipped.
+#[derive(Debug, Default)]
+pub struct Standard;
+
+impl InstructionSet for Standard {
+    fn decode(&self, value: isize) -> Result<Intcode, ComputerError> {
+        Intcode::decode(value)
+    }
+
+    fn execute(
+        &self,
+        opcode: usize,
+        _computer: &mut Computer,
+        _parameters: &[Parameter],
+    ) -> Result<Flow, ComputerError> {
+        Err(ComputerError::UnknownOpcode(opcode))
+    }
+}
+
+/// The standard instruction set with additional opcodes. Registered opcodes
+/// take precedence over the standard ones.
+#[derive(Default)]
+pub struct Extended {
+    instructions: BTreeMap<usize, Box<dyn Instruction>>,
+}
+
+impl Extended {
+    pub fn with_instruction<I: Instruction + 'static>(
+        mut self,
+        opcode: usize,
+        instruction: I,
+    ) -> Self {
+        self.instructions.insert(opcode, Box::new(instruction));
+        self
+    }
+}
+
+impl InstructionSet for Extended {
+    fn decode(&self, value: isize) -> Result<Intcode, ComputerError> {
+        if value < 0 {
+            return Standard.decode(value);
+        }
+
+        let opcode = value as usize % 100;
+
+        match self.instructions.get(&opcode) {
+            Some(instruction) => Ok(Intcode::Extension {
+                opcode,
+                name: instruction.name().to_string(),
+                modes: parameter_modes(value as usize, instruction.arity())?,
+            }),
+
+            None => Standard.decode(value),
+        }
+    }
+
+    fn execute(
+        &self,
+        opcode: usize,
+        computer: &mut Computer,
+        parameters: &[Parameter],
+    ) -> Result<Flow, ComputerError> {
+        self.instructions
+            .get(&opcode)
+            .ok_or(ComputerError::UnknownOpcode(opcode))?
+            .execute(computer, parameters)
+    }
+}
+
+/// Prints the value of its only parameter.
+#[derive(Debug, Default)]
+pub struct DebugPrint;
+
+impl Instruction for DebugPrint {
+    fn name(&self) -> &str {
+        "DEBUG"
+    }
+
+    fn arity(&self) -> usize {
+        1
+    }
+
+    fn execute(
+        &self,
+        _computer: &mut Computer,
+        parameters: &[Parameter],
+    ) -> Result<Flow, ComputerError> {
+        println!("debug: {}", parameters[0].value);
+
+        Ok(Flow::Continue)
+    }
+}
+
+/// Halts the computer and sets its exit code to the value of its only
+/// parameter.
+#[derive(Debug, Default)]
+pub struct HaltWithCode;
+
+impl Instruction for HaltWithCode {
+    fn name(&self) -> &str {
+        "HALTC"
+    }
+
+    fn arity(&self) -> usize {
+        1
+    }
+
+    fn execute(
+        &self,
+        _computer: &mut Computer,
+        parameters: &[Parameter],
+    ) -> Result<Flow, ComputerError> {
+        Ok(Flow::Exit(parameters[0].value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DebugPrint,
+        Extended,
+        Flow,
+        HaltWithCode,
+        Instruction,
+        Standard,
+    };
+    use crate::intcode::computer::{
+        Computer,
+        ComputerError,
+        Intcode,
+        Parameter,
+        ParameterMode::*,
+    };
+    use pretty_assertions::assert_eq;
+
+    struct Swap;
+
+    impl Instruction for Swap {
+        fn name(&self) -> &str {
+            "SWAP"
+        }
+
+        fn arity(&self) -> usize {
+            2
+        }
+
+        fn execute(
+            &self,
+            computer: &mut Computer,
+            parameters: &[Parameter],
+        ) -> Result<Flow, ComputerError> {
+            computer.write_value(parameters[0].address as usize, parameters[1].value)?;
+            computer.write_value(parameters[1].address as usize, parameters[0].value)?;
+
+            Ok(Flow::Continue)
+        }
+    }
+
+    #[test]
+    fn standard_matches_intcode() {
+        use super::InstructionSet;
+
+        assert_eq!(Intcode::from(1002), Standard.decode(1002).unwrap());
+        assert_eq!(Intcode::Skip, Standard.decode(50).unwrap());
+        assert!(matches!(
+            Standard.decode(301),
+            Err(ComputerError::InvalidParameterMode(3))
+        ));
+    }
+
+    #[test]
+    fn extended_decode() {
+        use super::InstructionSet;
+
+        let instruction_set = Extended::default().with_instruction(50, Swap);
+
+        let expected = Intcode::Extension {
+            opcode: 50,
+            name: "SWAP".to_string(),
+            modes: vec![Relative, Position],
+        };
+
+        assert_eq!(expected, instruction_set.decode(250).unwrap());
+        assert_eq!(Intcode::from(1002), instruction_set.decode(1002).unwrap());
+        assert!(matches!(
+            instruction_set.decode(950),
+            Err(ComputerError::InvalidParameterMode(9))
+        ));
+    }
+
+    #[test]
+    fn extended_run_swap() {
+        let input_software = vec![50, 5, 6, 99, 0, 1, 2];
+        let expected = vec![50, 5, 6, 99, 0, 2, 1];
+
+        let mut computer = Computer::from(input_software)
+            .with_instruction_set(Extended::default().with_instruction(50, Swap));
+
+        let got = computer.run().unwrap().memory.to_vec();
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn extended_run_halt_with_code() {
+        let input_software = vec![151, 42, 99];
+
+        let mut computer = Computer::from(input_software).with_instruction_set(
+            Extended::default()
+                .with_instruction(50, DebugPrint)
+                .with_instruction(51, HaltWithCode),
+        );
+
+        computer.run().unwrap();
+
+        assert_eq!(Some(42), computer.exit_code);
+    }
+}
@@ -0,0 +1,511 @@
+//! Journal of every value a computer reads and writes to replay a run.
+//!
+//! A journal is shared with a computer through `Computer::with_journal` and
+//! can be filled from another thread. It keeps the software the computer was
+//! started with, its relative base unless it is 0 and the patches it was
+//! given, so a run can be replayed from the journal alone:
+//!
+//! ```text
+//! software 3,9,1001,9,1,9,4,9,99,0
+//! relative_base 0
+//! patch boot 9:0:1
+//! 1 in 41
+//! 3 out 42
+//! ```
+//!
+//! A patch is its trigger, `boot`, `pc=<address>` or
+//! `memory=<address>:<value>`, followed by its edits as
+//! `<address>:<original>:<value>`.
+//!
+//! Every value is prefixed with the number of instructions executed up to and
+//! including the instruction that read or wrote it.
+
+use super::{
+    computer::{
+        Computer,
+        ComputerError,
+        Reader,
+    },
+    patch::{
+        Patch,
+        Trigger,
+    },
+    program::{
+        ParseProgramError,
+        Program,
+    },
+    report::RunReport,
+};
+use log::trace;
+use std::{
+    path::Path,
+    str::FromStr,
+    sync::{
+        Arc,
+        Mutex,
+    },
+};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Entry {
+    pub instruction: usize,
+    pub event: Event,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Event {
+    Input(isize),
+    Output(isize),
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Journal {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+struct Inner {
+    software: Vec<isize>,
+    relative_base: isize,
+    patches: Vec<Patch>,
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug)]
+pub enum JournalError {
+    Io(std::io::Error),
+    MissingSoftware,
+    InvalidSoftware(ParseProgramError),
+    InvalidRelativeBase { line: usize, content: String },
+    InvalidPatch { line: usize, content: String },
+    InvalidEntry { line: usize, content: String },
+}
+
+impl From<std::io::Error> for JournalError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    /// The replayed run did something else than the journaled run for the
+    /// first time at entry `index`.
+    Diverged {
+        index: usize,
+        expected: Option<Entry>,
+        found: Option<Entry>,
+    },
+    Computer(ComputerError),
+}
+
+impl Journal {
+    /// Forgets everything recorded so far and starts a new run of the
+    /// software from `relative_base` with the patches that may be applied
+    /// while it runs.
+    pub fn start(&self, software: Vec<isize>, relative_base: isize, patches: Vec<Patch>) {
+        *self.inner.lock().unwrap() = Inner {
+            software,
+            relative_base,
+            patches,
+            entries: Vec::new(),
+        };
+    }
+
+    pub fn record(&self, instruction: usize, event: Event) {
+        trace!("journal {:?} at instruction {}", event, instruction);
+
+        self.inner
+            .lock()
+            .unwrap()
+            .entries
+            .push(Entry { instruction, event });
+    }
+
+    pub fn software(&self) -> Vec<isize> {
+        self.inner.lock().unwrap().software.clone()
+    }
+
+    pub fn relative_base(&self) -> isize {
+        self.inner.lock().unwrap().relative_base
+    }
+
+    pub fn patches(&self) -> Vec<Patch> {
+        self.inner.lock().unwrap().patches.clone()
+    }
+
+    pub fn entries(&self) -> Vec<Entry> {
+        self.inner.lock().unwrap().entries.clone()
+    }
+
+    pub fn inputs(&self) -> Vec<isize> {
+        self.events(|event| match event {
+            Event::Input(value) => Some(value),
+            Event::Output(_) => None,
+        })
+    }
+
+    pub fn outputs(&self) -> Vec<isize> {
+        self.events(|event| match event {
+            Event::Input(_) => None,
+            Event::Output(value) => Some(value),
+        })
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), JournalError> {
+        std::fs::write(path, self.to_string())?;
+
+        Ok(())
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, JournalError> {
+        std::fs::read_to_string(path)?.parse()
+    }
+
+    /// Runs the software on a fresh computer with the journaled inputs and
+    /// checks that it reads and writes the same values at the same
+    /// instructions.
+    pub fn replay(&self) -> Result<RunReport, ReplayError> {
+        let replayed = Journal::default();
+
+        let mut computer = self.patches().into_iter().fold(
+            Computer::default()
+                .with_software(self.software())
+                .with_relative_base(self.relative_base())
+                .with_input(Reader::Tester(Box::new(self.inputs().into_iter())))
+                .with_journal(&replayed),
+            Computer::with_patch,
+        );
+
+        let result = computer.run();
+
+        let expected = self.entries();
+        let found = replayed.entries();
+
+        let divergence = (0..expected.len().max(found.len()))
+            .find(|index| expected.get(*index) != found.get(*index));
+
+        if let Some(index) = divergence {
+            return Err(ReplayError::Diverged {
+                index,
+                expected: expected.get(index).copied(),
+                found: found.get(index).copied(),
+            });
+        }
+
+        result.map_err(ReplayError::Computer)
+    }
+
+    fn events<F: Fn(Event) -> Option<isize>>(&self, f: F) -> Vec<isize> {
+        self.inner
+            .lock()
+            .unwrap()
+            .entries
+            .iter()
+            .filter_map(|entry| f(entry.event))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for Journal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inner = self.inner.lock().unwrap();
+
+        writeln!(f, "software {}", Program::from(inner.software.clone()))?;
+
+        if inner.relative_base != 0 {
+            writeln!(f, "relative_base {}", inner.relative_base)?;
+        }
+
+        for patch in &inner.patches {
+            let trigger = match patch.trigger {
+                Trigger::Boot => "boot".to_string(),
+                Trigger::Pc(address) => format!("pc={}", address),
+                Trigger::Memory { address, value } => format!("memory={}:{}", address, value),
+            };
+
+            write!(f, "patch {}", trigger)?;
+
+            for edit in &patch.edits {
+                write!(f, " {}:{}:{}", edit.address, edit.original, edit.value)?;
+            }
+
+            writeln!(f)?;
+        }
+
+        for entry in &inner.entries {
+            match entry.event {
+                Event::Input(value) => writeln!(f, "{} in {}", entry.instruction, value)?,
+                Event::Output(value) => writeln!(f, "{} out {}", entry.instruction, value)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Journal {
+    type Err = JournalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().enumerate();
+
+        let software = match lines.next() {
+            Some((_, line)) if line.starts_with("software ") => line["software ".len()..]
+                .parse::<Program>()
+                .map_err(JournalError::InvalidSoftware)?,
+            _ => return Err(JournalError::MissingSoftware),
+        };
+
+        let mut lines = lines.peekable();
+
+        let relative_base = match lines.next_if(|(_, line)| line.starts_with("relative_base ")) {
+            Some((index, line)) => line["relative_base ".len()..].trim().parse().map_err(|_| {
+                JournalError::InvalidRelativeBase {
+                    line: index + 1,
+                    content: line.to_string(),
+                }
+            })?,
+            None => 0,
+        };
+
+        let mut patches = Vec::new();
+
+        while let Some((index, line)) = lines.next_if(|(_, line)| line.starts_with("patch ")) {
+            patches.push(parse_patch(&line["patch ".len()..]).ok_or_else(|| {
+                JournalError::InvalidPatch {
+                    line: index + 1,
+                    content: line.to_string(),
+                }
+            })?);
+        }
+
+        let entries = lines
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                let invalid = || JournalError::InvalidEntry {
+                    line: index + 1,
+                    content: line.to_string(),
+                };
+
+                let mut fields = line.split_whitespace();
+
+                let instruction = fields
+                    .next()
+                    .and_then(|field| field.parse().ok())
+                    .ok_or_else(invalid)?;
+                let kind = fields.next().ok_or_else(invalid)?;
+                let value = fields
+                    .next()
+                    .and_then(|field| field.parse().ok())
+                    .ok_or_else(invalid)?;
+
+                let event = match kind {
+                    "in" => Event::Input(value),
+                    "out" => Event::Output(value),
+                    _ => return Err(invalid()),
+                };
+
+                if fields.next().is_some() {
+                    return Err(invalid());
+                }
+
+                Ok(Entry { instruction, event })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            inner: Arc::new(Mutex::new(Inner {
+                software: software.into(),
+                relative_base,
+                patches,
+                entries,
+            })),
+        })
+    }
+}
+
+/// Parses a patch like `pc=7 15:1:2 16:0:3`.
+fn parse_patch(s: &str) -> Option<Patch> {
+    let mut fields = s.split_whitespace();
+
+    let trigger = match fields.next()? {
+        "boot" => Trigger::Boot,
+        field if field.starts_with("pc=") => Trigger::Pc(field["pc=".len()..].parse().ok()?),
+        field if field.starts_with("memory=") => {
+            let (address, value) = field["memory=".len()..].split_once(':')?;
+
+            Trigger::Memory {
+                address: address.parse().ok()?,
+                value: value.parse().ok()?,
+            }
+        }
+        _ => return None,
+    };
+
+    fields.try_fold(
+        Patch::new("journal", "patch recorded in a journal").with_trigger(trigger),
+        |patch, field| {
+            let mut values = field.split(':');
+            let address = values.next()?.parse().ok()?;
+            let original = values.next()?.parse().ok()?;
+            let value = values.next()?.parse().ok()?;
+
+            if values.next().is_some() {
+                return None;
+            }
+
+            Some(patch.with_edit(address, original, value))
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Entry,
+        Event,
+        Journal,
+        JournalError,
+        ReplayError,
+    };
+    use crate::intcode::{
+        computer::{
+            Computer,
+            Reader,
+        },
+        patch::Patch,
+    };
+    use pretty_assertions::assert_eq;
+
+    /// Reads a value, increments it and writes it.
+    const INCREMENT: &[isize] = &[3, 9, 1001, 9, 1, 9, 4, 9, 99, 0];
+
+    fn record(input: isize) -> Journal {
+        let journal = Journal::default();
+
+        let mut computer = Computer::default()
+            .with_software(INCREMENT.to_vec())
+            .with_input(Reader::Tester(Box::new(vec![input].into_iter())))
+            .with_journal(&journal);
+
+        computer.run().unwrap();
+
+        journal
+    }
+
+    #[test]
+    fn journal_record() {
+        let journal = record(41);
+
+        assert_eq!(INCREMENT.to_vec(), journal.software());
+        assert_eq!(
+            vec![
+                Entry {
+                    instruction: 1,
+                    event: Event::Input(41),
+                },
+                Entry {
+                    instruction: 3,
+                    event: Event::Output(42),
+                },
+            ],
+            journal.entries()
+        );
+
+        assert_eq!(
+            "software 3,9,1001,9,1,9,4,9,99,0\n1 in 41\n3 out 42\n",
+            journal.to_string()
+        );
+    }
+
+    #[test]
+    fn journal_round_trip_and_replay() {
+        let journal = record(41).to_string().parse::<Journal>().unwrap();
+
+        let report = journal.replay().unwrap();
+        assert_eq!(vec![42], report.outputs);
+    }
+
+    #[test]
+    fn journal_patched_round_trip_and_replay() {
+        let journal = Journal::default();
+
+        // Adds 2 instead of 1.
+        let mut computer = Computer::default()
+            .with_software(INCREMENT.to_vec())
+            .with_input(Reader::Tester(Box::new(vec![41].into_iter())))
+            .with_patch(Patch::new("add two", "adds 2").with_edit(4, 1, 2))
+            .with_journal(&journal);
+
+        assert_eq!(vec![43], computer.run().unwrap().outputs);
+        assert!(journal.to_string().contains("\npatch boot 4:1:2\n"));
+
+        let journal = journal.to_string().parse::<Journal>().unwrap();
+
+        assert_eq!(vec![43], journal.replay().unwrap().outputs);
+    }
+
+    #[test]
+    fn journal_relative_base_round_trip_and_replay() {
+        let journal = Journal::default();
+
+        // Outputs the value at relative address 1, which is 99 only from a
+        // relative base of 1.
+        let mut computer = Computer::default()
+            .with_software(vec![204, 1, 99])
+            .with_relative_base(1)
+            .with_journal(&journal);
+
+        assert_eq!(vec![99], computer.run().unwrap().outputs);
+        assert!(journal.to_string().contains("\nrelative_base 1\n"));
+
+        let journal = journal.to_string().parse::<Journal>().unwrap();
+
+        assert_eq!(1, journal.relative_base());
+        assert_eq!(vec![99], journal.replay().unwrap().outputs);
+    }
+
+    #[test]
+    fn journal_replay_diverged() {
+        let journal = "software 3,9,1001,9,1,9,4,9,99,0\n1 in 41\n3 out 43\n"
+            .parse::<Journal>()
+            .unwrap();
+
+        match journal.replay() {
+            Err(ReplayError::Diverged {
+                index,
+                expected,
+                found,
+            }) => {
+                assert_eq!(1, index);
+                assert_eq!(Some(Event::Output(43)), expected.map(|entry| entry.event));
+                assert_eq!(Some(Event::Output(42)), found.map(|entry| entry.event));
+            }
+            got => panic!("unexpected result: {:?}", got),
+        }
+    }
+
+    #[test]
+    fn journal_parse_errors() {
+        match "1 in 41".parse::<Journal>() {
+            Err(JournalError::MissingSoftware) => {}
+            got => panic!("unexpected result: {:?}", got),
+        }
+
+        match "software 99\nrelative_base one\n1 in 41".parse::<Journal>() {
+            Err(JournalError::InvalidRelativeBase { line, .. }) => assert_eq!(2, line),
+            got => panic!("unexpected result: {:?}", got),
+        }
+
+        match "software 99\npatch boot 4:1\n1 in 41".parse::<Journal>() {
+            Err(JournalError::InvalidPatch { line, .. }) => assert_eq!(2, line),
+            got => panic!("unexpected result: {:?}", got),
+        }
+
+        match "software 99\n1 sideways 41".parse::<Journal>() {
+            Err(JournalError::InvalidEntry { line, .. }) => assert_eq!(2, line),
+            got => panic!("unexpected result: {:?}", got),
+        }
+    }
+}
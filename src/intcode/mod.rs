@@ -0,0 +1,22 @@
+//! Intcode computer shared between the days that run Intcode software.
+
+pub mod call_stack;
+pub mod codec;
+pub mod compiler;
+pub mod computer;
+pub mod coverage;
+pub mod crash;
+pub mod dap;
+pub mod debugger;
+pub mod decompiler;
+pub mod diagnostics;
+pub mod gdb;
+pub mod instruction_set;
+pub mod journal;
+pub mod patch;
+pub mod program;
+pub mod protection;
+pub mod report;
+pub mod sanitizer;
+pub mod scheduler;
+pub mod visualizer;
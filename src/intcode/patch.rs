@@ -0,0 +1,198 @@
+use std::collections::BTreeMap;
+
+/// A named set of memory edits applied to software before it boots or when a
+/// trigger fires at runtime. Every edit knows the value it expects to
+/// replace so a patch for one version of a program is not silently applied to
+/// a different one.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Patch {
+    pub name: String,
+    pub description: String,
+    pub trigger: Trigger,
+    pub edits: Vec<Edit>,
+}
+
+/// When a patch is applied. Each patch is applied at most once per run.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Trigger {
+    /// Before the first instruction is executed.
+    Boot,
+    /// Before the instruction at the address is executed for the first time.
+    Pc(usize),
+    /// Before the next instruction once the address holds the value.
+    Memory { address: usize, value: isize },
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Edit {
+    pub address: usize,
+    /// Value the address has to hold before the patch is applied.
+    pub original: isize,
+    pub value: isize,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PatchError {
+    OriginalMismatch {
+        patch: String,
+        address: usize,
+        expected: isize,
+        found: isize,
+    },
+}
+
+impl Patch {
+    pub fn new(name: &str, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            trigger: Trigger::Boot,
+            edits: Vec::new(),
+        }
+    }
+
+    pub fn with_trigger(self, trigger: Trigger) -> Self {
+        Self { trigger, ..self }
+    }
+
+    pub fn with_edit(mut self, address: usize, original: isize, value: isize) -> Self {
+        self.edits.push(Edit {
+            address,
+            original,
+            value,
+        });
+
+        self
+    }
+
+    pub fn is_triggered(&self, pc: usize, memory: &BTreeMap<usize, isize>) -> bool {
+        match self.trigger {
+            Trigger::Boot => true,
+            Trigger::Pc(address) => pc == address,
+            Trigger::Memory { address, value } => *memory.get(&address).unwrap_or(&0) == value,
+        }
+    }
+
+    /// Checks every edit before writing anything so a mismatching patch
+    /// leaves the memory untouched.
+    pub fn apply(&self, memory: &mut BTreeMap<usize, isize>) -> Result<(), PatchError> {
+        for edit in &self.edits {
+            let found = *memory.get(&edit.address).unwrap_or(&0);
+
+            if found != edit.original {
+                return Err(PatchError::OriginalMismatch {
+                    patch: self.name.clone(),
+                    address: edit.address,
+                    expected: edit.original,
+                    found,
+                });
+            }
+        }
+
+        for edit in &self.edits {
+            memory.insert(edit.address, edit.value);
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Patch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}: {}", self.name, self.description)?;
+
+        for edit in &self.edits {
+            writeln!(
+                f,
+                "{:>6}  {} -> {}",
+                edit.address, edit.original, edit.value
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Patch,
+        PatchError,
+        Trigger,
+    };
+    use crate::intcode::computer::{
+        Computer,
+        ComputerError,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn patch_boot() {
+        let input_software = vec![1, 0, 0, 0, 99];
+        let expected = vec![15, 7, 8, 0, 99];
+
+        let patch = Patch::new("immediate", "add 7 and 8 instead")
+            .with_edit(0, 1, 1101)
+            .with_edit(1, 0, 7)
+            .with_edit(2, 0, 8);
+
+        let mut computer = Computer::from(input_software).with_patch(patch);
+        let got = computer.run().unwrap().memory.to_vec();
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn patch_original_mismatch() {
+        let input_software = vec![1, 0, 0, 0, 99];
+
+        let patch = Patch::new("wrong version", "")
+            .with_edit(2, 0, 5)
+            .with_edit(4, 98, 99);
+
+        let mut computer = Computer::from(input_software.clone()).with_patch(patch);
+
+        match computer.run() {
+            Err(ComputerError::Patch(PatchError::OriginalMismatch {
+                patch,
+                address,
+                expected,
+                found,
+            })) => {
+                assert_eq!("wrong version", patch);
+                assert_eq!((4, 98, 99), (address, expected, found));
+            }
+            got => panic!("unexpected result: {:?}", got),
+        }
+
+        let got: Vec<_> = computer.memory.values().copied().collect();
+        assert_eq!(input_software, got);
+    }
+
+    #[test]
+    fn patch_runtime_triggers() {
+        // Outputs the value at address 11 twice and then the sum of two zeros.
+        let input_software = vec![4, 11, 4, 11, 1101, 0, 0, 12, 4, 12, 99, 1];
+
+        let at_pc = Patch::new("second output", "")
+            .with_trigger(Trigger::Pc(2))
+            .with_edit(11, 1, 2);
+
+        let on_memory = Patch::new("sum", "")
+            .with_trigger(Trigger::Memory {
+                address: 11,
+                value: 2,
+            })
+            .with_edit(5, 0, 3)
+            .with_edit(6, 0, 4);
+
+        let mut computer = Computer::default()
+            .with_software(input_software)
+            .with_patch(at_pc)
+            .with_patch(on_memory);
+
+        computer.run().unwrap();
+
+        assert_eq!(vec![1, 2, 7], computer.output.values());
+    }
+}
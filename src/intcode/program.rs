@@ -0,0 +1,209 @@
+use std::{
+    path::Path,
+    str::FromStr,
+};
+
+/// Intcode software as loaded from comma separated text. Whitespace and
+/// newlines between values are ignored and `#` starts a comment that runs to
+/// the end of the line.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Program {
+    values: Vec<isize>,
+}
+
+#[derive(Debug)]
+pub enum ParseProgramError {
+    EmptyValue {
+        offset: usize,
+    },
+    MissingSeparator {
+        offset: usize,
+    },
+    InvalidValue {
+        offset: usize,
+        value: String,
+        err: std::num::ParseIntError,
+    },
+}
+
+#[derive(Debug)]
+pub enum LoadProgramError {
+    Io(std::io::Error),
+    Parse(ParseProgramError),
+}
+
+impl From<std::io::Error> for LoadProgramError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<ParseProgramError> for LoadProgramError {
+    fn from(err: ParseProgramError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+impl Program {
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, LoadProgramError> {
+        let content = std::fs::read_to_string(path)?;
+
+        Ok(content.parse()?)
+    }
+}
+
+impl FromStr for Program {
+    type Err = ParseProgramError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut values = Vec::new();
+        let mut expect_value = true;
+        let mut chars = s.char_indices().peekable();
+
+        while let Some((offset, c)) = chars.next() {
+            match c {
+                '#' => {
+                    for (_, c) in chars.by_ref() {
+                        if c == '\n' {
+                            break;
+                        }
+                    }
+                }
+
+                ',' => {
+                    if expect_value {
+                        return Err(ParseProgramError::EmptyValue { offset });
+                    }
+
+                    expect_value = true;
+                }
+
+                c if c.is_whitespace() => {}
+
+                _ => {
+                    if !expect_value {
+                        return Err(ParseProgramError::MissingSeparator { offset });
+                    }
+
+                    let mut end = offset + c.len_utf8();
+
+                    while let Some((next_offset, next)) = chars.peek() {
+                        if *next == ',' || *next == '#' || next.is_whitespace() {
+                            break;
+                        }
+
+                        end = next_offset + next.len_utf8();
+                        chars.next();
+                    }
+
+                    let value = &s[offset..end];
+                    let parsed = value
+                        .parse()
+                        .map_err(|err| ParseProgramError::InvalidValue {
+                            offset,
+                            value: value.to_string(),
+                            err,
+                        })?;
+
+                    values.push(parsed);
+                    expect_value = false;
+                }
+            }
+        }
+
+        Ok(Self { values })
+    }
+}
+
+impl std::ops::Deref for Program {
+    type Target = [isize];
+
+    fn deref(&self) -> &Self::Target {
+        &self.values
+    }
+}
+
+impl From<Vec<isize>> for Program {
+    fn from(values: Vec<isize>) -> Self {
+        Self { values }
+    }
+}
+
+impl From<Program> for Vec<isize> {
+    fn from(program: Program) -> Self {
+        program.values
+    }
+}
+
+impl std::fmt::Display for Program {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let values = self
+            .values
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(",");
+
+        write!(f, "{}", values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ParseProgramError,
+        Program,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_simple() {
+        let expected = vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+        let got: Program = "1,9,10,3,2,3,11,0,99,30,40,50".parse().unwrap();
+
+        assert_eq!(expected, got.to_vec());
+    }
+
+    #[test]
+    fn parse_whitespace_and_comments() {
+        let input = "# add the first two values
+1, 9, 10, 3,
+2,3,11,0, # multiply
+99,
+-30 , 40,50,
+";
+
+        let expected = vec![1, 9, 10, 3, 2, 3, 11, 0, 99, -30, 40, 50];
+        let got: Program = input.parse().unwrap();
+
+        assert_eq!(expected, got.to_vec());
+    }
+
+    #[test]
+    fn parse_errors() {
+        match "1,,2".parse::<Program>() {
+            Err(ParseProgramError::EmptyValue { offset }) => assert_eq!(2, offset),
+            got => panic!("unexpected result: {:?}", got),
+        }
+
+        match "1,2 3".parse::<Program>() {
+            Err(ParseProgramError::MissingSeparator { offset }) => assert_eq!(4, offset),
+            got => panic!("unexpected result: {:?}", got),
+        }
+
+        match "1,\n  2x,3".parse::<Program>() {
+            Err(ParseProgramError::InvalidValue { offset, value, .. }) => {
+                assert_eq!(5, offset);
+                assert_eq!("2x", value);
+            }
+            got => panic!("unexpected result: {:?}", got),
+        }
+    }
+
+    #[test]
+    fn parse_arcade_software() {
+        let got: Program = crate::day_13::arcade::ARCADE_SOFTWARE.parse().unwrap();
+
+        assert_eq!(&[1, 380, 379, 385], &got[..4]);
+    }
+}
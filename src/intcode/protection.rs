@@ -0,0 +1,135 @@
+use super::computer::ComputerError;
+use log::warn;
+use std::{
+    collections::BTreeSet,
+    ops::Range,
+};
+
+/// Detects writes to memory that holds code. Intcode allows self modifying
+/// software but for software we write ourselves it is usually a bug.
+#[derive(Debug, Clone)]
+pub struct CodeProtection {
+    pub policy: WritePolicy,
+    pub region: CodeRegion,
+    executed: BTreeSet<usize>,
+}
+
+/// What happens when the software writes to code.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum WritePolicy {
+    Error,
+    Warn,
+}
+
+/// Which addresses are considered code.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum CodeRegion {
+    /// Every address that was part of an executed instruction.
+    Executed,
+    /// A fixed range of addresses.
+    Range(Range<usize>),
+}
+
+impl CodeProtection {
+    pub fn executed(policy: WritePolicy) -> Self {
+        Self {
+            policy,
+            region: CodeRegion::Executed,
+            executed: BTreeSet::default(),
+        }
+    }
+
+    pub fn range(range: Range<usize>, policy: WritePolicy) -> Self {
+        Self {
+            policy,
+            region: CodeRegion::Range(range),
+            executed: BTreeSet::default(),
+        }
+    }
+
+    /// Marks the instruction at `address` and its parameters as code.
+    pub fn execute(&mut self, address: usize, size: usize) {
+        if self.region == CodeRegion::Executed {
+            self.executed.extend(address..address + size);
+        }
+    }
+
+    pub fn is_code(&self, address: usize) -> bool {
+        match &self.region {
+            CodeRegion::Executed => self.executed.contains(&address),
+            CodeRegion::Range(range) => range.contains(&address),
+        }
+    }
+
+    pub fn check_write(&self, pc: usize, address: usize) -> Result<(), ComputerError> {
+        if !self.is_code(address) {
+            return Ok(());
+        }
+
+        match self.policy {
+            WritePolicy::Error => Err(ComputerError::CodeWrite { pc, address }),
+
+            WritePolicy::Warn => {
+                warn!("instruction at {} wrote to code at {}", pc, address);
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        CodeProtection,
+        WritePolicy,
+    };
+    use crate::intcode::computer::{
+        Computer,
+        ComputerError,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn code_protection_executed_error() {
+        let input_software = vec![1101, 5, 6, 0, 99];
+
+        let mut computer = Computer::from(input_software)
+            .with_code_protection(CodeProtection::executed(WritePolicy::Error));
+
+        match computer.run() {
+            Err(ComputerError::CodeWrite { pc, address }) => assert_eq!((0, 0), (pc, address)),
+            got => panic!("unexpected result: {:?}", got),
+        }
+    }
+
+    #[test]
+    fn code_protection_executed_warn() {
+        let input_software = vec![1101, 5, 6, 0, 99];
+        let expected = vec![11, 5, 6, 0, 99];
+
+        let mut computer = Computer::from(input_software)
+            .with_code_protection(CodeProtection::executed(WritePolicy::Warn));
+
+        let got = computer.run().unwrap().memory.to_vec();
+
+        assert_eq!(expected, got);
+    }
+
+    #[test]
+    fn code_protection_range() {
+        let input_software = vec![1, 9, 10, 3, 2, 3, 11, 0, 99, 30, 40, 50];
+
+        let mut computer = Computer::from(input_software.clone())
+            .with_code_protection(CodeProtection::range(4..9, WritePolicy::Error));
+
+        assert_eq!(3500, computer.run().unwrap().memory[0]);
+
+        let mut computer = Computer::from(input_software)
+            .with_code_protection(CodeProtection::range(0..9, WritePolicy::Error));
+
+        match computer.run() {
+            Err(ComputerError::CodeWrite { pc, address }) => assert_eq!((0, 3), (pc, address)),
+            got => panic!("unexpected result: {:?}", got),
+        }
+    }
+}
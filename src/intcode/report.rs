@@ -0,0 +1,137 @@
+use std::collections::BTreeMap;
+
+/// Summary of a finished run of a computer.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RunReport {
+    pub halt: HaltReason,
+    /// Address of the instruction that stopped the computer.
+    pub pc: usize,
+    pub instructions: usize,
+    pub inputs: Vec<isize>,
+    pub outputs: Vec<isize>,
+    /// Highest address that was read, written or executed.
+    pub highest_address: usize,
+    pub memory: Memory,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum HaltReason {
+    Halt,
+    Exit(isize),
+}
+
+/// Counters a computer keeps while it runs to build the `RunReport`.
+#[derive(Debug, Default, Clone)]
+pub struct Statistics {
+    pub instructions: usize,
+    pub inputs: Vec<isize>,
+    pub outputs: Vec<isize>,
+    pub highest_address: usize,
+}
+
+impl Statistics {
+    pub fn touch(&mut self, address: usize) {
+        self.highest_address = self.highest_address.max(address);
+    }
+}
+
+/// Memory of a computer indexed by address. Addresses that were never
+/// written read as 0 just like they do for the computer.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Memory {
+    values: BTreeMap<usize, isize>,
+}
+
+impl Memory {
+    pub fn get(&self, address: usize) -> isize {
+        self[address]
+    }
+
+    /// Addresses and values of every address that is set.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, isize)> + '_ {
+        self.values
+            .iter()
+            .map(|(address, value)| (*address, *value))
+    }
+
+    /// Every value from address 0 up to the highest set address.
+    pub fn to_vec(&self) -> Vec<isize> {
+        let len = self
+            .values
+            .keys()
+            .next_back()
+            .map(|address| address + 1)
+            .unwrap_or(0);
+
+        (0..len).map(|address| self[address]).collect()
+    }
+}
+
+impl std::ops::Index<usize> for Memory {
+    type Output = isize;
+
+    fn index(&self, address: usize) -> &Self::Output {
+        self.values.get(&address).unwrap_or(&0)
+    }
+}
+
+impl From<BTreeMap<usize, isize>> for Memory {
+    fn from(values: BTreeMap<usize, isize>) -> Self {
+        Self { values }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HaltReason;
+    use crate::intcode::{
+        computer::{
+            Computer,
+            Reader,
+        },
+        instruction_set::{
+            Extended,
+            HaltWithCode,
+        },
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn run_report() {
+        // Reads a value, adds 5 and stores it far behind the software before
+        // writing it to the output.
+        let input_software = vec![3, 100, 101, 5, 100, 200, 4, 200, 99];
+
+        let mut computer = Computer::default()
+            .with_software(input_software)
+            .with_input(Reader::Tester(Box::new(vec![37].into_iter())));
+
+        let report = computer.run().unwrap();
+
+        assert_eq!(HaltReason::Halt, report.halt);
+        assert_eq!(8, report.pc);
+        assert_eq!(4, report.instructions);
+        assert_eq!(vec![37], report.inputs);
+        assert_eq!(vec![42], report.outputs);
+        assert_eq!(200, report.highest_address);
+
+        assert_eq!(37, report.memory[100]);
+        assert_eq!(42, report.memory.get(200));
+        assert_eq!(0, report.memory[150]);
+        assert_eq!(201, report.memory.to_vec().len());
+    }
+
+    #[test]
+    fn run_report_exit_code() {
+        let input_software = vec![151, 3];
+
+        let mut computer = Computer::from(input_software)
+            .with_instruction_set(Extended::default().with_instruction(51, HaltWithCode));
+
+        let report = computer.run().unwrap();
+
+        assert_eq!(HaltReason::Exit(3), report.halt);
+        assert_eq!(1, report.instructions);
+        assert_eq!(1, report.highest_address);
+    }
+}
@@ -0,0 +1,156 @@
+use super::computer::ComputerError;
+use log::warn;
+use std::{
+    collections::BTreeSet,
+    ops::Range,
+};
+
+/// Detects reads of memory that was neither part of the loaded software nor
+/// written by it. Reading such memory returns 0 which hides reads past the end
+/// of the software or through bad pointers.
+#[derive(Debug, Clone)]
+pub struct MemorySanitizer {
+    pub policy: ReadPolicy,
+    /// Regions the software expects to be zero, e.g. scratch memory.
+    pub allowed: Vec<Range<usize>>,
+    /// Uninitialized reads seen with `ReadPolicy::Warn`.
+    pub reads: Vec<UninitializedRead>,
+    initialized: BTreeSet<usize>,
+}
+
+/// What happens when the software reads uninitialized memory.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ReadPolicy {
+    Error,
+    Warn,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct UninitializedRead {
+    pub pc: usize,
+    pub address: usize,
+}
+
+impl MemorySanitizer {
+    pub fn new(policy: ReadPolicy) -> Self {
+        Self {
+            policy,
+            allowed: Vec::new(),
+            reads: Vec::new(),
+            initialized: BTreeSet::default(),
+        }
+    }
+
+    pub fn with_allowed(mut self, range: Range<usize>) -> Self {
+        self.allowed.push(range);
+        self
+    }
+
+    pub fn with_initialized<I: IntoIterator<Item = usize>>(mut self, addresses: I) -> Self {
+        self.initialized.extend(addresses);
+        self
+    }
+
+    pub fn initialize(&mut self, address: usize) {
+        self.initialized.insert(address);
+    }
+
+    pub fn is_initialized(&self, address: usize) -> bool {
+        self.initialized.contains(&address)
+            || self.allowed.iter().any(|range| range.contains(&address))
+    }
+
+    pub fn check_read(&mut self, pc: usize, address: usize) -> Result<(), ComputerError> {
+        if self.is_initialized(address) {
+            return Ok(());
+        }
+
+        match self.policy {
+            ReadPolicy::Error => Err(ComputerError::UninitializedRead { pc, address }),
+
+            ReadPolicy::Warn => {
+                warn!(
+                    "instruction at {} read uninitialized memory at {}",
+                    pc, address
+                );
+
+                self.reads.push(UninitializedRead { pc, address });
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        MemorySanitizer,
+        ReadPolicy,
+        UninitializedRead,
+    };
+    use crate::intcode::computer::{
+        Computer,
+        ComputerError,
+    };
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn memory_sanitizer_error() {
+        let input_software = vec![4, 10, 99];
+
+        let mut computer = Computer::default()
+            .with_memory_sanitizer(MemorySanitizer::new(ReadPolicy::Error))
+            .with_software(input_software);
+
+        match computer.run() {
+            Err(ComputerError::UninitializedRead { pc, address }) => {
+                assert_eq!((0, 10), (pc, address))
+            }
+            got => panic!("unexpected result: {:?}", got),
+        }
+    }
+
+    #[test]
+    fn memory_sanitizer_allowed() {
+        let input_software = vec![4, 10, 99];
+
+        let mut computer = Computer::default()
+            .with_software(input_software)
+            .with_memory_sanitizer(MemorySanitizer::new(ReadPolicy::Error).with_allowed(10..20));
+
+        computer.run().unwrap();
+
+        assert_eq!(vec![0], computer.output.values());
+    }
+
+    #[test]
+    fn memory_sanitizer_written() {
+        let input_software = vec![1101, 1, 2, 10, 4, 10, 99];
+
+        let mut computer = Computer::default()
+            .with_software(input_software)
+            .with_memory_sanitizer(MemorySanitizer::new(ReadPolicy::Error));
+
+        computer.run().unwrap();
+
+        assert_eq!(vec![3], computer.output.values());
+    }
+
+    #[test]
+    fn memory_sanitizer_warn() {
+        let input_software = vec![4, 10, 204, 5, 99];
+
+        let mut computer = Computer::default()
+            .with_software(input_software)
+            .with_memory_sanitizer(MemorySanitizer::new(ReadPolicy::Warn));
+
+        computer.run().unwrap();
+
+        let expected = vec![
+            UninitializedRead { pc: 0, address: 10 },
+            UninitializedRead { pc: 2, address: 5 },
+        ];
+
+        assert_eq!(expected, computer.memory_sanitizer.unwrap().reads);
+    }
+}
@@ -0,0 +1,312 @@
+//! Runs many computers on a single thread.
+//!
+//! Machines take turns round robin and execute up to `quantum` instructions
+//! each turn. A machine gives up its turn early when it waits for input. If
+//! every machine that has not halted waits for input, none of them can ever
+//! continue and the scheduler reports the deadlock instead of hanging.
+//!
+//! Machines talk through unbounded channels, as a bounded channel would block
+//! the thread of the scheduler when a machine writes.
+
+use super::{
+    computer::{
+        Computer,
+        ComputerError,
+    },
+    report::HaltReason,
+};
+
+pub struct Scheduler {
+    machines: Vec<Machine>,
+    quantum: usize,
+}
+
+struct Machine {
+    name: String,
+    computer: Computer,
+    instructions: usize,
+    halt: Option<HaltReason>,
+}
+
+/// How a machine finished.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MachineReport {
+    pub name: String,
+    pub halt: HaltReason,
+    pub instructions: usize,
+}
+
+#[derive(Debug)]
+pub enum SchedulerError {
+    Deadlock(Deadlock),
+    Computer {
+        machine: String,
+        pc: usize,
+        error: ComputerError,
+    },
+}
+
+/// Every machine that has not halted waits for input.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Deadlock {
+    /// Names of the waiting machines and the addresses of their reads.
+    pub waiting: Vec<(String, usize)>,
+    pub halted: Vec<String>,
+    /// Instructions executed by all machines before the deadlock.
+    pub instructions: usize,
+}
+
+impl Default for Scheduler {
+    fn default() -> Self {
+        Self {
+            machines: Vec::new(),
+            quantum: 1000,
+        }
+    }
+}
+
+impl Scheduler {
+    /// Instructions a machine executes before the next one gets its turn.
+    pub fn with_quantum(self, quantum: usize) -> Self {
+        Self {
+            quantum: quantum.max(1),
+            ..self
+        }
+    }
+
+    /// Adds a machine that starts at its current `pc`.
+    pub fn with_machine<S: Into<String>>(mut self, name: S, computer: Computer) -> Self {
+        self.machines.push(Machine {
+            name: name.into(),
+            computer,
+            instructions: 0,
+            halt: None,
+        });
+
+        self
+    }
+
+    /// Runs until every machine halted.
+    pub fn run(&mut self) -> Result<Vec<MachineReport>, SchedulerError> {
+        loop {
+            let mut running = false;
+            let mut progressed = false;
+
+            for machine in self
+                .machines
+                .iter_mut()
+                .filter(|machine| machine.halt.is_none())
+            {
+                running = true;
+
+                for _ in 0..self.quantum {
+                    if machine.computer.is_waiting_for_input() {
+                        break;
+                    }
+
+                    let halt = match machine.computer.step() {
+                        Ok(halt) => halt,
+                        Err(error) => {
+                            return Err(SchedulerError::Computer {
+                                machine: machine.name.clone(),
+                                pc: machine.computer.pc,
+                                error,
+                            })
+                        }
+                    };
+
+                    machine.instructions += 1;
+                    progressed = true;
+
+                    if halt.is_some() {
+                        machine.halt = halt;
+                        break;
+                    }
+                }
+            }
+
+            if !running {
+                break;
+            }
+
+            if !progressed {
+                return Err(SchedulerError::Deadlock(self.deadlock()));
+            }
+        }
+
+        Ok(self
+            .machines
+            .iter()
+            .map(|machine| MachineReport {
+                name: machine.name.clone(),
+                halt: machine.halt.unwrap_or(HaltReason::Halt),
+                instructions: machine.instructions,
+            })
+            .collect())
+    }
+
+    /// The computer of the machine with the given name.
+    pub fn computer(&self, name: &str) -> Option<&Computer> {
+        self.machines
+            .iter()
+            .find(|machine| machine.name == name)
+            .map(|machine| &machine.computer)
+    }
+
+    fn deadlock(&self) -> Deadlock {
+        let (halted, waiting): (Vec<_>, Vec<_>) = self
+            .machines
+            .iter()
+            .partition(|machine| machine.halt.is_some());
+
+        Deadlock {
+            waiting: waiting
+                .iter()
+                .map(|machine| (machine.name.clone(), machine.computer.pc))
+                .collect(),
+            halted: halted.iter().map(|machine| machine.name.clone()).collect(),
+            instructions: self
+                .machines
+                .iter()
+                .map(|machine| machine.instructions)
+                .sum(),
+        }
+    }
+}
+
+impl std::fmt::Display for Deadlock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "deadlock after {} instructions, every running machine waits for input:",
+            self.instructions
+        )?;
+
+        for (name, pc) in &self.waiting {
+            writeln!(f, "  {} reads at {}", name, pc)?;
+        }
+
+        if !self.halted.is_empty() {
+            writeln!(f, "halted: {}", self.halted.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Deadlock,
+        Scheduler,
+        SchedulerError,
+    };
+    use crate::intcode::{
+        computer::{
+            Computer,
+            Reader,
+            Recording,
+            Writer,
+        },
+        report::HaltReason,
+    };
+    use crossbeam_channel::unbounded;
+    use pretty_assertions::assert_eq;
+
+    /// Writes every value it reads until it reads a 0.
+    const ECHO: &[isize] = &[3, 10, 4, 10, 1005, 10, 0, 99];
+
+    #[test]
+    fn scheduler_amplifiers_loop() {
+        let software = vec![
+            3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1,
+            28, 1005, 28, 6, 99, 0, 0, 5,
+        ];
+        let phase_settings = vec![9, 8, 7, 6, 5];
+        let count = phase_settings.len();
+
+        let channels = (0..count).map(|_| unbounded()).collect::<Vec<_>>();
+        let thrusters = Recording::default();
+
+        let mut scheduler = phase_settings.into_iter().enumerate().fold(
+            Scheduler::default().with_quantum(3),
+            |scheduler, (index, phase_setting)| {
+                let mut prefix = vec![phase_setting];
+
+                if index == 0 {
+                    prefix.push(0);
+                }
+
+                let input = Reader::chain(prefix, Reader::Channel(channels[index].1.clone()));
+                let mut output = Writer::Channel(channels[(index + 1) % count].0.clone());
+
+                if index == count - 1 {
+                    output = output.record(&thrusters);
+                }
+
+                let computer = Computer::default()
+                    .with_software(software.clone())
+                    .with_input(input)
+                    .with_output(output);
+
+                scheduler.with_machine(format!("amplifier {}", index), computer)
+            },
+        );
+
+        let reports = scheduler.run().unwrap();
+
+        assert_eq!(5, reports.len());
+        assert!(reports.iter().all(|report| report.halt == HaltReason::Halt));
+        assert_eq!(Some(&139_629_729), thrusters.values().last());
+    }
+
+    #[test]
+    fn scheduler_deadlock() {
+        let (sender_a, receiver_a) = unbounded();
+        let (sender_b, receiver_b) = unbounded();
+        let (sender_sink, _receiver_sink) = unbounded();
+
+        // `a` echoes its only value to `b` which echoes it to nobody, then
+        // both wait for more.
+        sender_a.send(5).unwrap();
+
+        let mut scheduler = Scheduler::default()
+            .with_machine(
+                "a",
+                Computer::default()
+                    .with_software(ECHO.to_vec())
+                    .with_input(Reader::Channel(receiver_a))
+                    .with_output(Writer::Channel(sender_b)),
+            )
+            .with_machine(
+                "b",
+                Computer::default()
+                    .with_software(ECHO.to_vec())
+                    .with_input(Reader::Channel(receiver_b))
+                    .with_output(Writer::Channel(sender_sink)),
+            )
+            .with_machine("done", Computer::default().with_software(vec![99]));
+
+        match scheduler.run() {
+            Err(SchedulerError::Deadlock(deadlock)) => {
+                assert_eq!(
+                    Deadlock {
+                        waiting: vec![("a".to_string(), 0), ("b".to_string(), 0)],
+                        halted: vec!["done".to_string()],
+                        instructions: 7,
+                    },
+                    deadlock
+                );
+
+                assert_eq!(
+                    "deadlock after 7 instructions, every running machine waits for input:\n  \
+                     a reads at 0\n  b reads at 0\nhalted: done\n",
+                    deadlock.to_string()
+                );
+            }
+            got => panic!("unexpected result: {:?}", got.map(|_| ())),
+        }
+
+        drop(sender_a);
+    }
+}
@@ -0,0 +1,351 @@
+//! Window that shows the memory of a running computer as a heatmap.
+//!
+//! Every cell is a square in a grid, colored by its value. Cells written in
+//! the last frames are highlighted, `pc` is framed white and the relative
+//! base magenta.
+//!
+//! Space pauses and resumes, `S` executes a single instruction while paused
+//! and Up/Down double or halve the instructions executed per frame.
+
+use super::{
+    computer::Computer,
+    debugger::{
+        Debugger,
+        Stop,
+    },
+};
+use ggez::{
+    self,
+    event::{
+        self,
+        KeyCode,
+        KeyMods,
+    },
+    graphics::{
+        self,
+        Color,
+        DrawMode,
+        MeshBuilder,
+        Rect,
+        Text,
+    },
+    nalgebra as na,
+    timer,
+    Context,
+    GameResult,
+};
+use std::collections::BTreeMap;
+
+const COLUMNS: usize = 64;
+const CELL_SIZE: f32 = 12.0;
+const STATUS_HEIGHT: f32 = 30.0;
+const FRAMES_PER_SECOND: u32 = 30;
+/// Number of frames a written cell stays highlighted.
+const HIGHLIGHT_FRAMES: usize = 15;
+const MAX_SPEED: usize = 1 << 20;
+
+const PC_COLOR: Color = Color {
+    r: 1.0,
+    g: 1.0,
+    b: 1.0,
+    a: 1.0,
+};
+
+const RELATIVE_BASE_COLOR: Color = Color {
+    r: 1.0,
+    g: 0.0,
+    b: 1.0,
+    a: 1.0,
+};
+
+pub struct Visualizer {
+    debugger: Debugger,
+    paused: bool,
+    /// Instructions executed per frame.
+    speed: usize,
+    frame: usize,
+    /// Frame each address was last written in.
+    writes: BTreeMap<usize, usize>,
+    /// Memory as of the end of the previous frame to find the writes.
+    snapshot: BTreeMap<usize, isize>,
+    status: String,
+}
+
+impl Visualizer {
+    pub fn new(computer: Computer) -> Self {
+        let snapshot = computer.memory.clone();
+
+        Self {
+            debugger: Debugger::new(computer),
+            paused: false,
+            speed: 16,
+            frame: 0,
+            writes: BTreeMap::new(),
+            snapshot,
+            status: "running".to_string(),
+        }
+    }
+
+    pub fn run(self) {
+        let cells = self
+            .snapshot
+            .keys()
+            .next_back()
+            .map_or(0, |address| address + 1);
+        let rows = cells.div_ceil(COLUMNS);
+
+        let window_setup = ggez::conf::WindowSetup {
+            title: "Intcode memory".to_owned(),
+            samples: ggez::conf::NumSamples::Zero,
+            vsync: true,
+            icon: "".to_owned(),
+            srgb: true,
+        };
+
+        let window_mode = ggez::conf::WindowMode {
+            width: COLUMNS as f32 * CELL_SIZE,
+            height: (rows + 1) as f32 * CELL_SIZE + STATUS_HEIGHT,
+            maximized: false,
+            fullscreen_type: ggez::conf::FullscreenType::Windowed,
+            borderless: false,
+            min_width: 0.0,
+            max_width: 0.0,
+            min_height: 0.0,
+            max_height: 0.0,
+            resizable: true,
+        };
+
+        let context_builder = ggez::ContextBuilder::new("intcode_memory", "ggez")
+            .window_setup(window_setup)
+            .window_mode(window_mode);
+
+        let (context, events_loop) = &mut context_builder.build().unwrap();
+
+        let mut state = self;
+        event::run(context, events_loop, &mut state).unwrap();
+    }
+
+    /// Executes the instructions of one frame unless paused and marks the
+    /// cells that changed.
+    pub fn tick(&mut self) {
+        if !self.paused {
+            self.execute(self.speed);
+        }
+
+        self.frame += 1;
+    }
+
+    /// Executes a single instruction.
+    pub fn step(&mut self) {
+        self.execute(1);
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    pub fn faster(&mut self) {
+        self.speed = (self.speed * 2).min(MAX_SPEED);
+    }
+
+    pub fn slower(&mut self) {
+        self.speed = (self.speed / 2).max(1);
+    }
+
+    /// Addresses that were written in the last frames and how many frames
+    /// ago.
+    pub fn recent_writes(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.writes
+            .iter()
+            .map(move |(address, frame)| (*address, self.frame - frame))
+            .filter(|(_, age)| *age < HIGHLIGHT_FRAMES)
+    }
+
+    fn execute(&mut self, instructions: usize) {
+        for _ in 0..instructions {
+            match self.debugger.step() {
+                Stop::Step => {}
+                Stop::Halt(halt) => self.status = format!("halted: {:?}", halt),
+                Stop::Error(err) => self.status = format!("failed: {:?}", err),
+                _ => {}
+            }
+
+            if self.debugger.is_finished() {
+                break;
+            }
+        }
+
+        let memory = &self.debugger.computer.memory;
+
+        for (address, value) in memory {
+            if self.snapshot.get(address) != Some(value) {
+                self.writes.insert(*address, self.frame);
+            }
+        }
+
+        self.snapshot.clone_from(memory);
+    }
+
+    fn cell(address: usize) -> Rect {
+        Rect {
+            x: (address % COLUMNS) as f32 * CELL_SIZE,
+            y: STATUS_HEIGHT + (address / COLUMNS) as f32 * CELL_SIZE,
+            w: CELL_SIZE,
+            h: CELL_SIZE,
+        }
+    }
+}
+
+/// Color of a value in the heatmap. Zero is black, positive values go from
+/// dark red to yellow and negative values from dark blue to cyan with the
+/// magnitude of the value.
+pub fn heat(value: isize) -> Color {
+    if value == 0 {
+        return Color {
+            r: 0.0,
+            g: 0.0,
+            b: 0.0,
+            a: 1.0,
+        };
+    }
+
+    // Values up to about a million spread over the scale.
+    let magnitude = ((value.unsigned_abs() as f32).log10() / 6.0).min(1.0);
+    let intensity = 0.3 + 0.7 * magnitude;
+
+    if value > 0 {
+        Color {
+            r: intensity,
+            g: magnitude,
+            b: 0.0,
+            a: 1.0,
+        }
+    } else {
+        Color {
+            r: 0.0,
+            g: magnitude,
+            b: intensity,
+            a: 1.0,
+        }
+    }
+}
+
+impl event::EventHandler for Visualizer {
+    fn update(&mut self, context: &mut Context) -> GameResult {
+        while timer::check_update_time(context, FRAMES_PER_SECOND) {
+            self.tick();
+        }
+
+        Ok(())
+    }
+
+    fn draw(&mut self, context: &mut Context) -> GameResult {
+        graphics::clear(context, [0.1, 0.1, 0.1, 1.0].into());
+
+        let computer = &self.debugger.computer;
+        let mut mesh = MeshBuilder::new();
+
+        for (address, value) in &computer.memory {
+            mesh.rectangle(DrawMode::fill(), Self::cell(*address), heat(*value));
+        }
+
+        for (address, age) in self.recent_writes() {
+            let alpha = 1.0 - age as f32 / HIGHLIGHT_FRAMES as f32;
+
+            mesh.rectangle(
+                DrawMode::fill(),
+                Self::cell(address),
+                Color {
+                    r: 1.0,
+                    g: 1.0,
+                    b: 0.0,
+                    a: 0.8 * alpha,
+                },
+            );
+        }
+
+        mesh.rectangle(DrawMode::stroke(2.0), Self::cell(computer.pc), PC_COLOR);
+
+        if computer.relative_base >= 0 {
+            mesh.rectangle(
+                DrawMode::stroke(2.0),
+                Self::cell(computer.relative_base as usize),
+                RELATIVE_BASE_COLOR,
+            );
+        }
+
+        let mesh = mesh.build(context)?;
+        graphics::draw(context, &mesh, (na::Point2::new(0.0, 0.0),))?;
+
+        let state = if self.debugger.is_finished() {
+            self.status.as_str()
+        } else if self.paused {
+            "paused"
+        } else {
+            "running"
+        };
+
+        let status = Text::new(format!(
+            "pc {}  relative base {}  {} instructions/frame  {}",
+            computer.pc, computer.relative_base, self.speed, state
+        ));
+        graphics::draw(context, &status, (na::Point2::new(4.0, 4.0), PC_COLOR))?;
+
+        graphics::present(context)?;
+
+        Ok(())
+    }
+
+    fn key_up_event(&mut self, context: &mut Context, keycode: KeyCode, _keymod: KeyMods) {
+        match keycode {
+            KeyCode::Space => self.toggle_pause(),
+            KeyCode::S if self.paused => self.step(),
+            KeyCode::Up => self.faster(),
+            KeyCode::Down => self.slower(),
+            KeyCode::Escape => event::quit(context),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        heat,
+        Visualizer,
+    };
+    use crate::intcode::computer::Computer;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn visualizer_heat() {
+        let black = heat(0);
+        assert_eq!((0.0, 0.0, 0.0), (black.r, black.g, black.b));
+
+        assert!(heat(1).r > 0.0 && heat(1).b == 0.0);
+        assert!(heat(-1).b > 0.0 && heat(-1).r == 0.0);
+        assert!(heat(1_000).g > heat(10).g);
+    }
+
+    #[test]
+    fn visualizer_tracks_writes() {
+        // Adds 1 to address 9 twice.
+        let software = vec![1001, 9, 1, 9, 1001, 9, 1, 9, 99, 0];
+        let mut visualizer = Visualizer::new(Computer::default().with_software(software));
+
+        visualizer.toggle_pause();
+        visualizer.tick();
+        assert_eq!(0, visualizer.recent_writes().count());
+
+        visualizer.step();
+        visualizer.tick();
+        assert_eq!(vec![(9, 1)], visualizer.recent_writes().collect::<Vec<_>>());
+
+        visualizer.toggle_pause();
+        visualizer.tick();
+        assert_eq!(vec![(9, 1)], visualizer.recent_writes().collect::<Vec<_>>());
+        assert_eq!(Some(&2), visualizer.debugger.computer.memory.get(&9));
+        assert!(visualizer.debugger.is_finished());
+        assert_eq!("halted: Halt", visualizer.status);
+    }
+}
@@ -4,6 +4,8 @@
 
 extern crate test;
 
+pub mod bench;
+pub mod cli;
 pub mod day_01;
 pub mod day_02;
 pub mod day_03;
@@ -16,75 +18,421 @@ pub mod day_09;
 pub mod day_11;
 pub mod day_12;
 pub mod day_13;
+pub mod input;
+pub mod intcode;
+pub mod output;
+pub mod solution;
+pub mod verify;
+
+use cli::{
+    Arguments,
+    CliError,
+    Command,
+    Options,
+};
+use intcode::{
+    computer::{
+        Computer,
+        Reader,
+        Writer,
+    },
+    journal::Journal,
+    program::Program,
+};
+use input::Input;
+use output::Format;
+use solution::Part;
+use std::{
+    path::{
+        Path,
+        PathBuf,
+    },
+    time::Instant,
+};
 
 fn main() {
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn"))
+    let arguments = match cli::parse(std::env::args().skip(1)) {
+        Ok(arguments) => arguments,
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let level = if arguments.options.quiet {
+        "error"
+    } else {
+        "warn"
+    };
+
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(level))
         .format_timestamp_nanos()
         .init();
 
-    let args = std::env::args();
-    let mut args = args.skip(1);
+    if let Err(err) = run(arguments) {
+        eprintln!("error: {}", err);
+        std::process::exit(if err.is_usage() { 2 } else { 1 });
+    }
+}
 
-    let day = args.next().unwrap_or_default();
+fn run(arguments: Arguments) -> Result<(), CliError> {
+    let Arguments { command, options } = arguments;
 
-    match day.as_str() {
-        "day_01" => {
-            dbg!(day_01::part_1::calculate_ship_fuel_requirement());
-            dbg!(day_01::part_2::calculate_compensated_ship_fuel_requirement());
-        }
+    match command {
+        Command::Run { day, part, journal } => {
+            let days = days(day, &options)?;
+            let parts = parts(part);
 
-        "day_02" => {
-            dbg!(day_02::part_1::restore_gravity_assist_program());
-            dbg!(day_02::part_2::what().unwrap());
-        }
+            for day in days.iter().copied() {
+                let input = load_input(&options, day)?;
+
+                let entry =
+                    solution::get(day).ok_or_else(|| CliError::UnknownDay(day.to_string()))?;
+                let mut solution = entry.parse(&input.text)?;
+                solution.set_visualize(options.visualize);
+
+                for part in parts.iter().copied() {
+                    let recorded = (0..solution.computers())
+                        .map(|_| Journal::default())
+                        .collect::<Vec<_>>();
+
+                    if journal.is_some() {
+                        solution.set_journals(&recorded);
+                    }
+
+                    let start = Instant::now();
+                    let answer = solution.part(part)?;
+                    let elapsed = start.elapsed();
+
+                    if let Some(path) = &journal {
+                        let started = recorded
+                            .iter()
+                            .filter(|journal| !journal.software().is_empty())
+                            .count();
+
+                        if started == 0 {
+                            log::warn!("day {} part {} runs no computer to journal", day, part);
+                        }
+
+                        for (computer, recorded) in recorded.iter().enumerate() {
+                            if recorded.software().is_empty() {
+                                continue;
+                            }
+
+                            let path = journal_path(
+                                path,
+                                if days.len() > 1 { Some(day) } else { None },
+                                if parts.len() > 1 { Some(part) } else { None },
+                                if started > 1 { Some(computer) } else { None },
+                            );
+                            recorded.save(path).map_err(failed)?;
+                        }
+                    }
+
+                    if options.format == Format::Json {
+                        println!("{}", output::answer(day, part, &answer, elapsed));
+                        continue;
+                    }
+
+                    let answer = answer.to_string();
 
-        "day_03" => {
-            dbg!(day_03::closest_intersection());
-            dbg!(day_03::closest_intersection_draw());
+                    if options.quiet {
+                        println!("{}", answer);
+                    } else if answer.contains('\n') {
+                        println!("day {} part {}:\n{}", day, part, answer);
+                    } else {
+                        println!("day {} part {}: {}", day, part, answer);
+                    }
+                }
+            }
         }
 
-        "day_04" => {
-            dbg!(day_04::part_1::count_passwords());
-            dbg!(day_04::part_2::count_passwords());
+        Command::List => {
+            for day in solution::days() {
+                println!("day_{:02}", day);
+            }
         }
 
-        "day_05" => {
-            day_05::part_1::run();
-            day_05::part_2::run();
+        Command::Bench {
+            day,
+            part,
+            runs,
+            save,
+            baseline,
+            threshold,
+        } => {
+            let baseline = baseline.map(bench::Baseline::load).transpose()?;
+            let mut timings = Vec::new();
+            let mut regressions = 0;
+
+            for day in days(day, &options)? {
+                let input = load_input(&options, day)?;
+                let entry =
+                    solution::get(day).ok_or_else(|| CliError::UnknownDay(day.to_string()))?;
+
+                for part in parts(part) {
+                    let timing = bench::measure(entry, &input.text, part, runs)?;
+
+                    match &baseline {
+                        Some(baseline) => {
+                            let comparison = baseline.compare(&[timing])[0];
+                            let regression = comparison.is_regression(threshold);
+
+                            if regression {
+                                regressions += 1;
+                            }
+
+                            match options.format {
+                                Format::Json => println!("{}", comparison.to_json(threshold)),
+                                Format::Text if regression => {
+                                    println!("{} regression", comparison)
+                                }
+                                Format::Text => println!("{}", comparison),
+                            }
+                        }
+                        None => match options.format {
+                            Format::Json => println!("{}", timing.to_json()),
+                            Format::Text => println!("{}", timing),
+                        },
+                    }
+
+                    timings.push(timing);
+                }
+            }
+
+            if let Some(path) = save {
+                bench::Baseline { runs, timings }.save(path)?;
+            }
+
+            if regressions > 0 {
+                return Err(CliError::Failed(format!(
+                    "{} regressions slower than {}% over the baseline",
+                    regressions, threshold
+                )));
+            }
         }
 
-        "day_07" => {
-            day_07::part_1::run();
-            day_07::part_2::run();
+        Command::Verify {
+            day,
+            part,
+            answers,
+            save,
+        } => {
+            let directory = options
+                .inputs
+                .clone()
+                .unwrap_or_else(|| input::DIRECTORY.into());
+
+            let mut expected = match answers {
+                Some(path) => verify::Expected::load(path)?,
+                None if directory.join(verify::FILE).is_file() => {
+                    verify::Expected::load(directory.join(verify::FILE))?
+                }
+                None => verify::Expected::default(),
+            };
+
+            let mut outcomes = Vec::new();
+
+            for day in days(day, &options)? {
+                let input = load_input(&options, day)?;
+                let entry =
+                    solution::get(day).ok_or_else(|| CliError::UnknownDay(day.to_string()))?;
+
+                for part in parts(part) {
+                    let outcome = verify::verify(entry, &input.text, part, &expected);
+
+                    match options.format {
+                        Format::Json => println!("{}", outcome.to_json()),
+                        Format::Text => println!("{}", outcome),
+                    }
+
+                    outcomes.push(outcome);
+                }
+            }
+
+            let count = |status: fn(&verify::Status) -> bool| {
+                outcomes
+                    .iter()
+                    .filter(|outcome| status(&outcome.status))
+                    .count()
+            };
+            let failed = outcomes
+                .iter()
+                .filter(|outcome| outcome.is_failure())
+                .count();
+
+            if options.format == Format::Text {
+                println!(
+                    "{} passed, {} failed, {} missing",
+                    count(|status| matches!(status, verify::Status::Pass(_))),
+                    failed,
+                    count(|status| matches!(status, verify::Status::Missing(_)))
+                );
+            }
+
+            if let Some(path) = save {
+                expected.record(&outcomes);
+                expected.save(path)?;
+            }
+
+            // Saving records the answers found, but they still failed.
+            if failed > 0 {
+                return Err(CliError::Failed(format!(
+                    "{} answers differ from the expected ones",
+                    failed
+                )));
+            }
         }
 
-        "day_08" => {
-            day_08::part_1::run();
-            day_08::part_2::run();
+        Command::Help => println!("{}", cli::USAGE),
+
+        Command::Dap(address) => match address {
+            Some(address) => intcode::dap::serve_tcp(address).map_err(failed)?,
+            None => intcode::dap::serve_stdio().map_err(failed)?,
+        },
+
+        Command::Gdb {
+            program,
+            address,
+            inputs,
+        } => {
+            let address = address.unwrap_or_else(|| "127.0.0.1:1234".to_string());
+
+            let computer = Computer::default()
+                .with_software(load(program)?.into())
+                .with_input(Reader::Tester(Box::new(inputs.into_iter())))
+                .with_output(Writer::Tester { values: Vec::new() }.map(|value| {
+                    println!("{}", value);
+                    value
+                }));
+
+            intcode::gdb::serve_tcp(address, computer).map_err(failed)?;
         }
 
-        "day_09" => {
-            day_09::part_1::run();
-            day_09::part_2::run();
+        Command::Visualize(program) => {
+            let software = match program {
+                Some(path) => load(path)?,
+                None => day_13::arcade::software(),
+            };
+
+            let computer = Computer::default()
+                .with_software(software.into())
+                .with_input(Reader::Tester(Box::new(std::iter::repeat(0))));
+
+            intcode::visualizer::Visualizer::new(computer).run();
         }
 
-        // "day_10" => {
-        //     day_10::part_1::run();
-        //     day_10::part_2::run();
-        // }
-        "day_11" => {
-            day_11::part_1::run();
-            day_11::part_2::run();
+        Command::Decompile(program) => {
+            let software = match program {
+                Some(path) => load(path)?,
+                None => day_13::arcade::software(),
+            };
+
+            print!("{}", intcode::decompiler::decompile(&Vec::from(software)));
         }
 
-        "day_12" => {
-            day_12::part_1::run();
-            day_12::part_2::run();
+        Command::Diagnostics => {
+            let test = load_input(&options, 5)?.text.parse().map_err(failed)?;
+            let boost = load_input(&options, 9)?.text.parse().map_err(failed)?;
+
+            let diagnostics =
+                intcode::diagnostics::Diagnostics::run(Computer::default, &test, &boost);
+            print!("{}", diagnostics);
+
+            if !diagnostics.passed() {
+                return Err(CliError::Failed(
+                    "the computer failed its self-tests".to_string(),
+                ));
+            }
         }
 
-        _ => {
-            day_13::part_1::run();
-            day_13::part_2::run();
+        Command::Replay(path) => {
+            let journal = intcode::journal::Journal::load(path).map_err(failed)?;
+
+            match journal.replay() {
+                Ok(report) => println!(
+                    "replayed {} instructions with {} inputs and {} outputs",
+                    report.instructions,
+                    report.inputs.len(),
+                    report.outputs.len()
+                ),
+                Err(err) => return Err(CliError::Failed(format!("replay failed: {:?}", err))),
+            }
         }
     }
+
+    Ok(())
+}
+
+/// Every day unless only one is asked for. `--input` needs a day.
+fn days(day: Option<usize>, options: &Options) -> Result<Vec<usize>, CliError> {
+    match day {
+        Some(day) => Ok(vec![day]),
+        None if options.input.is_some() => Err(CliError::MissingArgument("day")),
+        None => Ok(solution::days().collect()),
+    }
+}
+
+/// Both parts unless only one is asked for.
+fn parts(part: Option<Part>) -> Vec<Part> {
+    match part {
+        Some(part) => vec![part],
+        None => Part::ALL.to_vec(),
+    }
+}
+
+/// `<path>` with the day, part and computer if given before its extension,
+/// like `runs.day_07.part_2.computer_4.journal`.
+fn journal_path(
+    path: &Path,
+    day: Option<usize>,
+    part: Option<Part>,
+    computer: Option<usize>,
+) -> PathBuf {
+    let mut name = path.file_stem().unwrap_or_default().to_os_string();
+
+    if let Some(day) = day {
+        name.push(format!(".day_{:02}", day));
+    }
+
+    if let Some(part) = part {
+        name.push(format!(".part_{}", part.number()));
+    }
+
+    if let Some(computer) = computer {
+        name.push(format!(".computer_{}", computer));
+    }
+
+    if let Some(extension) = path.extension() {
+        name.push(".");
+        name.push(extension);
+    }
+
+    path.with_file_name(name)
+}
+
+/// The input of a day given by `--input`, from `--inputs` or the default
+/// directory, or compiled into the binary.
+fn load_input(options: &Options, day: usize) -> Result<Input, CliError> {
+    let input = match &options.input {
+        Some(path) => input::read(path, day)?,
+        None => input::load(
+            options
+                .inputs
+                .as_deref()
+                .unwrap_or_else(|| input::DIRECTORY.as_ref()),
+            day,
+        )?,
+    };
+    log::info!("day {} reads {}", day, input.source);
+
+    Ok(input)
+}
+
+fn load(path: std::path::PathBuf) -> Result<Program, CliError> {
+    Program::from_file(path).map_err(failed)
+}
+
+fn failed<E: std::fmt::Debug>(err: E) -> CliError {
+    CliError::Failed(format!("{:?}", err))
 }
@@ -0,0 +1,108 @@
+//! Output formats of the commands that solve days.
+//!
+//! The JSON format prints one record per line, one for every day and part:
+//!
+//! ```json
+//! {"answer":null,"day":8,"elapsed_ns":1096812,"part":2,"render":"█  █ ..."}
+//! ```
+
+use crate::solution::{
+    Answer,
+    Part,
+};
+use serde_json::{
+    json,
+    Value,
+};
+use std::time::Duration;
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum Format {
+    #[default]
+    Text,
+    /// One JSON record per line.
+    Json,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(s.to_string()),
+        }
+    }
+}
+
+/// Record of the answer to a part. Renders like the day 8 image go to
+/// `render` instead of `answer`. `elapsed` is the time it took to solve the
+/// part of the parsed input.
+pub fn answer(day: usize, part: Part, answer: &Answer, elapsed: Duration) -> Value {
+    let (number, render) = match answer {
+        Answer::Number(value) => (Some(*value), None),
+        Answer::Render(text) => (None, Some(text)),
+    };
+
+    json!({
+        "day": day,
+        "part": part.number(),
+        "answer": number,
+        "render": render,
+        "elapsed_ns": elapsed.as_nanos() as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Format;
+    use crate::solution::{
+        Answer,
+        Part,
+    };
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use std::time::Duration;
+
+    #[test]
+    fn parse_format() {
+        assert_eq!(Ok(Format::Json), "json".parse());
+        assert_eq!(Ok(Format::Text), "text".parse());
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn answer() {
+        assert_eq!(
+            json!({
+                "day": 1,
+                "part": 1,
+                "answer": 3454942,
+                "render": null,
+                "elapsed_ns": 1500,
+            }),
+            super::answer(
+                1,
+                Part::One,
+                &Answer::Number(3454942),
+                Duration::from_nanos(1500)
+            )
+        );
+        assert_eq!(
+            json!({
+                "day": 11,
+                "part": 2,
+                "answer": null,
+                "render": " #  #\n ####",
+                "elapsed_ns": 2000000,
+            }),
+            super::answer(
+                11,
+                Part::Two,
+                &Answer::Render(" #  #\n ####".to_string()),
+                Duration::from_millis(2)
+            )
+        );
+    }
+}
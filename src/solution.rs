@@ -0,0 +1,308 @@
+//! Common interface of the solutions of every day.
+//!
+//! A day parses its puzzle input once and answers both parts from it. The
+//! registry maps day numbers to their solution so that the binary, the tests
+//! and the benchmarks run every day the same way.
+
+use crate::{
+    day_01,
+    day_02,
+    day_03,
+    day_04,
+    day_05,
+    day_07,
+    day_08,
+    day_09,
+    day_11,
+    day_12,
+    day_13,
+    intcode::{
+        codec::CodecError,
+        journal::Journal,
+        program::ParseProgramError,
+    },
+};
+
+pub trait Solution {
+    /// Parses the puzzle input.
+    fn parse(input: &str) -> Result<Self, SolutionError>
+    where
+        Self: Sized;
+
+    fn part_one(&self) -> Result<Answer, SolutionError>;
+
+    fn part_two(&self) -> Result<Answer, SolutionError>;
+
+    /// Opens canvases while solving. Only some days draw.
+    fn set_visualize(&mut self, _visualize: bool) {}
+
+    /// Number of computers a part runs, each recorded in its own journal.
+    fn computers(&self) -> usize {
+        1
+    }
+
+    /// Records the run of every computer in its journal. Only parts that run
+    /// computers record.
+    fn set_journals(&mut self, _journals: &[Journal]) {}
+
+    fn part(&self, part: Part) -> Result<Answer, SolutionError> {
+        match part {
+            Part::One => self.part_one(),
+            Part::Two => self.part_two(),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    /// Part `1` or `2`.
+    pub fn from_number(number: usize) -> Option<Self> {
+        match number {
+            1 => Some(Self::One),
+            2 => Some(Self::Two),
+            _ => None,
+        }
+    }
+
+    pub fn number(self) -> usize {
+        match self {
+            Self::One => 1,
+            Self::Two => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Answer {
+    Number(isize),
+    /// Text drawn by the puzzle, like letters on a screen.
+    Render(String),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SolutionError {
+    InvalidInput(String),
+    /// The input parsed but the solution found no answer for it.
+    NoAnswer(String),
+}
+
+/// A day in the registry.
+pub struct Entry {
+    pub day: usize,
+    parse: fn(&str) -> Result<Box<dyn Solution>, SolutionError>,
+}
+
+impl Entry {
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Solution>, SolutionError> {
+        (self.parse)(input)
+    }
+}
+
+/// Every day with a solution, ordered by day.
+pub const REGISTRY: &[Entry] = &[
+    Entry {
+        day: 1,
+        parse: boxed::<day_01::Day01>,
+    },
+    Entry {
+        day: 2,
+        parse: boxed::<day_02::Day02>,
+    },
+    Entry {
+        day: 3,
+        parse: boxed::<day_03::Day03>,
+    },
+    Entry {
+        day: 4,
+        parse: boxed::<day_04::Day04>,
+    },
+    Entry {
+        day: 5,
+        parse: boxed::<day_05::Day05>,
+    },
+    Entry {
+        day: 7,
+        parse: boxed::<day_07::Day07>,
+    },
+    Entry {
+        day: 8,
+        parse: boxed::<day_08::Day08>,
+    },
+    Entry {
+        day: 9,
+        parse: boxed::<day_09::Day09>,
+    },
+    Entry {
+        day: 11,
+        parse: boxed::<day_11::Day11>,
+    },
+    Entry {
+        day: 12,
+        parse: boxed::<day_12::Day12>,
+    },
+    Entry {
+        day: 13,
+        parse: boxed::<day_13::Day13>,
+    },
+];
+
+fn boxed<S: Solution + 'static>(input: &str) -> Result<Box<dyn Solution>, SolutionError> {
+    Ok(Box::new(S::parse(input)?))
+}
+
+pub fn get(day: usize) -> Option<&'static Entry> {
+    REGISTRY.iter().find(|entry| entry.day == day)
+}
+
+pub fn days() -> impl Iterator<Item = usize> {
+    REGISTRY.iter().map(|entry| entry.day)
+}
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Self::Number(value as isize)
+    }
+}
+
+impl From<ParseProgramError> for SolutionError {
+    fn from(err: ParseProgramError) -> Self {
+        Self::InvalidInput(format!("{:?}", err))
+    }
+}
+
+impl From<std::num::ParseIntError> for SolutionError {
+    fn from(err: std::num::ParseIntError) -> Self {
+        Self::InvalidInput(err.to_string())
+    }
+}
+
+impl From<CodecError> for SolutionError {
+    fn from(err: CodecError) -> Self {
+        Self::NoAnswer(format!("{:?}", err))
+    }
+}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(value) => write!(f, "{}", value),
+            Self::Render(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+impl std::fmt::Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl std::fmt::Display for SolutionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidInput(message) => write!(f, "invalid input: {}", message),
+            Self::NoAnswer(message) => write!(f, "no answer: {}", message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        get,
+        Answer,
+        Part,
+        SolutionError,
+        REGISTRY,
+    };
+    use crate::input;
+    use pretty_assertions::assert_eq;
+
+    fn solve(day: usize, input: &str, part: Part) -> Result<Answer, SolutionError> {
+        get(day).unwrap().parse(input)?.part(part)
+    }
+
+    #[test]
+    fn registry_is_ordered() {
+        let days = REGISTRY.iter().map(|entry| entry.day).collect::<Vec<_>>();
+        let mut sorted = days.clone();
+        sorted.sort();
+        sorted.dedup();
+
+        assert_eq!(sorted, days);
+        assert!(get(10).is_none());
+    }
+
+    #[test]
+    fn embedded_inputs_parse() {
+        for entry in REGISTRY {
+            let input = input::embedded(entry.day).unwrap();
+
+            if let Err(err) = entry.parse(input) {
+                panic!("day {}: {}", entry.day, err);
+            }
+        }
+    }
+
+    #[test]
+    fn examples() {
+        assert_eq!(
+            Ok(Answer::Number(34241)),
+            solve(1, "12\n14\n1969\n100756\n", Part::One)
+        );
+        assert_eq!(
+            Ok(Answer::Number(51316)),
+            solve(1, "12\n14\n1969\n100756\n", Part::Two)
+        );
+
+        let wires = "R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83\n";
+        assert_eq!(Ok(Answer::Number(159)), solve(3, wires, Part::One));
+        assert_eq!(Ok(Answer::Number(610)), solve(3, wires, Part::Two));
+
+        assert_eq!(Ok(Answer::Number(0)), solve(4, "111110-111110", Part::Two));
+
+        let moons = "<x=-1, y=0, z=2>\n<x=2, y=-10, z=-7>\n<x=4, y=-8, z=8>\n<x=3, y=5, z=-1>\n";
+        assert_eq!(Ok(Answer::Number(2772)), solve(12, moons, Part::Two));
+    }
+
+    #[test]
+    fn invalid_inputs() {
+        assert!(matches!(
+            solve(1, "twelve", Part::One),
+            Err(SolutionError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            solve(3, "R8,U5", Part::One),
+            Err(SolutionError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            solve(3, "R8,U5\nX3", Part::One),
+            Err(SolutionError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            solve(4, "153517", Part::One),
+            Err(SolutionError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            solve(8, "0123", Part::One),
+            Err(SolutionError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            solve(13, "1,,2", Part::One),
+            Err(SolutionError::InvalidInput(_))
+        ));
+    }
+}
@@ -0,0 +1,372 @@
+//! Verification of answers against recorded expected answers.
+//!
+//! The expected answers of the inputs live next to them in
+//! `inputs/answers.json`. Renders are recorded as strings:
+//!
+//! ```json
+//! {
+//!   "answers": [
+//!     { "day": 1, "part": 1, "answer": 3454942 },
+//!     { "day": 8, "part": 2, "answer": "█  █ ..." }
+//!   ]
+//! }
+//! ```
+
+use crate::solution::{
+    Answer,
+    Entry,
+    Part,
+    SolutionError,
+};
+use serde_json::{
+    json,
+    Value,
+};
+use std::{
+    collections::BTreeMap,
+    path::Path,
+};
+
+/// File with the expected answers in the directory of the inputs.
+pub const FILE: &str = "answers.json";
+
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Expected {
+    pub answers: BTreeMap<(usize, Part), Answer>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Status {
+    /// The answer found is the expected one.
+    Pass(Answer),
+    Fail {
+        expected: Answer,
+        found: Answer,
+    },
+    /// There is no expected answer for the answer found.
+    Missing(Answer),
+    Error(SolutionError),
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Outcome {
+    pub day: usize,
+    pub part: Part,
+    pub status: Status,
+}
+
+#[derive(Debug)]
+pub enum VerifyError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+    InvalidAnswers(String),
+}
+
+impl From<std::io::Error> for VerifyError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for VerifyError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err)
+    }
+}
+
+impl std::fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{}", err),
+            Self::Json(err) => write!(f, "invalid answers: {}", err),
+            Self::InvalidAnswers(message) => write!(f, "invalid answers: {}", message),
+        }
+    }
+}
+
+/// Solves `part` of `input` and compares the answer with the expected one.
+pub fn verify(entry: &Entry, input: &str, part: Part, expected: &Expected) -> Outcome {
+    let status = match entry.parse(input).and_then(|solution| solution.part(part)) {
+        Ok(found) => expected.check(entry.day, part, found),
+        Err(err) => Status::Error(err),
+    };
+
+    Outcome {
+        day: entry.day,
+        part,
+        status,
+    }
+}
+
+impl Expected {
+    pub fn get(&self, day: usize, part: Part) -> Option<&Answer> {
+        self.answers.get(&(day, part))
+    }
+
+    pub fn check(&self, day: usize, part: Part, found: Answer) -> Status {
+        match self.get(day, part) {
+            Some(expected) if *expected == found => Status::Pass(found),
+            Some(expected) => Status::Fail {
+                expected: expected.clone(),
+                found,
+            },
+            None => Status::Missing(found),
+        }
+    }
+
+    /// Records the answers found by `outcomes` as the expected ones.
+    pub fn record(&mut self, outcomes: &[Outcome]) {
+        for outcome in outcomes {
+            if let Status::Fail { found, .. } | Status::Missing(found) = &outcome.status {
+                self.answers
+                    .insert((outcome.day, outcome.part), found.clone());
+            }
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let answers = self
+            .answers
+            .iter()
+            .map(|((day, part), answer)| {
+                json!({ "day": day, "part": part.number(), "answer": answer_to_json(answer) })
+            })
+            .collect::<Vec<_>>();
+
+        json!({ "answers": answers })
+    }
+
+    pub fn from_json(value: &Value) -> Result<Self, VerifyError> {
+        let invalid =
+            |value: &Value| VerifyError::InvalidAnswers(format!("invalid answer {}", value));
+
+        let answers = value["answers"]
+            .as_array()
+            .ok_or_else(|| VerifyError::InvalidAnswers("missing `answers`".to_string()))?;
+
+        Ok(Self {
+            answers: answers
+                .iter()
+                .map(|value| {
+                    let day = value["day"].as_u64().ok_or_else(|| invalid(value))? as usize;
+                    let part = value["part"]
+                        .as_u64()
+                        .and_then(|part| Part::from_number(part as usize))
+                        .ok_or_else(|| invalid(value))?;
+                    let answer = match &value["answer"] {
+                        Value::String(text) => Answer::Render(text.clone()),
+                        answer => {
+                            Answer::Number(answer.as_i64().ok_or_else(|| invalid(value))? as isize)
+                        }
+                    };
+
+                    Ok(((day, part), answer))
+                })
+                .collect::<Result<_, VerifyError>>()?,
+        })
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), VerifyError> {
+        std::fs::write(path, serde_json::to_string_pretty(&self.to_json())? + "\n")?;
+
+        Ok(())
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, VerifyError> {
+        Self::from_json(&serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+}
+
+fn answer_to_json(answer: &Answer) -> Value {
+    match answer {
+        Answer::Number(value) => json!(value),
+        Answer::Render(text) => json!(text),
+    }
+}
+
+impl Outcome {
+    /// Whether the answer differs from the expected one or solving failed.
+    pub fn is_failure(&self) -> bool {
+        matches!(self.status, Status::Fail { .. } | Status::Error(_))
+    }
+
+    pub fn to_json(&self) -> Value {
+        let (status, expected, found, error) = match &self.status {
+            Status::Pass(found) => ("pass", Some(found), Some(found), None),
+            Status::Fail { expected, found } => ("fail", Some(expected), Some(found), None),
+            Status::Missing(found) => ("missing", None, Some(found), None),
+            Status::Error(err) => ("error", None, None, Some(err.to_string())),
+        };
+
+        json!({
+            "day": self.day,
+            "part": self.part.number(),
+            "status": status,
+            "expected": expected.map(answer_to_json),
+            "found": found.map(answer_to_json),
+            "error": error,
+        })
+    }
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "day {:>2} part {}: ", self.day, self.part)?;
+
+        match &self.status {
+            Status::Pass(_) => write!(f, "pass"),
+            Status::Fail { expected, found } => {
+                if expected.to_string().contains('\n') || found.to_string().contains('\n') {
+                    write!(f, "fail, expected\n{}\nfound\n{}", expected, found)
+                } else {
+                    write!(f, "fail, expected {} found {}", expected, found)
+                }
+            }
+            Status::Missing(found) if found.to_string().contains('\n') => {
+                write!(f, "missing, found\n{}", found)
+            }
+            Status::Missing(found) => write!(f, "missing, found {}", found),
+            Status::Error(err) => write!(f, "error, {}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Expected,
+        Outcome,
+        Status,
+    };
+    use crate::solution::{
+        self,
+        Answer,
+        Part,
+        SolutionError,
+    };
+    use pretty_assertions::assert_eq;
+
+    fn expected() -> Expected {
+        let mut expected = Expected::default();
+        expected
+            .answers
+            .insert((1, Part::One), Answer::Number(34241));
+        expected
+            .answers
+            .insert((8, Part::Two), Answer::Render("█ \n █".to_string()));
+        expected
+    }
+
+    #[test]
+    fn recorded_answers_cover_every_day() {
+        let expected = Expected::from_json(
+            &serde_json::from_str(include_str!("../inputs/answers.json")).unwrap(),
+        )
+        .unwrap();
+
+        for day in solution::days() {
+            for part in Part::ALL.iter() {
+                assert!(
+                    expected.get(day, *part).is_some(),
+                    "day {} part {}",
+                    day,
+                    part
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn verify_statuses() {
+        let expected = expected();
+        let entry = solution::get(1).unwrap();
+
+        assert_eq!(
+            Status::Pass(Answer::Number(34241)),
+            super::verify(entry, "12\n14\n1969\n100756\n", Part::One, &expected).status
+        );
+        assert_eq!(
+            Status::Fail {
+                expected: Answer::Number(34241),
+                found: Answer::Number(2),
+            },
+            super::verify(entry, "12\n", Part::One, &expected).status
+        );
+        assert_eq!(
+            Status::Missing(Answer::Number(2)),
+            super::verify(entry, "12\n", Part::Two, &expected).status
+        );
+        assert!(matches!(
+            super::verify(entry, "twelve", Part::One, &expected).status,
+            Status::Error(SolutionError::InvalidInput(_))
+        ));
+    }
+
+    #[test]
+    fn record() {
+        let mut expected = expected();
+        let outcomes = vec![
+            Outcome {
+                day: 1,
+                part: Part::One,
+                status: Status::Fail {
+                    expected: Answer::Number(34241),
+                    found: Answer::Number(2),
+                },
+            },
+            Outcome {
+                day: 1,
+                part: Part::Two,
+                status: Status::Missing(Answer::Number(2)),
+            },
+        ];
+
+        assert!(outcomes[0].is_failure());
+        assert!(!outcomes[1].is_failure());
+
+        expected.record(&outcomes);
+
+        assert_eq!(Some(&Answer::Number(2)), expected.get(1, Part::One));
+        assert_eq!(Some(&Answer::Number(2)), expected.get(1, Part::Two));
+    }
+
+    #[test]
+    fn outcome_output() {
+        let pass = Outcome {
+            day: 1,
+            part: Part::One,
+            status: Status::Pass(Answer::Number(34241)),
+        };
+        let error = Outcome {
+            day: 1,
+            part: Part::One,
+            status: Status::Error(SolutionError::InvalidInput("twelve".to_string())),
+        };
+
+        assert_eq!(
+            serde_json::json!({
+                "day": 1,
+                "part": 1,
+                "status": "pass",
+                "expected": 34241,
+                "found": 34241,
+                "error": null,
+            }),
+            pass.to_json()
+        );
+        assert_eq!("day  1 part 1: pass", pass.to_string());
+        assert!(error.to_string().starts_with("day  1 part 1: error, "));
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let expected = expected();
+
+        assert_eq!(expected, Expected::from_json(&expected.to_json()).unwrap());
+        assert!(Expected::from_json(&serde_json::json!({ "answers": [{ "day": 1 }] })).is_err());
+        assert!(Expected::from_json(&serde_json::json!({
+            "answers": [{ "day": 1, "part": 3, "answer": 1 }]
+        }))
+        .is_err());
+    }
+}